@@ -175,6 +175,24 @@ if [ "$DEPLOY_TARGET" == "all" ] || [ "$DEPLOY_TARGET" == "backend" ]; then
         echo "Please install with: cargo install candid-extractor"
         exit 1
     fi
+
+    # Build admin canister (its did is what dfx.json and the frontend use)
+    echo "Building admin..."
+    if ! cargo build --target wasm32-unknown-unknown --release -p admin --locked; then
+        echo "❌ Admin build failed!"
+        exit 1
+    fi
+
+    # Extract candid interface for admin
+    echo "Extracting Candid interface for admin..."
+    if command -v candid-extractor &> /dev/null; then
+        candid-extractor target/wasm32-unknown-unknown/release/admin.wasm > src/daopad/admin/admin.did
+        echo "✓ Candid interface extracted"
+    else
+        echo "❌ ERROR: candid-extractor not found!"
+        echo "Please install with: cargo install candid-extractor"
+        exit 1
+    fi
     
     # No Rust build needed for kong_locker_frontend - it's a frontend asset canister
     
@@ -1,6 +1,7 @@
-// Minimal API types needed for admin canister to approve/reject Orbit requests
+// Minimal API types needed for admin canister to approve/reject and track Orbit requests
 use candid::{CandidType, Deserialize};
 
+pub mod http;
 pub mod voting_power;
 
 /// Result type for submit_request_approval
@@ -33,3 +34,83 @@ pub struct Error {
     pub message: Option<String>,
     pub details: Option<Vec<(String, String)>>,
 }
+
+/// Input for get_request
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GetRequestInput {
+    pub request_id: String,
+    pub with_full_info: Option<bool>,
+}
+
+/// Result type for get_request
+/// Only the request's status and kind of operation are decoded; Orbit's other
+/// fields are skipped
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum GetRequestResult {
+    Ok(GetRequestResponse),
+    Err(Error),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GetRequestResponse {
+    pub request: RequestWithStatus,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RequestWithStatus {
+    pub id: String,
+    pub status: RequestStatus,
+    pub operation: RequestOperation,
+}
+
+/// The operation a request performs; only the variant is read
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum RequestOperation {
+    ManageSystemInfo(candid::Reserved),
+    Transfer(candid::Reserved),
+    AddAccount(candid::Reserved),
+    EditAccount(candid::Reserved),
+    AddAddressBookEntry(candid::Reserved),
+    EditAddressBookEntry(candid::Reserved),
+    RemoveAddressBookEntry(candid::Reserved),
+    AddUser(candid::Reserved),
+    EditUser(candid::Reserved),
+    AddUserGroup(candid::Reserved),
+    EditUserGroup(candid::Reserved),
+    RemoveUserGroup(candid::Reserved),
+    SystemUpgrade(candid::Reserved),
+    SystemRestore(candid::Reserved),
+    SetDisasterRecovery(candid::Reserved),
+    ChangeExternalCanister(candid::Reserved),
+    CreateExternalCanister(candid::Reserved),
+    ConfigureExternalCanister(candid::Reserved),
+    CallExternalCanister(candid::Reserved),
+    FundExternalCanister(candid::Reserved),
+    MonitorExternalCanister(candid::Reserved),
+    SnapshotExternalCanister(candid::Reserved),
+    RestoreExternalCanister(candid::Reserved),
+    PruneExternalCanister(candid::Reserved),
+    EditPermission(candid::Reserved),
+    AddRequestPolicy(candid::Reserved),
+    EditRequestPolicy(candid::Reserved),
+    RemoveRequestPolicy(candid::Reserved),
+    AddAsset(candid::Reserved),
+    EditAsset(candid::Reserved),
+    RemoveAsset(candid::Reserved),
+    AddNamedRule(candid::Reserved),
+    EditNamedRule(candid::Reserved),
+    RemoveNamedRule(candid::Reserved),
+}
+
+/// Request status (timestamps are RFC 3339)
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum RequestStatus {
+    Created,
+    Approved,
+    Rejected,
+    Cancelled { reason: Option<String> },
+    Scheduled { scheduled_at: String },
+    Processing { started_at: String },
+    Completed { completed_at: String },
+    Failed { reason: Option<String> },
+}
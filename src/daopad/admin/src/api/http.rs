@@ -0,0 +1,124 @@
+// HTTP gateway interface for downloadable exports
+//
+// Responses are uncertified, so fetch them through the raw domain:
+//   https://<admin canister>.raw.icp0.io/cap-table/<station>.csv
+//   https://<admin canister>.raw.icp0.io/cap-table/<station>.json?at=<nanoseconds>
+// `.csv` is the cap table at `at` (default: now); `.json` is that snapshot.
+// `/cap-table/<station>/history.json?from=<nanoseconds>&limit=<n>` is the
+// history a page at a time: up to `limit` (at most 100) snapshots taken at or
+// after `from`, oldest first. Page on with `from` = the last taken_at + 1.
+
+use crate::equity::history::{cap_table_at, cap_table_history_page, snapshot_csv};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::query;
+
+const MAX_HISTORY_PAGE: usize = 100;
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+fn response(status_code: u16, content_type: &str, filename: Option<String>, body: Vec<u8>) -> HttpResponse {
+    let mut headers = vec![("Content-Type".to_string(), content_type.to_string())];
+    if let Some(filename) = filename {
+        headers.push((
+            "Content-Disposition".to_string(),
+            format!("attachment; filename=\"{}\"", filename),
+        ));
+    }
+    HttpResponse { status_code, headers, body }
+}
+
+fn error(status_code: u16, message: &str) -> HttpResponse {
+    response(status_code, "text/plain", None, message.as_bytes().to_vec())
+}
+
+fn json<T: serde::Serialize>(value: &T, filename: String) -> HttpResponse {
+    match serde_json::to_vec_pretty(value) {
+        Ok(body) => response(200, "application/json", Some(filename), body),
+        Err(e) => error(500, &format!("Failed to encode JSON: {}", e)),
+    }
+}
+
+/// A numeric query parameter, if present
+fn query_param(query: &str, name: &str) -> Result<Option<u64>, HttpResponse> {
+    let prefix = format!("{}=", name);
+    match query.split('&').find_map(|pair| pair.strip_prefix(prefix.as_str())) {
+        Some(value) => value
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| error(400, &format!("`{}` must be a non-negative integer", name))),
+        None => Ok(None),
+    }
+}
+
+/// Cap table export routes (GET only)
+fn cap_table_export(path: &str, query: &str) -> HttpResponse {
+    let at = match query_param(query, "at") {
+        Ok(at) => at.unwrap_or_else(ic_cdk::api::time),
+        Err(response) => return response,
+    };
+
+    let (station, export) = match path.split_once('/') {
+        Some((station, export)) => (station, export),
+        None => match path.rsplit_once('.') {
+            Some((station, format)) => (station, format),
+            None => return error(404, "Not found"),
+        },
+    };
+    let Ok(station_id) = Principal::from_text(station) else {
+        return error(400, "Invalid station principal");
+    };
+
+    if export == "history.json" {
+        let (from, limit) = match (query_param(query, "from"), query_param(query, "limit")) {
+            (Ok(from), Ok(limit)) => (
+                from.unwrap_or(0),
+                limit.map_or(MAX_HISTORY_PAGE, |l| usize::try_from(l).unwrap_or(usize::MAX)),
+            ),
+            (Err(response), _) | (_, Err(response)) => return response,
+        };
+        return json(
+            &cap_table_history_page(station_id, from, limit.min(MAX_HISTORY_PAGE)),
+            format!("cap-table-history-{}-{}.json", station_id, from),
+        );
+    }
+
+    let Some(snapshot) = cap_table_at(station_id, at) else {
+        return error(404, "No cap table recorded for this station at that time");
+    };
+    match export {
+        "csv" => response(
+            200,
+            "text/csv",
+            Some(format!("cap-table-{}-{}.csv", station_id, snapshot.taken_at)),
+            snapshot_csv(&snapshot).into_bytes(),
+        ),
+        "json" => json(&snapshot, format!("cap-table-{}-{}.json", station_id, snapshot.taken_at)),
+        _ => error(404, "Not found"),
+    }
+}
+
+#[query]
+pub fn http_request(request: HttpRequest) -> HttpResponse {
+    if request.method != "GET" {
+        return error(405, "Method not allowed");
+    }
+
+    let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+    match path.strip_prefix("/cap-table/") {
+        Some(rest) => cap_table_export(rest, query),
+        None => error(404, "Not found"),
+    }
+}
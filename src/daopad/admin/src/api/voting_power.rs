@@ -1,10 +1,11 @@
+use crate::kong_locker::VotingPowerBreakdown;
 use candid::{CandidType, Deserialize, Principal};
 use ic_cdk::update;
 
 /// Source of voting power for display purposes
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum VotingPowerSource {
-    Equity,      // From equity percentage
+    Equity,      // From equity, in basis points
     KongLocker,  // From locked liquidity
 }
 
@@ -13,10 +14,12 @@ pub enum VotingPowerSource {
 pub struct VotingPowerResult {
     pub voting_power: u64,
     pub source: VotingPowerSource,
+    // Kong Locker only: per-position value and lock age multiplier
+    pub breakdown: Option<VotingPowerBreakdown>,
 }
 
 /// Unified voting power query that routes by station type
-/// - Equity stations: Returns equity % as VP
+/// - Equity stations: Returns equity in basis points as VP
 /// - Token stations: Returns Kong Locker VP
 /// Note: Must be update (not query) because Kong Locker path uses cross-canister calls
 #[update]
@@ -26,18 +29,20 @@ pub async fn get_voting_power_display(
 ) -> Result<VotingPowerResult, String> {
     // Check if this is an equity station
     if crate::equity::is_equity_station(station_id) {
-        // Equity station: return equity % as VP
-        let equity_pct = crate::equity::get_user_equity(station_id, user);
+        // Equity station: return equity basis points as VP
+        let equity_bps = crate::equity::get_user_equity(station_id, user);
         Ok(VotingPowerResult {
-            voting_power: equity_pct as u64,
+            voting_power: equity_bps as u64,
             source: VotingPowerSource::Equity,
+            breakdown: None,
         })
     } else {
         // Token station: query Kong Locker
-        match crate::kong_locker::get_user_voting_power_for_token(user, station_id).await {
-            Ok(vp) => Ok(VotingPowerResult {
-                voting_power: vp,
+        match crate::kong_locker::get_user_voting_power_breakdown(user, station_id).await {
+            Ok(breakdown) => Ok(VotingPowerResult {
+                voting_power: breakdown.voting_power,
                 source: VotingPowerSource::KongLocker,
+                breakdown: Some(breakdown),
             }),
             Err(e) => Err(format!("Kong Locker query failed: {}", e)),
         }
@@ -0,0 +1,391 @@
+// Converting between equity and token (Kong Locker) governance
+//
+// Equity → token: holders propose linking the station to a token canister and
+// approve it by supermajority. On execution the backend links the token in
+// TOKEN_ORBIT_STATIONS, the final cap table is recorded in the history and
+// the station is archived: its holdings, grants and settings are cleared, so
+// is_equity_station turns false and voting power comes from Kong Locker.
+// Execution waits until no proposal, escrowed payment, dividend payout or
+// sell order is left open on the station.
+//
+// Token → equity: the DAO's own Orbit Station calls convert_to_equity_station
+// (a CallExternalCanister request approved by token holders) with the initial
+// cap table. The backend unlinks the token and the station starts over as an
+// equity station.
+//
+// Every conversion is logged with the cap table it left or started with.
+
+use super::{
+    add_to_tally, begin_settlement, commit_cap_table, dividends, escrow, get_equity_holders,
+    get_user_equity, is_equity_station, record_equity_vote, snapshot_vote_weights, vote_outcome,
+    voting_equity_bps,
+    PROPOSAL_DURATION_NANOS, TOTAL_EQUITY_BPS,
+};
+use super::market::{cancel_station_sell_orders, open_sell_orders};
+use super::history::{cap_table_at, record_snapshot, CapTableEvent, CapTableSnapshot};
+use crate::kong_locker::config::linked_station;
+use crate::proposals::types::{EquityProposalStatus, EquityStationConfig};
+use crate::storage::state::{
+    DIVIDEND_PROPOSALS, EQUITY_CONVERSION_PROPOSALS, EQUITY_HOLDERS, EQUITY_ISSUANCE_PROPOSALS,
+    EQUITY_SETTINGS, EQUITY_STATIONS, EQUITY_TRANSFER_PROPOSALS, STATION_CONVERSIONS, VESTING_GRANTS,
+};
+use crate::types::{PrincipalPair, PrincipalTimestamp, StorableCandid, StorablePrincipal, StorableString};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+use std::collections::BTreeSet;
+
+// Share of equity that must vote yes to hand governance to a token (80%)
+const CONVERSION_APPROVAL_BPS: u32 = 8_000;
+
+const BACKEND_CANISTER_ID: &str = "lwsav-iiaaa-aaaap-qp2qq-cai";
+
+/// Proposal to move an equity station to token governance
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EquityConversionProposal {
+    pub proposal_id: String,
+    pub station_id: Principal,
+    pub proposer: Principal,
+    pub token_canister_id: Principal,
+    pub status: EquityProposalStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub yes_votes_bps: u32,  // Out of 10_000 total equity
+    pub no_votes_bps: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum ConversionDirection {
+    EquityToToken,
+    TokenToEquity,
+}
+
+/// A completed conversion
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StationConversion {
+    pub station_id: Principal,
+    pub token_canister_id: Principal,
+    pub direction: ConversionDirection,
+    pub converted_at: u64,
+    pub cap_table: Option<CapTableSnapshot>,  // Final table (to token) or initial table (to equity)
+}
+
+fn load_conversion(proposal_id: &str) -> Result<EquityConversionProposal, String> {
+    EQUITY_CONVERSION_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .get(&StorableString(proposal_id.to_string()))
+            .map(|p| p.0)
+            .ok_or("Proposal not found".to_string())
+    })
+}
+
+fn save_conversion(proposal: EquityConversionProposal) {
+    EQUITY_CONVERSION_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(
+            StorableString(proposal.proposal_id.clone()),
+            StorableCandid(proposal)
+        );
+    });
+}
+
+fn is_open(status: &EquityProposalStatus) -> bool {
+    matches!(status, EquityProposalStatus::Proposed | EquityProposalStatus::Approved)
+}
+
+/// Transfers and issuances still awaiting a vote or execution or holding an
+/// escrowed payment, dividends still being voted on or paid out, and sell
+/// orders that can still be accepted
+fn open_equity_proposals(station_id: Principal) -> usize {
+    let now = time();
+    let outstanding = |proposal_id: &str, status: &EquityProposalStatus| {
+        is_open(status) || escrow::escrow_for(proposal_id).is_some_and(|e| escrow::is_live(&e))
+    };
+    let transfers = EQUITY_TRANSFER_PROPOSALS.with(|p| {
+        p.borrow().iter()
+            .filter(|(_, p)| p.0.station_id == station_id && outstanding(&p.0.proposal_id, &p.0.status))
+            .count()
+    });
+    let issuances = EQUITY_ISSUANCE_PROPOSALS.with(|p| {
+        p.borrow().iter()
+            .filter(|(_, p)| p.0.station_id == station_id && outstanding(&p.0.proposal_id, &p.0.status))
+            .count()
+    });
+    let dividends = DIVIDEND_PROPOSALS.with(|p| {
+        p.borrow().iter()
+            .filter(|(_, p)| p.0.station_id == station_id && dividends::is_outstanding(&p.0, now))
+            .count()
+    });
+    transfers + issuances + dividends + open_sell_orders(station_id)
+}
+
+fn record_conversion(conversion: StationConversion) {
+    STATION_CONVERSIONS.with(|conversions| {
+        conversions.borrow_mut().insert(
+            PrincipalTimestamp(conversion.station_id, conversion.converted_at),
+            StorableCandid(conversion),
+        );
+    });
+}
+
+/// Ask the backend to (un)link the token and station
+async fn call_backend(method: &str, token_canister_id: Principal, station_id: Principal) -> Result<(), String> {
+    let backend = Principal::from_text(BACKEND_CANISTER_ID)
+        .map_err(|e| format!("Invalid backend principal: {}", e))?;
+
+    let result: Result<(Result<(), String>,), _> =
+        ic_cdk::call(backend, method, (token_canister_id, station_id)).await;
+    match result {
+        Ok((Ok(()),)) => Ok(()),
+        Ok((Err(e),)) => Err(e),
+        Err((code, msg)) => Err(format!("Cross-canister call failed: {:?} - {}", code, msg)),
+    }
+}
+
+/// Record the final cap table, then clear the station's equity state
+fn archive_equity_station(station_id: Principal, token_canister_id: Principal) -> Option<CapTableSnapshot> {
+    record_snapshot(station_id, CapTableEvent::ConvertedToToken(token_canister_id));
+    let final_table = cap_table_at(station_id, u64::MAX);
+
+    EQUITY_HOLDERS.with(|holders| {
+        let mut holders = holders.borrow_mut();
+        for (holder, _) in get_equity_holders(station_id) {
+            holders.remove(&PrincipalPair(station_id, holder));
+        }
+    });
+    VESTING_GRANTS.with(|grants| {
+        let mut grants = grants.borrow_mut();
+        let ids: Vec<StorableString> = grants.iter()
+            .filter(|(_, g)| g.0.station_id == station_id)
+            .map(|(id, _)| id)
+            .collect();
+        for id in ids {
+            grants.remove(&id);
+        }
+    });
+    cancel_station_sell_orders(station_id);
+    EQUITY_SETTINGS.with(|settings| settings.borrow_mut().remove(&StorablePrincipal(station_id)));
+    EQUITY_STATIONS.with(|stations| stations.borrow_mut().remove(&StorablePrincipal(station_id)));
+
+    final_table
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Propose handing a station's governance to holders of `token_canister_id`
+/// Any holder can propose; needs 80% of equity to approve
+#[update]
+pub fn create_equity_conversion_proposal(
+    station_id: Principal,
+    token_canister_id: Principal,
+) -> Result<String, String> {
+    let proposer = ic_cdk::caller();
+
+    if !is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+    if get_user_equity(station_id, proposer) == 0 {
+        return Err("Only equity holders can propose a conversion".to_string());
+    }
+    if token_canister_id == station_id || token_canister_id == Principal::anonymous() {
+        return Err("Invalid token canister".to_string());
+    }
+
+    let now = time();
+    let proposal_id = format!("convert-{}-{}", now, proposer.to_text());
+    if EQUITY_CONVERSION_PROPOSALS.with(|p| p.borrow().contains_key(&StorableString(proposal_id.clone()))) {
+        return Err("You already proposed a conversion just now; try again shortly".to_string());
+    }
+    save_conversion(EquityConversionProposal {
+        proposal_id: proposal_id.clone(),
+        station_id,
+        proposer,
+        token_canister_id,
+        status: EquityProposalStatus::Proposed,
+        created_at: now,
+        expires_at: now + PROPOSAL_DURATION_NANOS,
+        yes_votes_bps: 0,
+        no_votes_bps: 0,
+    });
+    snapshot_vote_weights(&proposal_id, station_id);
+
+    Ok(proposal_id)
+}
+
+#[update]
+pub fn vote_on_equity_conversion(proposal_id: String, approve: bool) -> Result<(), String> {
+    let voter = ic_cdk::caller();
+    let mut proposal = load_conversion(&proposal_id)?;
+
+    if time() > proposal.expires_at {
+        proposal.status = EquityProposalStatus::Expired;
+        save_conversion(proposal);
+        return Err("Proposal expired".to_string());
+    }
+
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Voting is closed (status: {:?})", proposal.status));
+    }
+
+    let voter_equity = record_equity_vote(&proposal_id, proposal.station_id, voter, approve)?;
+    if approve {
+        proposal.yes_votes_bps = add_to_tally(proposal.yes_votes_bps, voter_equity);
+    } else {
+        proposal.no_votes_bps = add_to_tally(proposal.no_votes_bps, voter_equity);
+    }
+
+    // Check 80% threshold of all vested equity
+    let voting_equity = voting_equity_bps(&proposal_id, proposal.station_id, &[], false);
+    if let Some(outcome) = vote_outcome(
+        proposal.yes_votes_bps,
+        proposal.no_votes_bps,
+        voting_equity,
+        CONVERSION_APPROVAL_BPS,
+    ) {
+        proposal.status = outcome;
+    }
+
+    save_conversion(proposal);
+    Ok(())
+}
+
+/// Withdraw a conversion proposal before it is approved (proposer only)
+#[update]
+pub fn cancel_equity_conversion(proposal_id: String) -> Result<(), String> {
+    let mut proposal = load_conversion(&proposal_id)?;
+
+    if ic_cdk::caller() != proposal.proposer {
+        return Err("Only proposer can cancel".to_string());
+    }
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Only proposals awaiting approval can be cancelled (status: {:?})", proposal.status));
+    }
+
+    proposal.status = EquityProposalStatus::Cancelled;
+    save_conversion(proposal);
+    Ok(())
+}
+
+/// Link the token and archive the equity station (proposer or any holder)
+/// Every other equity proposal must be executed, cancelled or closed first
+#[update]
+pub async fn execute_equity_conversion(proposal_id: String) -> Result<(), String> {
+    let caller = ic_cdk::caller();
+    let mut proposal = load_conversion(&proposal_id)?;
+    let station_id = proposal.station_id;
+
+    if caller != proposal.proposer && get_user_equity(station_id, caller) == 0 {
+        return Err("Only the proposer or a holder can execute".to_string());
+    }
+    if proposal.status != EquityProposalStatus::Approved {
+        return Err(format!("Not approved (status: {:?})", proposal.status));
+    }
+    if time() > proposal.expires_at {
+        return Err("Proposal expired".to_string());
+    }
+
+    let open = open_equity_proposals(station_id);
+    if open > 0 {
+        return Err(format!(
+            "{} equity proposal(s), escrow(s) or sell order(s) are still open; settle or cancel them before converting",
+            open
+        ));
+    }
+
+    let settlement = begin_settlement(station_id)?;
+    call_backend("link_converted_station", proposal.token_canister_id, station_id).await?;
+
+    let final_table = archive_equity_station(station_id, proposal.token_canister_id);
+    drop(settlement);
+
+    record_conversion(StationConversion {
+        station_id,
+        token_canister_id: proposal.token_canister_id,
+        direction: ConversionDirection::EquityToToken,
+        converted_at: time(),
+        cap_table: final_table,
+    });
+
+    proposal.status = EquityProposalStatus::Executed;
+    save_conversion(proposal);
+    Ok(())
+}
+
+/// Take a token DAO private: unlink the token and make the station an equity
+/// station with the given holdings (basis points, summing to 10_000)
+/// ONLY the DAO's Orbit Station can call this (via a CallExternalCanister request)
+#[update]
+pub async fn convert_to_equity_station(
+    token_canister_id: Principal,
+    holders: Vec<(Principal, u32)>,
+) -> Result<(), String> {
+    let station_id = linked_station(token_canister_id).await?;
+    if ic_cdk::caller() != station_id {
+        return Err("Only the DAO's Orbit Station can convert it to an equity station".to_string());
+    }
+    if is_equity_station(station_id) {
+        return Err("Already an equity station".to_string());
+    }
+
+    let unique: BTreeSet<Principal> = holders.iter().map(|(holder, _)| *holder).collect();
+    if unique.len() != holders.len() {
+        return Err("Each holder can only be listed once".to_string());
+    }
+    if holders.iter().any(|(holder, bps)| *bps == 0 || *holder == Principal::anonymous()) {
+        return Err("Every holder needs a non-anonymous principal and a positive holding".to_string());
+    }
+    let total: u64 = holders.iter().map(|(_, bps)| *bps as u64).sum();
+    if total != TOTAL_EQUITY_BPS as u64 {
+        return Err(format!("Holdings must sum to {} basis points (got {})", TOTAL_EQUITY_BPS, total));
+    }
+
+    call_backend("unlink_converted_station", token_canister_id, station_id).await?;
+
+    let now = time();
+    EQUITY_STATIONS.with(|stations| {
+        stations.borrow_mut().insert(
+            StorablePrincipal(station_id),
+            StorableCandid(EquityStationConfig { station_id, creator: station_id, created_at: now }),
+        );
+    });
+    EQUITY_HOLDERS.with(|equity| {
+        let mut equity = equity.borrow_mut();
+        for (holder, bps) in &holders {
+            equity.insert(PrincipalPair(station_id, *holder), *bps);
+        }
+    });
+    commit_cap_table(station_id, CapTableEvent::ConvertedFromToken(token_canister_id));
+
+    record_conversion(StationConversion {
+        station_id,
+        token_canister_id,
+        direction: ConversionDirection::TokenToEquity,
+        converted_at: now,
+        cap_table: cap_table_at(station_id, u64::MAX),
+    });
+
+    Ok(())
+}
+
+#[query]
+pub fn get_equity_conversion_proposals(station_id: Principal) -> Vec<EquityConversionProposal> {
+    EQUITY_CONVERSION_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .iter()
+            .map(|(_, p)| p.0)
+            .filter(|p| p.station_id == station_id)
+            .collect()
+    })
+}
+
+/// Every conversion of a station, oldest first
+#[query]
+pub fn get_station_conversions(station_id: Principal) -> Vec<StationConversion> {
+    STATION_CONVERSIONS.with(|conversions| {
+        conversions.borrow()
+            .range(PrincipalTimestamp(station_id, 0)..=PrincipalTimestamp(station_id, u64::MAX))
+            .map(|(_, c)| c.0)
+            .collect()
+    })
+}
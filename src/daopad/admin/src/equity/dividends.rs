@@ -0,0 +1,513 @@
+// Dividends: paying treasury funds out to equity holders
+//
+// A dividend proposal names an Orbit account and asset and a total amount.
+// Each holder's share comes from the cap table at the record date (see
+// history.rs), rounded down, with the leftover dust handed out one unit at a
+// time by largest remainder so the payouts add up to exactly the total.
+// Holdings count in full whether vested or not.
+//
+// Once holders approve it, executing the dividend has the backend create one
+// Orbit Transfer request per holder to the ICRC-1 account in the station's
+// address book, and the admin canister approves them as a batch. The backend
+// keeps one request per (dividend, holder), so a retry never pays twice.
+// Executing again refreshes each request's status from Orbit: a payout is only
+// Paid once its transfer completes, and one Orbit rejected, cancelled or failed
+// is requested again. Holders with no address book entry are left pending until
+// they add one. After the proposal expires no new requests are made.
+
+use super::{
+    add_to_tally, get_user_equity, is_equity_station, record_equity_vote, snapshot_vote_weights,
+    vote_outcome, voting_equity_bps, PROPOSAL_DURATION_NANOS,
+};
+use super::history::cap_table_at;
+use crate::proposals::types::EquityProposalStatus;
+use crate::api::RequestStatus;
+use crate::proposals::unified::{approve_orbit_request, get_orbit_request_status};
+use crate::storage::state::DIVIDEND_PROPOSALS;
+use crate::types::{StorableCandid, StorableString};
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+// Share of equity that must vote yes for a dividend (75%, as for a transfer)
+const DIVIDEND_APPROVAL_BPS: u32 = 7_500;
+
+const BACKEND_CANISTER_ID: &str = "lwsav-iiaaa-aaaap-qp2qq-cai";
+
+thread_local! {
+    // Dividends with payouts in flight; guards against creating duplicate requests
+    static PAYING_DIVIDENDS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Releases a dividend's payout lock when dropped, even if a call traps
+struct PayoutGuard(String);
+
+impl Drop for PayoutGuard {
+    fn drop(&mut self) {
+        PAYING_DIVIDENDS.with(|d| d.borrow_mut().remove(&self.0));
+    }
+}
+
+/// Take a dividend's payout lock
+fn begin_payout(proposal_id: &str) -> Result<PayoutGuard, String> {
+    if !PAYING_DIVIDENDS.with(|d| d.borrow_mut().insert(proposal_id.to_string())) {
+        return Err("This dividend is already being paid out".to_string());
+    }
+    Ok(PayoutGuard(proposal_id.to_string()))
+}
+
+/// Proposal to distribute treasury funds pro rata to equity
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendProposal {
+    pub proposal_id: String,
+    pub station_id: Principal,
+    pub proposer: Principal,
+    pub from_account_id: String,  // Orbit treasury account (UUID)
+    pub from_asset_id: String,    // Orbit asset (UUID)
+    pub total_amount: Nat,        // Smallest units of the asset
+    pub record_date: u64,         // Cap table snapshot the shares come from
+    pub payouts: Vec<DividendPayout>,
+    pub status: EquityProposalStatus,  // Executed once every payout is paid in Orbit
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub yes_votes_bps: u32,  // Out of 10_000 total equity
+    pub no_votes_bps: u32,
+}
+
+/// One holder's share of a dividend
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendPayout {
+    pub holder: Principal,
+    pub equity_bps: u32,  // Holding at the record date
+    pub amount: Nat,
+    pub status: DividendPayoutStatus,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum DividendPayoutStatus {
+    Pending,
+    MissingAddress,     // No ICRC-1 address book entry for the holder yet
+    Requested(String),  // Orbit request created, approval outstanding
+    Approved(String),   // Orbit request approved by the admin canister, transfer outstanding
+    Paid(String),       // Orbit request completed
+    Failed(String),     // Request creation or the transfer failed; retried on the next execution
+}
+
+/// Batch sent to the backend's create_dividend_transfers
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendTransfersInput {
+    pub station_id: Principal,
+    pub dividend_id: String,
+    pub from_account_id: String,
+    pub from_asset_id: String,
+    pub payouts: Vec<(Principal, Nat)>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum DividendTransferResult {
+    Requested(String),  // Orbit request ID
+    MissingAddress,
+    Failed(String),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendTransferOutcome {
+    pub holder: Principal,
+    pub result: DividendTransferResult,
+}
+
+fn load_dividend(proposal_id: &str) -> Result<DividendProposal, String> {
+    DIVIDEND_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .get(&StorableString(proposal_id.to_string()))
+            .map(|p| p.0)
+            .ok_or("Proposal not found".to_string())
+    })
+}
+
+fn save_dividend(proposal: DividendProposal) {
+    DIVIDEND_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(
+            StorableString(proposal.proposal_id.clone()),
+            StorableCandid(proposal)
+        );
+    });
+}
+
+/// Split `total` across holdings: floor of each pro rata share, then the dust
+/// one unit each by largest remainder (ties to the larger holding, then principal)
+fn split_amount(total: &Nat, holdings: &[(Principal, u32)]) -> Vec<(Principal, u32, Nat)> {
+    let held: u32 = holdings.iter().map(|(_, bps)| *bps).sum();
+    let held = Nat::from(held);
+
+    let mut shares: Vec<(Principal, u32, Nat, Nat)> = holdings.iter()
+        .map(|(holder, bps)| {
+            let scaled = total.clone() * Nat::from(*bps);
+            let share = scaled.clone() / held.clone();
+            let remainder = scaled - share.clone() * held.clone();
+            (*holder, *bps, share, remainder)
+        })
+        .collect();
+
+    let distributed = shares.iter().fold(Nat::from(0u32), |sum, (_, _, share, _)| sum + share.clone());
+    let mut dust = total.clone() - distributed;
+
+    let mut order: Vec<usize> = (0..shares.len()).collect();
+    order.sort_by(|a, b| {
+        let (a, b) = (&shares[*a], &shares[*b]);
+        b.3.cmp(&a.3).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0))
+    });
+    for i in order {
+        if dust == 0u32 {
+            break;
+        }
+        shares[i].2 += Nat::from(1u32);
+        dust -= Nat::from(1u32);
+    }
+
+    shares.into_iter().map(|(holder, bps, share, _)| (holder, bps, share)).collect()
+}
+
+fn is_unpaid(status: &DividendPayoutStatus) -> bool {
+    matches!(
+        status,
+        DividendPayoutStatus::Pending | DividendPayoutStatus::MissingAddress | DividendPayoutStatus::Failed(_)
+    )
+}
+
+fn is_in_flight(status: &DividendPayoutStatus) -> bool {
+    matches!(status, DividendPayoutStatus::Requested(_) | DividendPayoutStatus::Approved(_))
+}
+
+/// Whether a dividend is still being voted on or can still pay out
+/// (approved and unexpired, or with payouts Orbit hasn't settled yet)
+pub(crate) fn is_outstanding(proposal: &DividendProposal, now: u64) -> bool {
+    match proposal.status {
+        EquityProposalStatus::Proposed => true,
+        EquityProposalStatus::Approved => {
+            now <= proposal.expires_at || proposal.payouts.iter().any(|p| is_in_flight(&p.status))
+        }
+        _ => false,
+    }
+}
+
+/// Bring every outstanding payout up to date with its Orbit request
+async fn refresh_payouts(proposal: &mut DividendProposal) {
+    for payout in proposal.payouts.iter_mut() {
+        let (DividendPayoutStatus::Requested(request_id) | DividendPayoutStatus::Approved(request_id)) =
+            payout.status.clone()
+        else {
+            continue;
+        };
+        let status = match get_orbit_request_status(proposal.station_id, &request_id).await {
+            Ok(status) => status,
+            Err(e) => {
+                ic_cdk::println!(
+                    "DIVIDEND: Reading request {} for {} failed: {}",
+                    request_id, proposal.proposal_id, e
+                );
+                continue;
+            }
+        };
+        payout.status = match status {
+            RequestStatus::Created => DividendPayoutStatus::Requested(request_id),
+            RequestStatus::Approved | RequestStatus::Scheduled { .. } | RequestStatus::Processing { .. } => {
+                DividendPayoutStatus::Approved(request_id)
+            }
+            RequestStatus::Completed { .. } => DividendPayoutStatus::Paid(request_id),
+            RequestStatus::Rejected => DividendPayoutStatus::Failed(format!("Request {} was rejected", request_id)),
+            RequestStatus::Cancelled { reason } | RequestStatus::Failed { reason } => DividendPayoutStatus::Failed(
+                format!("Request {} did not execute: {}", request_id, reason.unwrap_or_default()),
+            ),
+        };
+    }
+}
+
+/// Have the backend create Orbit transfers for every unpaid share
+async fn request_transfers(proposal: &mut DividendProposal) -> Result<(), String> {
+    let payouts: Vec<(Principal, Nat)> = proposal.payouts.iter()
+        .filter(|p| is_unpaid(&p.status))
+        .map(|p| (p.holder, p.amount.clone()))
+        .collect();
+    if payouts.is_empty() {
+        return Ok(());
+    }
+
+    let backend = Principal::from_text(BACKEND_CANISTER_ID)
+        .map_err(|e| format!("Invalid backend principal: {}", e))?;
+    let input = DividendTransfersInput {
+        station_id: proposal.station_id,
+        dividend_id: proposal.proposal_id.clone(),
+        from_account_id: proposal.from_account_id.clone(),
+        from_asset_id: proposal.from_asset_id.clone(),
+        payouts,
+    };
+
+    let result: Result<(Result<Vec<DividendTransferOutcome>, String>,), _> =
+        ic_cdk::call(backend, "create_dividend_transfers", (input,)).await;
+    let outcomes = match result {
+        Ok((Ok(outcomes),)) => outcomes,
+        Ok((Err(e),)) => return Err(e),
+        Err((code, msg)) => return Err(format!("Cross-canister call failed: {:?} - {}", code, msg)),
+    };
+
+    for outcome in outcomes {
+        if let Some(payout) = proposal.payouts.iter_mut()
+            .find(|p| p.holder == outcome.holder && is_unpaid(&p.status))
+        {
+            payout.status = match outcome.result {
+                DividendTransferResult::Requested(request_id) => DividendPayoutStatus::Requested(request_id),
+                DividendTransferResult::MissingAddress => DividendPayoutStatus::MissingAddress,
+                DividendTransferResult::Failed(e) => DividendPayoutStatus::Failed(e),
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Approve every created request in Orbit (the dividend vote stands in for per-request votes)
+async fn approve_requests(proposal: &mut DividendProposal) {
+    for payout in proposal.payouts.iter_mut() {
+        let DividendPayoutStatus::Requested(request_id) = &payout.status else {
+            continue;
+        };
+        match approve_orbit_request(proposal.station_id, request_id).await {
+            Ok(()) => payout.status = DividendPayoutStatus::Approved(request_id.clone()),
+            Err(e) => ic_cdk::println!(
+                "DIVIDEND: Approving request {} for {} failed: {}",
+                request_id, proposal.proposal_id, e
+            ),
+        }
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Propose paying `total_amount` of an Orbit asset out to holders pro rata
+/// Shares follow the cap table at `record_date` (default: now); any holder can propose
+#[update]
+pub fn create_dividend_proposal(
+    station_id: Principal,
+    from_account_id: String,
+    from_asset_id: String,
+    total_amount: Nat,
+    record_date: Option<u64>,
+) -> Result<String, String> {
+    let proposer = ic_cdk::caller();
+
+    if !is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+    if get_user_equity(station_id, proposer) == 0 {
+        return Err("Only equity holders can propose a dividend".to_string());
+    }
+    if total_amount == 0u32 {
+        return Err("Dividend amount must be positive".to_string());
+    }
+    if from_account_id.is_empty() || from_asset_id.is_empty() {
+        return Err("Treasury account and asset are required".to_string());
+    }
+
+    let now = time();
+    let record_date = record_date.unwrap_or(now);
+    if record_date > now {
+        return Err("Record date can't be in the future".to_string());
+    }
+    let snapshot = cap_table_at(station_id, record_date)
+        .ok_or("No cap table recorded for this station at the record date".to_string())?;
+
+    let holdings: Vec<(Principal, u32)> = snapshot.entries.iter()
+        .map(|entry| (entry.holder, entry.equity_bps))
+        .collect();
+    // Shares too small to round up to a unit are left out
+    let payouts = split_amount(&total_amount, &holdings)
+        .into_iter()
+        .filter(|(_, _, amount)| *amount != 0u32)
+        .map(|(holder, equity_bps, amount)| DividendPayout {
+            holder,
+            equity_bps,
+            amount,
+            status: DividendPayoutStatus::Pending,
+        })
+        .collect();
+
+    let proposal_id = format!("dividend-{}-{}", now, proposer.to_text());
+    if DIVIDEND_PROPOSALS.with(|p| p.borrow().contains_key(&StorableString(proposal_id.clone()))) {
+        return Err("You already proposed a dividend just now; try again shortly".to_string());
+    }
+    save_dividend(DividendProposal {
+        proposal_id: proposal_id.clone(),
+        station_id,
+        proposer,
+        from_account_id,
+        from_asset_id,
+        total_amount,
+        record_date,
+        payouts,
+        status: EquityProposalStatus::Proposed,
+        created_at: now,
+        expires_at: now + PROPOSAL_DURATION_NANOS,
+        yes_votes_bps: 0,
+        no_votes_bps: 0,
+    });
+    snapshot_vote_weights(&proposal_id, station_id);
+
+    Ok(proposal_id)
+}
+
+#[update]
+pub fn vote_on_dividend(proposal_id: String, approve: bool) -> Result<(), String> {
+    let voter = ic_cdk::caller();
+    let mut proposal = load_dividend(&proposal_id)?;
+
+    if time() > proposal.expires_at {
+        proposal.status = EquityProposalStatus::Expired;
+        save_dividend(proposal);
+        return Err("Proposal expired".to_string());
+    }
+
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Voting is closed (status: {:?})", proposal.status));
+    }
+
+    let voter_equity = record_equity_vote(&proposal_id, proposal.station_id, voter, approve)?;
+    if approve {
+        proposal.yes_votes_bps = add_to_tally(proposal.yes_votes_bps, voter_equity);
+    } else {
+        proposal.no_votes_bps = add_to_tally(proposal.no_votes_bps, voter_equity);
+    }
+
+    // Every holder is a party to a dividend, so nobody is excluded
+    let voting_equity = voting_equity_bps(&proposal_id, proposal.station_id, &[], false);
+    if let Some(outcome) = vote_outcome(
+        proposal.yes_votes_bps,
+        proposal.no_votes_bps,
+        voting_equity,
+        DIVIDEND_APPROVAL_BPS,
+    ) {
+        proposal.status = outcome;
+    }
+
+    save_dividend(proposal);
+    Ok(())
+}
+
+/// Withdraw a dividend proposal before it is approved (proposer only)
+#[update]
+pub fn cancel_dividend(proposal_id: String) -> Result<(), String> {
+    let mut proposal = load_dividend(&proposal_id)?;
+
+    if ic_cdk::caller() != proposal.proposer {
+        return Err("Only proposer can cancel".to_string());
+    }
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Only proposals awaiting approval can be cancelled (status: {:?})", proposal.status));
+    }
+
+    proposal.status = EquityProposalStatus::Cancelled;
+    save_dividend(proposal);
+    Ok(())
+}
+
+/// Pay out an approved dividend (proposer or any payee)
+/// Safe to call again: statuses are refreshed from Orbit and only shares that
+/// were never requested, or whose request failed, are requested again.
+/// Orbit executes approved transfers on its own, so call again to record them as paid
+#[update]
+pub async fn execute_dividend(proposal_id: String) -> Result<DividendProposal, String> {
+    let caller = ic_cdk::caller();
+    let mut proposal = load_dividend(&proposal_id)?;
+
+    if caller != proposal.proposer && !proposal.payouts.iter().any(|p| p.holder == caller) {
+        return Err("Only the proposer or a payee can execute".to_string());
+    }
+    if proposal.status != EquityProposalStatus::Approved {
+        return Err(format!("Not approved (status: {:?})", proposal.status));
+    }
+
+    // Requests already made are still followed to completion after expiry
+    let expired = time() > proposal.expires_at;
+    if expired && !proposal.payouts.iter().any(|p| is_in_flight(&p.status)) {
+        proposal.status = EquityProposalStatus::Expired;
+        save_dividend(proposal);
+        return Err("Proposal expired".to_string());
+    }
+
+    let payout = begin_payout(&proposal_id)?;
+
+    refresh_payouts(&mut proposal).await;
+    let requested = if expired {
+        Ok(())
+    } else {
+        request_transfers(&mut proposal).await
+    };
+    // Keep the request IDs even if approving below fails
+    save_dividend(proposal.clone());
+    approve_requests(&mut proposal).await;
+
+    if proposal.payouts.iter().all(|p| matches!(p.status, DividendPayoutStatus::Paid(_))) {
+        proposal.status = EquityProposalStatus::Executed;
+    } else if expired && !proposal.payouts.iter().any(|p| is_in_flight(&p.status)) {
+        proposal.status = EquityProposalStatus::Expired;
+    }
+    save_dividend(proposal.clone());
+    drop(payout);
+
+    requested?;
+    Ok(proposal)
+}
+
+#[query]
+pub fn get_dividend_proposals(station_id: Principal) -> Vec<DividendProposal> {
+    DIVIDEND_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .iter()
+            .map(|(_, p)| p.0)
+            .filter(|p| p.station_id == station_id)
+            .collect()
+    })
+}
+
+#[query]
+pub fn get_dividend_proposal(proposal_id: String) -> Option<DividendProposal> {
+    load_dividend(&proposal_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(id: u8) -> Principal {
+        Principal::from_slice(&[id])
+    }
+
+    #[test]
+    fn split_pays_out_the_whole_amount() {
+        let holdings = [(holder(1), 5_000), (holder(2), 3_000), (holder(3), 2_000)];
+        let shares = split_amount(&Nat::from(1_000u32), &holdings);
+        let amounts: Vec<Nat> = shares.iter().map(|(_, _, share)| share.clone()).collect();
+        assert_eq!(amounts, vec![Nat::from(500u32), Nat::from(300u32), Nat::from(200u32)]);
+    }
+
+    #[test]
+    fn split_gives_dust_to_largest_remainders() {
+        // 100 over three equal holders: 33 each, one unit of dust to the lowest principal
+        let holdings = [(holder(2), 1), (holder(1), 1), (holder(3), 1)];
+        let shares = split_amount(&Nat::from(100u32), &holdings);
+        let amounts: Vec<Nat> = shares.iter().map(|(_, _, share)| share.clone()).collect();
+        assert_eq!(amounts, vec![Nat::from(33u32), Nat::from(34u32), Nat::from(33u32)]);
+    }
+
+    #[test]
+    fn split_breaks_remainder_ties_by_holding_size() {
+        // 10 over 1:3: floors 2 and 7 with equal remainders; the larger holding gets the unit
+        let holdings = [(holder(1), 1), (holder(2), 3)];
+        let shares = split_amount(&Nat::from(10u32), &holdings);
+        let amounts: Vec<Nat> = shares.iter().map(|(_, _, share)| share.clone()).collect();
+        assert_eq!(amounts, vec![Nat::from(2u32), Nat::from(8u32)]);
+    }
+}
@@ -0,0 +1,416 @@
+// ckUSDC escrow for equity transfers and issuance subscriptions
+//
+// The payer (a transfer's buyer or an issuance's recipient) deposits the
+// proposal's ckusdc_amount (plus one ledger fee) into a per-proposal subaccount
+// of the admin canister via ICRC-2 transfer_from. Execution releases it to the
+// PaymentDestination (issuance subscriptions always go to the station treasury);
+// if the proposal can no longer execute (expired, rejected or cancelled), the
+// deposit is refunded to the payer, right away where possible and otherwise on
+// request or by the hourly sweep.
+//
+// A deposit whose transfer_from call fails without an answer stays Depositing.
+// Depositing again, or the sweep, settles it from the escrow subaccount's
+// balance; until the ledger's deduplication window closes, the original
+// transfer is simply repeated, which the ledger won't charge twice.
+
+use super::ledger::{self, Account, LedgerError};
+use crate::proposals::types::{
+    EquityEscrow, EquityProposalStatus, EscrowStatus, PaymentDestination,
+};
+use crate::storage::state::{
+    EQUITY_ESCROWS, EQUITY_ESCROW_NONCE, EQUITY_ISSUANCE_PROPOSALS, EQUITY_TRANSFER_PROPOSALS,
+};
+use crate::types::{StorableCandid, StorableString};
+use candid::{Nat, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+use ic_cdk_timers::set_timer_interval;
+use std::time::Duration;
+
+const REFUND_SWEEP_INTERVAL_SECS: u64 = 60 * 60;
+
+// ICRC ledgers deduplicate transactions for 24 hours, plus some clock drift
+const DEDUP_WINDOW_NANOS: u64 = (24 * 60 + 5) * 60 * 1_000_000_000;
+
+// First byte of every escrow subaccount, so they can't collide with other uses
+const ESCROW_SUBACCOUNT_TAG: u8 = 0x45; // 'E'
+
+/// Start the expired-escrow refund timer (called from init and post_upgrade)
+pub fn init_escrow_refunds() {
+    set_timer_interval(Duration::from_secs(REFUND_SWEEP_INTERVAL_SECS), || {
+        ic_cdk::spawn(refund_expired_escrows());
+    });
+}
+
+/// Fresh subaccount for a new escrow
+fn next_escrow_subaccount() -> Vec<u8> {
+    let nonce = EQUITY_ESCROW_NONCE.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let nonce = *cell.get();
+        cell.set(nonce + 1).expect("Failed to save escrow nonce");
+        nonce
+    });
+
+    let mut subaccount = vec![0u8; 32];
+    subaccount[0] = ESCROW_SUBACCOUNT_TAG;
+    subaccount[24..].copy_from_slice(&nonce.to_be_bytes());
+    subaccount
+}
+
+/// ICRC-1 account a payment destination pays into
+/// SellerAccount holds the owner principal's text (default subaccount)
+pub(crate) fn destination_account(destination: &PaymentDestination) -> Result<Account, String> {
+    match destination {
+        PaymentDestination::SellerAccount(owner) => Principal::from_text(owner)
+            .map(|owner| Account { owner, subaccount: None })
+            .map_err(|e| format!("Seller account must be a principal: {}", e)),
+        PaymentDestination::StationTreasury(station) => {
+            Ok(Account { owner: *station, subaccount: None })
+        }
+    }
+}
+
+pub(crate) fn escrow_for(proposal_id: &str) -> Option<EquityEscrow> {
+    EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow().get(&StorableString(proposal_id.to_string())).map(|e| e.0)
+    })
+}
+
+/// Whether the escrow still holds (or may yet hold) the payer's funds
+pub(crate) fn is_live(escrow: &EquityEscrow) -> bool {
+    matches!(
+        escrow.status,
+        EscrowStatus::Depositing | EscrowStatus::Held | EscrowStatus::Releasing | EscrowStatus::Refunding
+    )
+}
+
+fn save_escrow(escrow: &EquityEscrow) {
+    EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow_mut().insert(
+            StorableString(escrow.proposal_id.clone()),
+            StorableCandid(escrow.clone()),
+        );
+    });
+}
+
+fn drop_escrow(proposal_id: &str) {
+    EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow_mut().remove(&StorableString(proposal_id.to_string()));
+    });
+}
+
+/// Mark a deposit as held once its funds are in the escrow subaccount
+fn hold_deposit(mut escrow: EquityEscrow, block: Option<Nat>) -> EquityEscrow {
+    escrow.status = EscrowStatus::Held;
+    escrow.deposit_block = block;
+    save_escrow(&escrow);
+    escrow
+}
+
+/// Pull the deposit from the payer, deduplicated on the escrow's subaccount
+/// and creation time so a retry can't charge twice
+async fn pull_deposit(escrow: EquityEscrow) -> Result<EquityEscrow, String> {
+    let from = Account { owner: escrow.buyer, subaccount: None };
+    let result = ledger::transfer_from(
+        from,
+        escrow.subaccount.clone(),
+        escrow.amount + escrow.fee,
+        escrow.subaccount.clone(),
+        escrow.deposited_at,
+    ).await;
+
+    match result {
+        Ok(block) => Ok(hold_deposit(escrow, Some(block))),
+        Err(LedgerError::Rejected(e)) => {
+            drop_escrow(&escrow.proposal_id);
+            Err(e)
+        }
+        Err(LedgerError::Unknown(e)) => Err(format!(
+            "{}; the deposit is pending and will be reconciled on the next attempt", e
+        )),
+    }
+}
+
+/// Settle a pending deposit from the escrow subaccount's balance
+/// Ok(None) means it is still undecided and the original transfer can be repeated
+async fn reconcile_deposit(escrow: EquityEscrow) -> Result<Option<EquityEscrow>, String> {
+    let balance = ledger::balance_of(escrow.subaccount.clone()).await?;
+    if balance >= escrow.amount + escrow.fee {
+        return Ok(Some(hold_deposit(escrow, None)));
+    }
+    // The ledger now refuses the original transfer as too old, so it never landed
+    if time() > escrow.deposited_at + DEDUP_WINDOW_NANOS {
+        drop_escrow(&escrow.proposal_id);
+        return Err("The earlier deposit never arrived; deposit again".to_string());
+    }
+    Ok(None)
+}
+
+/// What an escrowed proposal asks to be paid, by whom and to where
+struct EscrowTerms {
+    payer: Principal,
+    amount: u64,
+    destination: PaymentDestination,
+    status: EquityProposalStatus,
+    expires_at: u64,
+    payable_from: u64,  // End of a ROFR window for an outside buyer, else 0
+}
+
+/// Terms of the transfer or issuance proposal with this ID
+fn escrow_terms(proposal_id: &str) -> Result<EscrowTerms, String> {
+    let key = StorableString(proposal_id.to_string());
+    let transfer = EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow().get(&key).map(|p| p.0)
+    });
+    if let Some(p) = transfer {
+        return Ok(EscrowTerms {
+            payable_from: super::rofr::buyer_eligible_at(&p),
+            payer: p.buyer,
+            amount: p.ckusdc_amount,
+            destination: p.payment_destination,
+            status: p.status,
+            expires_at: p.expires_at,
+        });
+    }
+
+    EQUITY_ISSUANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow().get(&key).map(|p| p.0)
+    })
+    .map(|p| EscrowTerms {
+        payer: p.recipient,
+        amount: p.ckusdc_amount,
+        destination: PaymentDestination::StationTreasury(p.station_id),
+        status: p.status,
+        expires_at: p.expires_at,
+        payable_from: 0,
+    })
+    .ok_or("Proposal not found".to_string())
+}
+
+/// A proposal that can never execute gets its deposit back
+fn is_refundable(terms: &EscrowTerms, now: u64) -> bool {
+    match terms.status {
+        EquityProposalStatus::Executed => false,
+        EquityProposalStatus::Expired
+        | EquityProposalStatus::Rejected
+        | EquityProposalStatus::Cancelled => true,
+        EquityProposalStatus::Proposed | EquityProposalStatus::Approved => now > terms.expires_at,
+    }
+}
+
+/// Settle a payout left Releasing or Refunding from the escrow subaccount's balance
+/// Returns whether the funds went out; if they are still there the escrow is Held again
+async fn reconcile_payout(mut escrow: EquityEscrow) -> Result<bool, String> {
+    let balance = ledger::balance_of(escrow.subaccount.clone()).await?;
+    // A payout leaves no more than the fee buffer behind
+    if balance >= escrow.amount {
+        escrow.status = EscrowStatus::Held;
+        save_escrow(&escrow);
+        return Ok(false);
+    }
+    escrow.status = match escrow.status {
+        EscrowStatus::Releasing => EscrowStatus::Released,
+        _ => EscrowStatus::Refunded,
+    };
+    escrow.settled_at = Some(time());
+    save_escrow(&escrow);
+    Ok(true)
+}
+
+/// Record the outcome of a payout the escrow is Releasing or Refunding
+/// An unanswered ledger call is settled from the balance, since the transfer may
+/// have gone through; if even that fails the escrow stays put for the sweep
+async fn finish_payout(mut escrow: EquityEscrow, result: Result<Nat, LedgerError>) -> Result<(), String> {
+    match result {
+        Ok(block) => {
+            escrow.status = match escrow.status {
+                EscrowStatus::Releasing => EscrowStatus::Released,
+                _ => EscrowStatus::Refunded,
+            };
+            escrow.settled_at = Some(time());
+            escrow.settlement_block = Some(block);
+            save_escrow(&escrow);
+            Ok(())
+        }
+        Err(LedgerError::Rejected(e)) => {
+            escrow.status = EscrowStatus::Held;
+            save_escrow(&escrow);
+            Err(e)
+        }
+        Err(LedgerError::Unknown(e)) => match reconcile_payout(escrow).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(e),
+            Err(check) => Err(format!(
+                "{}; checking the escrow balance also failed ({}), so the payout will be reconciled later",
+                e, check
+            )),
+        },
+    }
+}
+
+/// Pay out a held deposit to the proposal's PaymentDestination
+/// Called on execution while the station's settlement lock is held
+pub(crate) async fn release_escrow(proposal_id: &str) -> Result<(), String> {
+    let terms = escrow_terms(proposal_id)?;
+    let mut escrow = escrow_for(proposal_id)
+        .ok_or("Payment has not been deposited into escrow".to_string())?;
+    // Paid out by an earlier attempt whose settlement didn't finish
+    if escrow.status == EscrowStatus::Released {
+        return Ok(());
+    }
+    if escrow.status != EscrowStatus::Held {
+        return Err(format!("Escrow is not held (status: {:?})", escrow.status));
+    }
+    let destination = destination_account(&terms.destination)?;
+
+    escrow.status = EscrowStatus::Releasing;
+    save_escrow(&escrow);
+
+    let result = ledger::transfer(escrow.subaccount.clone(), destination, escrow.amount).await;
+    finish_payout(escrow, result).await
+}
+
+/// Return a held deposit to the payer
+pub(crate) async fn refund_escrow(proposal_id: &str) -> Result<(), String> {
+    let mut escrow = escrow_for(proposal_id).ok_or("No escrow for this proposal".to_string())?;
+    if escrow.status != EscrowStatus::Held {
+        return Err(format!("Escrow is not held (status: {:?})", escrow.status));
+    }
+
+    escrow.status = EscrowStatus::Refunding;
+    save_escrow(&escrow);
+
+    let buyer = Account { owner: escrow.buyer, subaccount: None };
+    let result = ledger::transfer(escrow.subaccount.clone(), buyer, escrow.amount).await;
+    finish_payout(escrow, result).await
+}
+
+/// Settle pending deposits and payouts, then refund every held deposit whose
+/// proposal can no longer execute
+async fn refund_expired_escrows() {
+    let pending: Vec<EquityEscrow> = EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow()
+            .iter()
+            .map(|(_, e)| e.0)
+            .filter(|e| e.status == EscrowStatus::Depositing)
+            .collect()
+    });
+    for escrow in pending {
+        let proposal_id = escrow.proposal_id.clone();
+        if let Err(e) = reconcile_deposit(escrow).await {
+            ic_cdk::println!("EQUITY ESCROW: Pending deposit for {}: {}", proposal_id, e);
+        }
+    }
+
+    // Payouts whose outcome couldn't be checked when they were made
+    let paying: Vec<EquityEscrow> = EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow()
+            .iter()
+            .map(|(_, e)| e.0)
+            .filter(|e| matches!(e.status, EscrowStatus::Releasing | EscrowStatus::Refunding))
+            .collect()
+    });
+    for escrow in paying {
+        let proposal_id = escrow.proposal_id.clone();
+        if let Err(e) = reconcile_payout(escrow).await {
+            ic_cdk::println!("EQUITY ESCROW: Pending payout for {}: {}", proposal_id, e);
+        }
+    }
+
+    let now = time();
+    let held: Vec<String> = EQUITY_ESCROWS.with(|escrows| {
+        escrows.borrow()
+            .iter()
+            .filter(|(_, e)| e.0.status == EscrowStatus::Held)
+            .map(|(id, _)| id.0)
+            .collect()
+    });
+
+    for proposal_id in held {
+        let refundable = escrow_terms(&proposal_id)
+            .map(|t| is_refundable(&t, now))
+            .unwrap_or(false);
+        if refundable {
+            if let Err(e) = refund_escrow(&proposal_id).await {
+                ic_cdk::println!("EQUITY ESCROW: Refund for {} failed: {}", proposal_id, e);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Deposit the proposal's ckusdc_amount into escrow (transfer buyer or issuance recipient only)
+/// The payer must first approve this canister on the ckUSDC ledger for
+/// ckusdc_amount + 2 fees (one for this transfer_from, one for the payout).
+/// Calling again after an unanswered deposit settles it rather than paying twice
+#[update]
+pub async fn deposit_equity_escrow(proposal_id: String) -> Result<EquityEscrow, String> {
+    let caller = ic_cdk::caller();
+    let terms = escrow_terms(&proposal_id)?;
+
+    if caller != terms.payer {
+        return Err("Only the buyer or issuance recipient can deposit".to_string());
+    }
+    if terms.amount == 0 {
+        return Err("Proposal has no payment to escrow".to_string());
+    }
+    let now = time();
+    if is_refundable(&terms, now) || terms.status == EquityProposalStatus::Executed {
+        return Err(format!("Proposal can no longer execute (status: {:?})", terms.status));
+    }
+    // The amount can still shrink while existing holders take up the offer
+    if now < terms.payable_from {
+        return Err("Payment can be deposited once the right of first refusal window closes".to_string());
+    }
+    match escrow_for(&proposal_id) {
+        Some(escrow) if escrow.status == EscrowStatus::Depositing => {
+            return match reconcile_deposit(escrow.clone()).await? {
+                Some(held) => Ok(held),
+                None => pull_deposit(escrow).await,
+            };
+        }
+        Some(_) => return Err("Payment already deposited".to_string()),
+        None => {}
+    }
+
+    let fee = ledger::fee().await?;
+
+    // Re-check after the fee query, then reserve the slot before awaiting the
+    // ledger so a second call can't double-deposit
+    if escrow_for(&proposal_id).is_some() {
+        return Err("Payment already deposited".to_string());
+    }
+    let escrow = EquityEscrow {
+        proposal_id: proposal_id.clone(),
+        buyer: caller,
+        subaccount: next_escrow_subaccount(),
+        amount: terms.amount,
+        fee,
+        status: EscrowStatus::Depositing,
+        deposited_at: now,
+        deposit_block: None,
+        settled_at: None,
+        settlement_block: None,
+    };
+    save_escrow(&escrow);
+
+    pull_deposit(escrow).await
+}
+
+/// Refund a deposit whose proposal can no longer execute (anyone can trigger)
+#[update]
+pub async fn refund_equity_escrow(proposal_id: String) -> Result<(), String> {
+    let terms = escrow_terms(&proposal_id)?;
+    if !is_refundable(&terms, time()) {
+        return Err(format!("Proposal can still execute (status: {:?})", terms.status));
+    }
+    refund_escrow(&proposal_id).await
+}
+
+#[query]
+pub fn get_equity_escrow(proposal_id: String) -> Option<EquityEscrow> {
+    escrow_for(&proposal_id)
+}
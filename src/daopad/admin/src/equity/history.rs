@@ -0,0 +1,148 @@
+// Versioned cap table history
+//
+// Every change to a station's holdings (and every vesting grant or revocation)
+// appends a full snapshot of its cap table, so the table at any past moment
+// can be read back with get_cap_table_at or exported as CSV/JSON over
+// http_request (see api/http.rs).
+
+use super::{get_equity_holders, vesting};
+use crate::storage::state::{CAP_TABLE_HISTORY, EQUITY_STATIONS};
+use crate::types::{PrincipalTimestamp, StorableCandid};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::time;
+use ic_cdk::query;
+use serde::Serialize;
+
+/// What changed the cap table
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum CapTableEvent {
+    Baseline,                // First snapshot of a station that predates history
+    StationCreated,
+    Transfer(String),        // Transfer proposal ID
+    Issuance(String),        // Issuance proposal ID
+    VestingGranted(String),  // Grant ID
+    VestingRevoked(String),  // Grant ID
+    ConvertedToToken(Principal),    // Final table before governance moved to this token
+    ConvertedFromToken(Principal),  // Initial table after leaving this token's governance
+}
+
+/// One holder's line on the cap table
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CapTableEntry {
+    pub holder: Principal,
+    pub equity_bps: u32,    // Recorded holding, vested or not
+    pub unvested_bps: u32,  // Part of equity_bps still vesting at taken_at
+}
+
+/// A station's full cap table right after an event
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CapTableSnapshot {
+    pub station_id: Principal,
+    pub taken_at: u64,
+    pub event: CapTableEvent,
+    pub entries: Vec<CapTableEntry>,
+}
+
+/// Append a snapshot of a station's current cap table
+pub(crate) fn record_snapshot(station_id: Principal, event: CapTableEvent) {
+    let now = time();
+    let entries = get_equity_holders(station_id)
+        .into_iter()
+        .map(|(holder, equity_bps)| CapTableEntry {
+            holder,
+            equity_bps,
+            unvested_bps: vesting::unvested_bps(station_id, holder, now),
+        })
+        .collect();
+
+    CAP_TABLE_HISTORY.with(|history| {
+        let mut history = history.borrow_mut();
+
+        // Events in the same round share a timestamp; keys stay unique and ordered
+        let last = history
+            .range(PrincipalTimestamp(station_id, 0)..=PrincipalTimestamp(station_id, u64::MAX))
+            .next_back()
+            .map(|(key, _)| key.1);
+        let key_time = match last {
+            Some(last) if last >= now => last + 1,
+            _ => now,
+        };
+
+        history.insert(
+            PrincipalTimestamp(station_id, key_time),
+            StorableCandid(CapTableSnapshot { station_id, taken_at: now, event, entries }),
+        );
+    });
+}
+
+/// Give every equity station without history a baseline snapshot (called from post_upgrade)
+pub fn seed_cap_table_history() {
+    let stations: Vec<Principal> = EQUITY_STATIONS.with(|stations| {
+        stations.borrow().iter().map(|(station, _)| station.0).collect()
+    });
+
+    for station_id in stations {
+        if cap_table_at(station_id, u64::MAX).is_none() {
+            record_snapshot(station_id, CapTableEvent::Baseline);
+        }
+    }
+}
+
+/// Latest snapshot taken at or before `timestamp`
+pub(crate) fn cap_table_at(station_id: Principal, timestamp: u64) -> Option<CapTableSnapshot> {
+    CAP_TABLE_HISTORY.with(|history| {
+        history.borrow()
+            .range(PrincipalTimestamp(station_id, 0)..=PrincipalTimestamp(station_id, timestamp))
+            .next_back()
+            .map(|(_, snapshot)| snapshot.0)
+    })
+}
+
+/// Render a snapshot as CSV, one row per holder
+pub(crate) fn snapshot_csv(snapshot: &CapTableSnapshot) -> String {
+    let mut csv = String::from("holder,equity_bps,equity_percent,vested_bps,unvested_bps\n");
+    for entry in &snapshot.entries {
+        csv.push_str(&format!(
+            "{},{},{}.{:02},{},{}\n",
+            entry.holder,
+            entry.equity_bps,
+            entry.equity_bps / 100,
+            entry.equity_bps % 100,
+            entry.equity_bps - entry.unvested_bps,
+            entry.unvested_bps,
+        ));
+    }
+    csv
+}
+
+/// Up to `limit` snapshots taken at or after `from` (nanoseconds), oldest first
+pub(crate) fn cap_table_history_page(station_id: Principal, from: u64, limit: usize) -> Vec<CapTableSnapshot> {
+    CAP_TABLE_HISTORY.with(|history| {
+        history.borrow()
+            .range(PrincipalTimestamp(station_id, from)..=PrincipalTimestamp(station_id, u64::MAX))
+            .take(limit)
+            .map(|(_, snapshot)| snapshot.0)
+            .collect()
+    })
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// The cap table as it stood at `timestamp` (nanoseconds), if the station had one
+#[query]
+pub fn get_cap_table_at(station_id: Principal, timestamp: u64) -> Option<CapTableSnapshot> {
+    cap_table_at(station_id, timestamp)
+}
+
+/// Every snapshot of a station's cap table, oldest first
+#[query]
+pub fn get_cap_table_history(station_id: Principal) -> Vec<CapTableSnapshot> {
+    CAP_TABLE_HISTORY.with(|history| {
+        history.borrow()
+            .range(PrincipalTimestamp(station_id, 0)..=PrincipalTimestamp(station_id, u64::MAX))
+            .map(|(_, snapshot)| snapshot.0)
+            .collect()
+    })
+}
@@ -0,0 +1,266 @@
+// Equity issuance: minting new equity to a recipient
+//
+// An issuance gives the recipient `equity_bps` of the post-issuance cap table
+// and scales every existing holding by (10_000 - equity_bps) / 10_000, so each
+// holder keeps the same share relative to the others. Issuance needs a higher
+// supermajority than a transfer since it dilutes everyone at once. An optional
+// ckUSDC subscription is escrowed by the recipient and paid into the station
+// treasury on execution. With vesting terms, the issued equity is granted
+// to the recipient subject to vesting (see vesting.rs).
+
+use super::{
+    add_to_tally, begin_settlement, commit_cap_table, escrow,
+    get_equity_holders, is_equity_station, record_equity_vote, rescale_holdings, vesting,
+    parties_excluded, snapshot_vote_weights, vote_outcome, voting_equity_bps, write_holdings,
+    PROPOSAL_DURATION_NANOS, TOTAL_EQUITY_BPS,
+};
+use super::history::CapTableEvent;
+use super::settings::equity_station_settings;
+use super::vesting::VestingTerms;
+use crate::proposals::types::{EquityIssuanceProposal, EquityProposalStatus};
+use crate::storage::state::EQUITY_ISSUANCE_PROPOSALS;
+use crate::types::{StorableCandid, StorableString};
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+
+// Share of equity that must vote yes for an issuance (80%)
+const ISSUANCE_APPROVAL_BPS: u32 = 8_000;
+
+fn load_issuance(proposal_id: &str) -> Result<EquityIssuanceProposal, String> {
+    EQUITY_ISSUANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .get(&StorableString(proposal_id.to_string()))
+            .map(|p| p.0)
+            .ok_or("Proposal not found".to_string())
+    })
+}
+
+fn save_issuance(proposal: EquityIssuanceProposal) {
+    EQUITY_ISSUANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(
+            StorableString(proposal.proposal_id.clone()),
+            StorableCandid(proposal)
+        );
+    });
+}
+
+/// Holdings after issuing `issued_bps` to `recipient`: existing holdings are
+/// scaled down pro rata to make room, then the recipient is credited
+fn diluted_holdings(
+    holdings: &[(Principal, u32)],
+    recipient: Principal,
+    issued_bps: u32,
+) -> Vec<(Principal, u32)> {
+    let mut diluted = rescale_holdings(holdings, TOTAL_EQUITY_BPS, TOTAL_EQUITY_BPS - issued_bps);
+    match diluted.iter_mut().find(|(holder, _)| *holder == recipient) {
+        Some((_, bps)) => *bps += issued_bps,
+        None => diluted.push((recipient, issued_bps)),
+    }
+    diluted
+}
+
+/// Collect the subscription from escrow, then rewrite the cap table
+async fn settle_equity_issuance(proposal: &EquityIssuanceProposal) -> Result<(), String> {
+    if proposal.ckusdc_amount > 0 {
+        escrow::release_escrow(&proposal.proposal_id).await?;
+    }
+
+    // Dilute the holdings as they are now, after the ledger call
+    let station_id = proposal.station_id;
+    let holdings = get_equity_holders(station_id);
+    let diluted = diluted_holdings(&holdings, proposal.recipient, proposal.equity_bps);
+
+    write_holdings(station_id, &holdings, &diluted);
+
+    // Existing grants shrink with the holdings they cover
+    vesting::rescale_grants(station_id, TOTAL_EQUITY_BPS, TOTAL_EQUITY_BPS - proposal.equity_bps);
+    if let Some(terms) = &proposal.vesting {
+        vesting::record_grant(station_id, proposal.recipient, proposal.equity_bps, terms.clone());
+    }
+
+    commit_cap_table(station_id, CapTableEvent::Issuance(proposal.proposal_id.clone()));
+    Ok(())
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Propose minting `equity_bps` of the post-issuance cap table to `recipient`
+/// Any holder can propose; `ckusdc_amount` is the recipient's subscription (0 for a grant)
+/// and `vesting` puts the issued equity under a vesting grant
+#[update]
+pub fn create_equity_issuance_proposal(
+    station_id: Principal,
+    recipient: Principal,
+    equity_bps: u32,
+    ckusdc_amount: u64,
+    vesting: Option<VestingTerms>,
+) -> Result<String, String> {
+    let proposer = ic_cdk::caller();
+
+    if !is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+
+    // Existing holders must keep something: 0.01%-99.99%
+    if !(1..TOTAL_EQUITY_BPS).contains(&equity_bps) {
+        return Err(format!("Issued equity must be 1-{} basis points", TOTAL_EQUITY_BPS - 1));
+    }
+
+    if super::get_user_equity(station_id, proposer) == 0 {
+        return Err("Only equity holders can propose an issuance".to_string());
+    }
+
+    if let Some(terms) = &vesting {
+        vesting::validate_terms(terms)?;
+    }
+
+    let now = time();
+    let proposal_id = format!("issue-{}-{}", now, proposer.to_text());
+    if EQUITY_ISSUANCE_PROPOSALS.with(|p| p.borrow().contains_key(&StorableString(proposal_id.clone()))) {
+        return Err("You already proposed an issuance just now; try again shortly".to_string());
+    }
+
+    save_issuance(EquityIssuanceProposal {
+        proposal_id: proposal_id.clone(),
+        station_id,
+        proposer,
+        recipient,
+        equity_bps,
+        ckusdc_amount,
+        status: EquityProposalStatus::Proposed,
+        created_at: now,
+        expires_at: now + PROPOSAL_DURATION_NANOS,
+        yes_votes_bps: 0,
+        no_votes_bps: 0,
+        vesting,
+    });
+    snapshot_vote_weights(&proposal_id, station_id);
+
+    Ok(proposal_id)
+}
+
+#[update]
+pub async fn vote_on_equity_issuance(proposal_id: String, approve: bool) -> Result<(), String> {
+    let voter = ic_cdk::caller();
+    let mut proposal = load_issuance(&proposal_id)?;
+
+    if time() > proposal.expires_at {
+        proposal.status = EquityProposalStatus::Expired;
+        save_issuance(proposal);
+        return Err("Proposal expired".to_string());
+    }
+
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Voting is closed (status: {:?})", proposal.status));
+    }
+
+    // The recipient sits out unless they already hold all the vested equity
+    let exclude_parties = equity_station_settings(proposal.station_id).exclude_parties_from_vote;
+    if parties_excluded(&proposal_id, proposal.station_id, &[proposal.recipient], exclude_parties) && voter == proposal.recipient {
+        return Err("The recipient can't vote on their own issuance".to_string());
+    }
+
+    let voter_equity = record_equity_vote(&proposal_id, proposal.station_id, voter, approve)?;
+    if approve {
+        proposal.yes_votes_bps = add_to_tally(proposal.yes_votes_bps, voter_equity);
+    } else {
+        proposal.no_votes_bps = add_to_tally(proposal.no_votes_bps, voter_equity);
+    }
+
+    // Check 80% threshold of the equity allowed to vote
+    let voting_equity = voting_equity_bps(&proposal_id, proposal.station_id, &[proposal.recipient], exclude_parties);
+    if let Some(outcome) = vote_outcome(
+        proposal.yes_votes_bps,
+        proposal.no_votes_bps,
+        voting_equity,
+        ISSUANCE_APPROVAL_BPS,
+    ) {
+        proposal.status = outcome;
+    }
+
+    let rejected = proposal.status == EquityProposalStatus::Rejected;
+    save_issuance(proposal);
+
+    // Return any escrowed subscription (the refund sweep retries on failure)
+    if rejected && escrow::escrow_for(&proposal_id).is_some() {
+        if let Err(e) = escrow::refund_escrow(&proposal_id).await {
+            ic_cdk::println!("EQUITY ESCROW: Refund for rejected {} failed: {}", proposal_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Withdraw an issuance proposal before it is approved (proposer only)
+#[update]
+pub async fn cancel_equity_issuance(proposal_id: String) -> Result<(), String> {
+    let mut proposal = load_issuance(&proposal_id)?;
+
+    if ic_cdk::caller() != proposal.proposer {
+        return Err("Only proposer can cancel".to_string());
+    }
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Only proposals awaiting approval can be cancelled (status: {:?})", proposal.status));
+    }
+
+    proposal.status = EquityProposalStatus::Cancelled;
+    save_issuance(proposal);
+
+    // Return any escrowed subscription (the refund sweep retries on failure)
+    if escrow::escrow_for(&proposal_id).is_some() {
+        if let Err(e) = escrow::refund_escrow(&proposal_id).await {
+            ic_cdk::println!("EQUITY ESCROW: Refund for cancelled {} failed: {}", proposal_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Mint the approved equity (recipient or proposer)
+#[update]
+pub async fn execute_equity_issuance(proposal_id: String) -> Result<(), String> {
+    let caller = ic_cdk::caller();
+    let mut proposal = load_issuance(&proposal_id)?;
+
+    if caller != proposal.recipient && caller != proposal.proposer {
+        return Err("Only recipient or proposer can execute".to_string());
+    }
+
+    if proposal.status != EquityProposalStatus::Approved {
+        return Err(format!("Not approved (status: {:?})", proposal.status));
+    }
+
+    if time() > proposal.expires_at {
+        return Err("Proposal expired".to_string());
+    }
+
+    let station_id = proposal.station_id;
+    let settlement = begin_settlement(station_id)?;
+    let settled = settle_equity_issuance(&proposal).await;
+    drop(settlement);
+    settled?;
+
+    proposal.status = EquityProposalStatus::Executed;
+    save_issuance(proposal);
+
+    Ok(())
+}
+
+#[query]
+pub fn get_equity_issuance_proposals(station_id: Principal) -> Vec<EquityIssuanceProposal> {
+    EQUITY_ISSUANCE_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .iter()
+            .map(|(_, p)| p.0)
+            .filter(|p| p.station_id == station_id)
+            .collect()
+    })
+}
+
+#[query]
+pub fn get_equity_issuance_proposal(proposal_id: String) -> Option<EquityIssuanceProposal> {
+    load_issuance(&proposal_id).ok()
+}
@@ -0,0 +1,139 @@
+// Minimal ckUSDC ledger client (ICRC-1 / ICRC-2) for equity escrow
+
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::call;
+
+pub const CKUSDC_LEDGER_ID: &str = "xevnm-gaaaa-aaaar-qafnq-cai";
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Account {
+    pub owner: Principal,
+    pub subaccount: Option<Vec<u8>>,
+}
+
+#[derive(CandidType)]
+struct TransferArg {
+    from_subaccount: Option<Vec<u8>>,
+    to: Account,
+    amount: Nat,
+    fee: Option<Nat>,
+    memo: Option<Vec<u8>>,
+    created_at_time: Option<u64>,
+}
+
+#[derive(CandidType)]
+struct TransferFromArgs {
+    spender_subaccount: Option<Vec<u8>>,
+    from: Account,
+    to: Account,
+    amount: Nat,
+    fee: Option<Nat>,
+    memo: Option<Vec<u8>>,
+    created_at_time: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum TransferError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum TransferFromError {
+    BadFee { expected_fee: Nat },
+    BadBurn { min_burn_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    TooOld,
+    CreatedInFuture { ledger_time: u64 },
+    Duplicate { duplicate_of: Nat },
+    TemporarilyUnavailable,
+    GenericError { error_code: Nat, message: String },
+}
+
+/// Why a transfer or transfer_from didn't complete
+#[derive(Debug)]
+pub enum LedgerError {
+    Rejected(String),  // The ledger refused it; nothing moved
+    Unknown(String),   // The call failed; the transfer may or may not have happened
+}
+
+fn ledger_id() -> Result<Principal, String> {
+    Principal::from_text(CKUSDC_LEDGER_ID).map_err(|e| format!("Invalid ckUSDC ledger ID: {}", e))
+}
+
+/// Current ledger fee
+pub async fn fee() -> Result<u64, String> {
+    let result: Result<(Nat,), _> = call(ledger_id()?, "icrc1_fee", ()).await;
+    let fee = result.map_err(|e| format!("Failed to query ckUSDC fee: {:?}", e))?.0;
+    u64::try_from(fee.0).map_err(|_| "ckUSDC fee does not fit in u64".to_string())
+}
+
+/// Pull `amount` from `from` into one of this canister's subaccounts (ICRC-2)
+/// `from` must have approved this canister for amount + fee. The ledger
+/// deduplicates on `memo` and `created_at_time`, so repeating the same call
+/// within its window returns the original block instead of charging again
+pub async fn transfer_from(
+    from: Account,
+    to_subaccount: Vec<u8>,
+    amount: u64,
+    memo: Vec<u8>,
+    created_at_time: u64,
+) -> Result<Nat, LedgerError> {
+    let ledger = ledger_id().map_err(LedgerError::Rejected)?;
+    let args = TransferFromArgs {
+        spender_subaccount: None,
+        from,
+        to: Account { owner: ic_cdk::id(), subaccount: Some(to_subaccount) },
+        amount: Nat::from(amount),
+        fee: None,
+        memo: Some(memo),
+        created_at_time: Some(created_at_time),
+    };
+
+    let result: Result<(Result<Nat, TransferFromError>,), _> =
+        call(ledger, "icrc2_transfer_from", (args,)).await;
+
+    match result {
+        Ok((Ok(block),)) => Ok(block),
+        Ok((Err(TransferFromError::Duplicate { duplicate_of }),)) => Ok(duplicate_of),
+        Ok((Err(e),)) => Err(LedgerError::Rejected(format!("ckUSDC transfer_from failed: {:?}", e))),
+        Err(e) => Err(LedgerError::Unknown(format!("Failed to call ckUSDC ledger: {:?}", e))),
+    }
+}
+
+/// Balance of one of this canister's subaccounts
+pub async fn balance_of(subaccount: Vec<u8>) -> Result<u64, String> {
+    let account = Account { owner: ic_cdk::id(), subaccount: Some(subaccount) };
+    let result: Result<(Nat,), _> = call(ledger_id()?, "icrc1_balance_of", (account,)).await;
+    let balance = result.map_err(|e| format!("Failed to query ckUSDC balance: {:?}", e))?.0;
+    u64::try_from(balance.0).map_err(|_| "ckUSDC balance does not fit in u64".to_string())
+}
+
+/// Send `amount` out of one of this canister's subaccounts (ICRC-1)
+pub async fn transfer(from_subaccount: Vec<u8>, to: Account, amount: u64) -> Result<Nat, LedgerError> {
+    let ledger = ledger_id().map_err(LedgerError::Rejected)?;
+    let args = TransferArg {
+        from_subaccount: Some(from_subaccount),
+        to,
+        amount: Nat::from(amount),
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+
+    let result: Result<(Result<Nat, TransferError>,), _> =
+        call(ledger, "icrc1_transfer", (args,)).await;
+
+    match result {
+        Ok((Ok(block),)) => Ok(block),
+        Ok((Err(e),)) => Err(LedgerError::Rejected(format!("ckUSDC transfer failed: {:?}", e))),
+        Err(e) => Err(LedgerError::Unknown(format!("Failed to call ckUSDC ledger: {:?}", e))),
+    }
+}
@@ -0,0 +1,249 @@
+// Standing sell orders for equity
+//
+// A holder posts an offer of some equity at a ckUSDC price until an expiry.
+// Anyone can accept all or part of it at the same price per basis point,
+// which opens an ordinary transfer proposal from the seller: it still needs
+// the 75% holder vote (and any right of first refusal), and the buyer's
+// payment is escrowed before the equity moves.
+//
+// An order's available equity is worked out from the transfers filling it,
+// so a fill that is rejected, cancelled or expires frees its equity again.
+// A fill reserves equity as soon as it is accepted, before any payment, so
+// the seller can withdraw a fill the buyer hasn't escrowed for with
+// cancel_equity_transfer (while it is voted on, or once approved); otherwise
+// it holds the equity until it expires.
+//
+// Open orders block a conversion to a token DAO and are cancelled with the
+// rest of the station's equity state when it converts.
+
+use super::{escrow, get_user_equity, is_equity_station, open_transfer_proposal, TOTAL_EQUITY_BPS};
+use crate::proposals::types::{EquityProposalStatus, PaymentDestination};
+use crate::storage::state::{EQUITY_SELL_ORDERS, EQUITY_TRANSFER_PROPOSALS};
+use crate::types::{StorableCandid, StorableString};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+
+const MAX_ORDER_DURATION_NANOS: u64 = 365 * 24 * 60 * 60 * 1_000_000_000; // 1 year
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SellOrderStatus {
+    Open,
+    Cancelled,  // Withdrawn by the seller; fills already accepted carry on
+}
+
+/// A seller's standing offer
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EquitySellOrder {
+    pub order_id: String,
+    pub station_id: Principal,
+    pub seller: Principal,
+    pub equity_bps: u32,     // Total offered
+    pub ckusdc_amount: u64,  // Price for the total; partial fills pay pro rata
+    pub payment_destination: PaymentDestination,
+    pub status: SellOrderStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// An order with how much of it is taken
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SellOrderListing {
+    pub order: EquitySellOrder,
+    pub available_bps: u32,
+    pub pending_bps: u32,  // In fills still being voted on or executed
+    pub filled_bps: u32,   // In executed fills
+}
+
+fn load_order(order_id: &str) -> Result<EquitySellOrder, String> {
+    EQUITY_SELL_ORDERS.with(|orders| {
+        orders.borrow()
+            .get(&StorableString(order_id.to_string()))
+            .map(|o| o.0)
+            .ok_or("Sell order not found".to_string())
+    })
+}
+
+fn save_order(order: EquitySellOrder) {
+    EQUITY_SELL_ORDERS.with(|orders| {
+        orders.borrow_mut().insert(StorableString(order.order_id.clone()), StorableCandid(order));
+    });
+}
+
+fn is_live(order: &EquitySellOrder, now: u64) -> bool {
+    order.status == SellOrderStatus::Open && now <= order.expires_at
+}
+
+fn station_orders(station_id: Principal) -> Vec<EquitySellOrder> {
+    EQUITY_SELL_ORDERS.with(|orders| {
+        orders.borrow()
+            .iter()
+            .map(|(_, o)| o.0)
+            .filter(|o| o.station_id == station_id)
+            .collect()
+    })
+}
+
+/// Orders on a station that haven't been cancelled or expired
+pub(crate) fn open_sell_orders(station_id: Principal) -> usize {
+    let now = time();
+    station_orders(station_id).iter().filter(|o| is_live(o, now)).count()
+}
+
+/// Cancel every open order on a station (when it stops being an equity station)
+pub(crate) fn cancel_station_sell_orders(station_id: Principal) {
+    let now = time();
+    for mut order in station_orders(station_id) {
+        if is_live(&order, now) {
+            order.status = SellOrderStatus::Cancelled;
+            save_order(order);
+        }
+    }
+}
+
+fn listing(order: EquitySellOrder, now: u64) -> SellOrderListing {
+    let (pending, filled) = EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .iter()
+            .map(|(_, p)| p.0)
+            .filter(|p| p.sell_order.as_deref() == Some(order.order_id.as_str()))
+            .fold((0u32, 0u32), |(pending, filled), p| match p.status {
+                EquityProposalStatus::Executed => (pending, filled + p.equity_bps),
+                EquityProposalStatus::Proposed | EquityProposalStatus::Approved
+                    if now <= p.expires_at => (pending + p.equity_bps, filled),
+                _ => (pending, filled),
+            })
+    });
+
+    let available = if is_live(&order, now) { order.equity_bps.saturating_sub(pending + filled) } else { 0 };
+    SellOrderListing { order, available_bps: available, pending_bps: pending, filled_bps: filled }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Offer `equity_bps` of the caller's vested equity for `ckusdc_amount` until `expires_at` (nanoseconds)
+#[update]
+pub fn create_sell_order(
+    station_id: Principal,
+    equity_bps: u32,
+    ckusdc_amount: u64,
+    payment_destination: PaymentDestination,
+    expires_at: u64,
+) -> Result<String, String> {
+    let seller = ic_cdk::caller();
+
+    if !is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+    if !(1..=TOTAL_EQUITY_BPS).contains(&equity_bps) {
+        return Err(format!("Equity must be 1-{} basis points", TOTAL_EQUITY_BPS));
+    }
+    let seller_equity = get_user_equity(station_id, seller);
+    if seller_equity < equity_bps {
+        return Err(format!(
+            "Insufficient equity: have {} bps, need {} bps",
+            seller_equity, equity_bps
+        ));
+    }
+
+    let now = time();
+    if expires_at <= now || expires_at - now > MAX_ORDER_DURATION_NANOS {
+        return Err("Expiry must be in the future and within a year".to_string());
+    }
+
+    // Escrow pays out to this account when a fill executes
+    escrow::destination_account(&payment_destination)?;
+
+    // One order per seller per round; the ID would collide otherwise
+    let order_id = format!("sell-{}-{}", now, seller.to_text());
+    if EQUITY_SELL_ORDERS.with(|orders| orders.borrow().contains_key(&StorableString(order_id.clone()))) {
+        return Err("You already created a sell order just now; try again shortly".to_string());
+    }
+    save_order(EquitySellOrder {
+        order_id: order_id.clone(),
+        station_id,
+        seller,
+        equity_bps,
+        ckusdc_amount,
+        payment_destination,
+        status: SellOrderStatus::Open,
+        created_at: now,
+        expires_at,
+    });
+
+    Ok(order_id)
+}
+
+/// Withdraw an order (seller only); fills already accepted are unaffected
+/// (cancel those individually with cancel_equity_transfer)
+#[update]
+pub fn cancel_sell_order(order_id: String) -> Result<(), String> {
+    let mut order = load_order(&order_id)?;
+
+    if ic_cdk::caller() != order.seller {
+        return Err("Only seller can cancel".to_string());
+    }
+    if order.status != SellOrderStatus::Open {
+        return Err("Sell order already cancelled".to_string());
+    }
+
+    order.status = SellOrderStatus::Cancelled;
+    save_order(order);
+    Ok(())
+}
+
+/// Buy `equity_bps` of an order at its price per basis point
+/// Returns the ID of the transfer proposal, which goes to the holder vote and
+/// then needs the caller's ckUSDC in escrow before it can execute
+#[update]
+pub fn accept_sell_order(order_id: String, equity_bps: u32) -> Result<String, String> {
+    let buyer = ic_cdk::caller();
+    let order = load_order(&order_id)?;
+
+    if buyer == Principal::anonymous() {
+        return Err("Authentication required".to_string());
+    }
+    if buyer == order.seller {
+        return Err("Sellers can't accept their own order".to_string());
+    }
+
+    let available = listing(order.clone(), time()).available_bps;
+    if available == 0 {
+        return Err("This sell order is no longer available".to_string());
+    }
+    if equity_bps == 0 || equity_bps > available {
+        return Err(format!("You can buy 1-{} basis points of this order", available));
+    }
+
+    // Same price per basis point as the order, rounded up in the seller's favour
+    let price = (order.ckusdc_amount as u128 * equity_bps as u128)
+        .div_ceil(order.equity_bps as u128) as u64;
+
+    open_transfer_proposal(
+        order.station_id,
+        order.seller,
+        buyer,
+        equity_bps,
+        price,
+        order.payment_destination,
+        Some(order_id),
+    )
+}
+
+/// Orders on a station that can still be accepted
+#[query]
+pub fn get_open_sell_orders(station_id: Principal) -> Vec<SellOrderListing> {
+    let now = time();
+    station_orders(station_id)
+        .into_iter()
+        .map(|order| listing(order, now))
+        .filter(|l| l.available_bps > 0)
+        .collect()
+}
+
+#[query]
+pub fn get_sell_order(order_id: String) -> Option<SellOrderListing> {
+    load_order(&order_id).ok().map(|order| listing(order, time()))
+}
@@ -0,0 +1,122 @@
+// One-time migration of equity from whole percents (u8, 1-100) to basis points
+//
+// Holdings and transfer proposals used to live in MemoryIds 11 and 12 as whole
+// percents. They are converted (x100) into the basis-point maps on upgrade and
+// the legacy entries removed, so this is a no-op once it has run.
+
+use super::{rescale_holdings, TOTAL_EQUITY_BPS};
+use crate::proposals::types::{EquityProposalStatus, EquityTransferProposal, PaymentDestination};
+use crate::storage::state::{
+    EQUITY_HOLDERS, EQUITY_TRANSFER_PROPOSALS, LEGACY_EQUITY_HOLDERS,
+    LEGACY_EQUITY_TRANSFER_PROPOSALS,
+};
+use crate::types::{PrincipalPair, StorableCandid, StorableString};
+use candid::{CandidType, Deserialize, Principal};
+use std::collections::BTreeMap;
+
+const BPS_PER_PERCENT: u32 = 100;
+
+/// EquityTransferProposal as stored before basis points
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LegacyEquityTransferProposal {
+    pub proposal_id: String,
+    pub station_id: Principal,
+    pub seller: Principal,
+    pub buyer: Principal,
+    pub percentage: u8,
+    pub ckusdc_amount: u64,
+    pub payment_destination: PaymentDestination,
+    pub status: EquityProposalStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub yes_votes_pct: u8,
+    pub no_votes_pct: u8,
+}
+
+impl From<LegacyEquityTransferProposal> for EquityTransferProposal {
+    fn from(legacy: LegacyEquityTransferProposal) -> Self {
+        EquityTransferProposal {
+            proposal_id: legacy.proposal_id,
+            station_id: legacy.station_id,
+            seller: legacy.seller,
+            buyer: legacy.buyer,
+            equity_bps: legacy.percentage as u32 * BPS_PER_PERCENT,
+            ckusdc_amount: legacy.ckusdc_amount,
+            payment_destination: legacy.payment_destination,
+            status: legacy.status,
+            created_at: legacy.created_at,
+            expires_at: legacy.expires_at,
+            yes_votes_bps: legacy.yes_votes_pct as u32 * BPS_PER_PERCENT,
+            no_votes_bps: legacy.no_votes_pct as u32 * BPS_PER_PERCENT,
+            rofr: None,
+            rofr_parent: None,
+            sell_order: None,
+        }
+    }
+}
+
+/// Move any whole-percent equity data into the basis-point maps (called from post_upgrade)
+pub fn migrate_equity_to_basis_points() {
+    let holdings: Vec<(PrincipalPair, u8)> = LEGACY_EQUITY_HOLDERS.with(|legacy| {
+        legacy.borrow().iter().collect()
+    });
+    let proposals: Vec<(StorableString, LegacyEquityTransferProposal)> =
+        LEGACY_EQUITY_TRANSFER_PROPOSALS.with(|legacy| {
+            legacy.borrow().iter().map(|(id, p)| (id, p.0)).collect()
+        });
+
+    if holdings.is_empty() && proposals.is_empty() {
+        return;
+    }
+
+    // Zero entries were left behind by full sales; they aren't holders
+    let mut stations: BTreeMap<Principal, Vec<(Principal, u32)>> = BTreeMap::new();
+    for (key, pct) in holdings.iter().filter(|(_, pct)| *pct > 0) {
+        stations.entry(key.0).or_default().push((key.1, *pct as u32 * BPS_PER_PERCENT));
+    }
+
+    EQUITY_HOLDERS.with(|holders| {
+        let mut holders = holders.borrow_mut();
+        for (station_id, station_holdings) in &stations {
+            let total: u32 = station_holdings.iter().map(|(_, bps)| bps).sum();
+            // A bad station mustn't trap post_upgrade, so it is scaled back to 100%
+            let migrated = if total == TOTAL_EQUITY_BPS {
+                station_holdings.clone()
+            } else {
+                ic_cdk::println!(
+                    "EQUITY MIGRATION: Station {} totals {} bps, rescaling to {}",
+                    station_id, total, TOTAL_EQUITY_BPS
+                );
+                rescale_holdings(station_holdings, total, TOTAL_EQUITY_BPS)
+            };
+            for (holder, bps) in migrated.into_iter().filter(|(_, bps)| *bps > 0) {
+                holders.insert(PrincipalPair(*station_id, holder), bps);
+            }
+        }
+    });
+
+    EQUITY_TRANSFER_PROPOSALS.with(|current| {
+        let mut current = current.borrow_mut();
+        for (id, proposal) in &proposals {
+            current.insert(id.clone(), StorableCandid(proposal.clone().into()));
+        }
+    });
+
+    LEGACY_EQUITY_HOLDERS.with(|legacy| {
+        let mut legacy = legacy.borrow_mut();
+        for (key, _) in &holdings {
+            legacy.remove(key);
+        }
+    });
+    LEGACY_EQUITY_TRANSFER_PROPOSALS.with(|legacy| {
+        let mut legacy = legacy.borrow_mut();
+        for (id, _) in &proposals {
+            legacy.remove(id);
+        }
+    });
+
+    ic_cdk::println!(
+        "EQUITY MIGRATION: Moved {} holdings across {} stations and {} proposals to basis points",
+        holdings.len(), stations.len(), proposals.len()
+    );
+}
@@ -1,13 +1,42 @@
+pub mod conversion;
+pub mod dividends;
+pub mod escrow;
+pub mod history;
+pub mod issuance;
+pub mod ledger;
+pub mod market;
+pub mod migration;
+pub mod rofr;
+pub mod settings;
+pub mod vesting;
+
 use crate::proposals::types::{
     EquityStationConfig, EquityTransferProposal, PaymentDestination,
     EquityProposalStatus, VoteChoice
 };
 use crate::storage::state::{
-    EQUITY_STATIONS, EQUITY_HOLDERS, EQUITY_TRANSFER_PROPOSALS, EQUITY_TRANSFER_VOTES
+    EQUITY_STATIONS, EQUITY_HOLDERS, EQUITY_TRANSFER_PROPOSALS, EQUITY_TRANSFER_VOTES, EQUITY_VOTE_WEIGHTS
 };
 use crate::types::{StorablePrincipal, StorableCandid, PrincipalPair, StorableString, StringPrincipalPair};
 use candid::Principal;
 use ic_cdk::{query, update};
+use history::CapTableEvent;
+use settings::equity_station_settings;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+/// Equity is held in basis points: all holders of a station always sum to exactly 100%
+pub const TOTAL_EQUITY_BPS: u32 = 10_000;
+
+// Share of equity that must vote yes for a transfer (75%)
+const TRANSFER_APPROVAL_BPS: u32 = 7_500;
+
+const PROPOSAL_DURATION_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days
+
+thread_local! {
+    // Stations with a settlement awaiting the ledger; equity can't move while set
+    static SETTLING_STATIONS: RefCell<BTreeSet<Principal>> = const { RefCell::new(BTreeSet::new()) };
+}
 
 // ============================================================================
 // INITIALIZATION
@@ -42,11 +71,14 @@ pub fn initialize_equity_station(station_id: Principal, creator: Principal) -> R
 
         // Creator gets 100% equity
         EQUITY_HOLDERS.with(|holders| {
-            holders.borrow_mut().insert(PrincipalPair(station_id, creator), 100);
+            holders.borrow_mut().insert(PrincipalPair(station_id, creator), TOTAL_EQUITY_BPS);
         });
 
         Ok(())
-    })
+    })?;
+
+    commit_cap_table(station_id, CapTableEvent::StationCreated);
+    Ok(())
 }
 
 // ============================================================================
@@ -57,30 +89,51 @@ pub fn initialize_equity_station(station_id: Principal, creator: Principal) -> R
 pub fn create_equity_transfer_proposal(
     station_id: Principal,
     buyer: Principal,
-    percentage: u8,
+    equity_bps: u32,
     ckusdc_amount: u64,
     payment_destination: PaymentDestination,
 ) -> Result<String, String> {
     let seller = ic_cdk::caller();
+    open_transfer_proposal(station_id, seller, buyer, equity_bps, ckusdc_amount, payment_destination, None)
+}
 
-    // Validate percentage 1-100
-    if percentage < 1 || percentage > 100 {
-        return Err("Percentage must be 1-100".to_string());
+/// Validate and store a new transfer proposal, direct or filling a sell order
+fn open_transfer_proposal(
+    station_id: Principal,
+    seller: Principal,
+    buyer: Principal,
+    equity_bps: u32,
+    ckusdc_amount: u64,
+    payment_destination: PaymentDestination,
+    sell_order: Option<String>,
+) -> Result<String, String> {
+    // Validate 0.01%-100%
+    if !(1..=TOTAL_EQUITY_BPS).contains(&equity_bps) {
+        return Err(format!("Equity must be 1-{} basis points", TOTAL_EQUITY_BPS));
     }
 
-    // Check seller has enough equity
-    let seller_equity = EQUITY_HOLDERS.with(|holders| {
-        holders.borrow()
-            .get(&PrincipalPair(station_id, seller))
-            .unwrap_or(0)
-    });
+    // Check seller has enough vested equity
+    let seller_equity = get_user_equity(station_id, seller);
 
-    if seller_equity < percentage {
-        return Err(format!("Insufficient equity: have {}%, need {}%", seller_equity, percentage));
+    if seller_equity < equity_bps {
+        return Err(format!(
+            "Insufficient equity: have {} bps, need {} bps",
+            seller_equity, equity_bps
+        ));
     }
 
-    // Generate proposal ID
-    let proposal_id = format!("{}-{}", ic_cdk::api::time(), seller.to_text());
+    // Escrow pays out to this account on execution
+    escrow::destination_account(&payment_destination)?;
+
+    // Generate proposal ID (buyers filling the same order in one round stay distinct)
+    let now = ic_cdk::api::time();
+    let proposal_id = match &sell_order {
+        Some(order_id) => format!("{}-fill-{}-{}", order_id, now, buyer.to_text()),
+        None => format!("{}-{}", now, seller.to_text()),
+    };
+    if EQUITY_TRANSFER_PROPOSALS.with(|p| p.borrow().contains_key(&StorableString(proposal_id.clone()))) {
+        return Err("A matching transfer was proposed just now; try again shortly".to_string());
+    }
 
     // Create proposal (does NOT lock seller's equity)
     let proposal = EquityTransferProposal {
@@ -88,14 +141,17 @@ pub fn create_equity_transfer_proposal(
         station_id,
         seller,
         buyer,
-        percentage,
+        equity_bps,
         ckusdc_amount,
         payment_destination,
         status: EquityProposalStatus::Proposed,
-        created_at: ic_cdk::api::time(),
-        expires_at: ic_cdk::api::time() + (7 * 24 * 60 * 60 * 1_000_000_000), // 7 days
-        yes_votes_pct: 0,
-        no_votes_pct: 0,
+        created_at: now,
+        expires_at: now + PROPOSAL_DURATION_NANOS,
+        yes_votes_bps: 0,
+        no_votes_bps: 0,
+        rofr: rofr::open_window(station_id, buyer, equity_bps, ckusdc_amount, now),
+        rofr_parent: None,
+        sell_order,
     };
 
     EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
@@ -104,12 +160,13 @@ pub fn create_equity_transfer_proposal(
             StorableCandid(proposal)
         );
     });
+    snapshot_vote_weights(&proposal_id, station_id);
 
     Ok(proposal_id)
 }
 
 #[update]
-pub fn vote_on_equity_transfer(proposal_id: String, approve: bool) -> Result<(), String> {
+pub async fn vote_on_equity_transfer(proposal_id: String, approve: bool) -> Result<(), String> {
     let voter = ic_cdk::caller();
 
     // Get proposal
@@ -132,25 +189,86 @@ pub fn vote_on_equity_transfer(proposal_id: String, approve: bool) -> Result<(),
         return Err("Proposal expired".to_string());
     }
 
-    // Get voter's equity (NOT locked - they can vote even with pending proposals)
-    let voter_equity = EQUITY_HOLDERS.with(|holders| {
-        holders.borrow()
-            .get(&PrincipalPair(proposal.station_id, voter))
-            .unwrap_or(0)
-    });
+    if proposal.status != EquityProposalStatus::Proposed {
+        return Err(format!("Voting is closed (status: {:?})", proposal.status));
+    }
+
+    if let Some(parent) = &proposal.rofr_parent {
+        return Err(format!("Right of first refusal claims are decided by the vote on {}", parent));
+    }
+
+    // ROFR claimants are parties to the transfer too
+    // Parties sit out unless they hold all the vested equity
+    let exclude_parties = equity_station_settings(proposal.station_id).exclude_parties_from_vote;
+    let mut parties = vec![proposal.seller, proposal.buyer];
+    parties.extend(rofr::claimants(&proposal_id));
+    if parties_excluded(&proposal_id, proposal.station_id, &parties, exclude_parties) && parties.contains(&voter) {
+        return Err("The seller, buyer and right of first refusal claimants can't vote on this transfer".to_string());
+    }
+
+    // Voter's equity (NOT locked - they can vote even with pending proposals)
+    let voter_equity = record_equity_vote(&proposal_id, proposal.station_id, voter, approve)?;
+    if approve {
+        proposal.yes_votes_bps = add_to_tally(proposal.yes_votes_bps, voter_equity);
+    } else {
+        proposal.no_votes_bps = add_to_tally(proposal.no_votes_bps, voter_equity);
+    }
 
+    // Check 75% threshold of the equity allowed to vote
+    let voting_equity = voting_equity_bps(&proposal_id, proposal.station_id, &parties, exclude_parties);
+    if let Some(outcome) = vote_outcome(
+        proposal.yes_votes_bps,
+        proposal.no_votes_bps,
+        voting_equity,
+        TRANSFER_APPROVAL_BPS,
+    ) {
+        proposal.status = outcome;
+    }
+
+    let rejected = proposal.status == EquityProposalStatus::Rejected;
+    let decided = proposal.status != EquityProposalStatus::Proposed;
+    save_transfer_proposal(proposal.clone());
+    if decided {
+        rofr::propagate_status(&proposal).await;
+        if rofr::close_if_fully_claimed(&mut proposal) {
+            save_transfer_proposal(proposal);
+        }
+    }
+
+    // Return any escrowed payment (the refund sweep retries on failure)
+    if rejected && escrow::escrow_for(&proposal_id).is_some() {
+        if let Err(e) = escrow::refund_escrow(&proposal_id).await {
+            ic_cdk::println!("EQUITY ESCROW: Refund for rejected {} failed: {}", proposal_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a holder's vote on a transfer or issuance proposal
+/// Returns the voter's vested equity when the proposal opened, which is the
+/// weight of the vote
+fn record_equity_vote(
+    proposal_id: &str,
+    station_id: Principal,
+    voter: Principal,
+    approve: bool,
+) -> Result<u32, String> {
+    let voter_equity = vote_weights(proposal_id, station_id)
+        .into_iter()
+        .find(|(holder, _)| *holder == voter)
+        .map_or(0, |(_, weight)| weight);
     if voter_equity == 0 {
-        return Err("No equity in this station".to_string());
+        return Err("No vested equity in this station when the proposal opened".to_string());
     }
 
     // Check not already voted
-    let vote_key = StringPrincipalPair(proposal_id.clone(), voter);
+    let vote_key = StringPrincipalPair(proposal_id.to_string(), voter);
     EQUITY_TRANSFER_VOTES.with(|votes| {
         if votes.borrow().contains_key(&vote_key) {
             return Err("Already voted".to_string());
         }
 
-        // Record vote
         votes.borrow_mut().insert(
             vote_key,
             StorableCandid(if approve { VoteChoice::Yes } else { VoteChoice::No })
@@ -158,35 +276,158 @@ pub fn vote_on_equity_transfer(proposal_id: String, approve: bool) -> Result<(),
         Ok(())
     })?;
 
-    // Update vote tallies with overflow protection
-    if approve {
-        proposal.yes_votes_pct = proposal.yes_votes_pct
-            .checked_add(voter_equity)
-            .expect("Vote tally overflow - invariant violated");
-    } else {
-        proposal.no_votes_pct = proposal.no_votes_pct
-            .checked_add(voter_equity)
-            .expect("Vote tally overflow - invariant violated");
+    Ok(voter_equity)
+}
+
+/// Add a vote to a tally, never past 100%
+fn add_to_tally(tally_bps: u32, voter_equity: u32) -> u32 {
+    tally_bps.saturating_add(voter_equity).min(TOTAL_EQUITY_BPS)
+}
+
+/// Record every holder's vested equity as a proposal opens
+/// Votes are weighed by it, so equity that changes hands during the vote
+/// isn't counted twice
+fn snapshot_vote_weights(proposal_id: &str, station_id: Principal) {
+    EQUITY_VOTE_WEIGHTS.with(|weights| {
+        let mut weights = weights.borrow_mut();
+        for (holder, _) in get_equity_holders(station_id) {
+            let vested = get_user_equity(station_id, holder);
+            if vested > 0 {
+                weights.insert(StringPrincipalPair(proposal_id.to_string(), holder), vested);
+            }
+        }
+    });
+}
+
+/// Holders' vote weights on a proposal
+/// Proposals opened before weights were recorded take them at their first vote
+fn vote_weights(proposal_id: &str, station_id: Principal) -> Vec<(Principal, u32)> {
+    let read = || EQUITY_VOTE_WEIGHTS.with(|weights| {
+        weights.borrow()
+            .range(StringPrincipalPair(proposal_id.to_string(), Principal::management_canister())..)
+            .take_while(|(key, _)| key.0 == proposal_id)
+            .map(|(key, weight)| (key.1, weight))
+            .collect::<Vec<_>>()
+    });
+    let weights = read();
+    if !weights.is_empty() {
+        return weights;
     }
+    snapshot_vote_weights(proposal_id, station_id);
+    read()
+}
 
-    // Check 75% threshold
-    if proposal.yes_votes_pct >= 75 {
-        proposal.status = EquityProposalStatus::Approved;
+/// Vested equity across a station: the total that can vote
+pub fn voting_equity_total(station_id: Principal) -> u32 {
+    TOTAL_EQUITY_BPS - vesting::station_unvested_bps(station_id, ic_cdk::api::time())
+}
+
+/// Equity that may vote on a proposal: the vested equity when it opened, less
+/// the parties' own when they are excluded
+fn voting_equity_bps(proposal_id: &str, station_id: Principal, parties: &[Principal], exclude_parties: bool) -> u32 {
+    let (total, parties_equity) = weight_split(proposal_id, station_id, parties);
+    excluded_voting_equity(total, parties_equity, exclude_parties).unwrap_or(total)
+}
+
+/// Whether the parties sit out a vote the station excludes them from
+fn parties_excluded(proposal_id: &str, station_id: Principal, parties: &[Principal], exclude_parties: bool) -> bool {
+    let (total, parties_equity) = weight_split(proposal_id, station_id, parties);
+    excluded_voting_equity(total, parties_equity, exclude_parties).is_some()
+}
+
+/// Total vote weight on a proposal, and the parties' share of it
+fn weight_split(proposal_id: &str, station_id: Principal, parties: &[Principal]) -> (u32, u32) {
+    vote_weights(proposal_id, station_id)
+        .into_iter()
+        .fold((0, 0), |(total, parties_equity), (holder, weight)| {
+            let party = if parties.contains(&holder) { weight } else { 0 };
+            (total + weight, parties_equity + party)
+        })
+}
+
+/// Equity left to vote once the parties are excluded, or None if everyone
+/// votes: the station doesn't exclude parties, or they hold all of it and
+/// nobody else could decide
+fn excluded_voting_equity(total: u32, parties_equity: u32, exclude_parties: bool) -> Option<u32> {
+    if !exclude_parties {
+        return None;
+    }
+    match total.saturating_sub(parties_equity) {
+        0 => None,
+        voting => Some(voting),
     }
+}
 
-    // Save
+/// Approved once yes-votes reach `threshold_bps` of the voting equity;
+/// rejected early once the remaining votes can no longer reach it
+fn vote_outcome(
+    yes_bps: u32,
+    no_bps: u32,
+    voting_equity: u32,
+    threshold_bps: u32,
+) -> Option<EquityProposalStatus> {
+    let required = (voting_equity as u64 * threshold_bps as u64 / TOTAL_EQUITY_BPS as u64) as u32;
+    if yes_bps >= required {
+        Some(EquityProposalStatus::Approved)
+    } else if no_bps > voting_equity - required {
+        Some(EquityProposalStatus::Rejected)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn save_transfer_proposal(proposal: EquityTransferProposal) {
     EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
         proposals.borrow_mut().insert(
-            StorableString(proposal_id),
+            StorableString(proposal.proposal_id.clone()),
             StorableCandid(proposal)
         );
     });
+}
+
+/// Withdraw a transfer proposal before it is approved (seller only)
+/// A sell order fill can also be withdrawn once approved, until the buyer's
+/// payment is escrowed
+#[update]
+pub async fn cancel_equity_transfer(proposal_id: String) -> Result<(), String> {
+    let caller = ic_cdk::caller();
+
+    let mut proposal = EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .get(&StorableString(proposal_id.clone()))
+            .map(|p| p.0.clone())
+            .ok_or("Proposal not found".to_string())
+    })?;
+
+    if caller != proposal.seller {
+        return Err("Only seller can cancel".to_string());
+    }
+    if proposal.rofr_parent.is_some() {
+        return Err("Right of first refusal claims are cancelled with the original transfer".to_string());
+    }
+    let unpaid_fill = proposal.sell_order.is_some()
+        && proposal.status == EquityProposalStatus::Approved
+        && escrow::escrow_for(&proposal_id).is_none();
+    if proposal.status != EquityProposalStatus::Proposed && !unpaid_fill {
+        return Err(format!("Only proposals awaiting approval can be cancelled (status: {:?})", proposal.status));
+    }
+
+    proposal.status = EquityProposalStatus::Cancelled;
+    save_transfer_proposal(proposal.clone());
+    rofr::propagate_status(&proposal).await;
+
+    // Return any escrowed payment (the refund sweep retries on failure)
+    if escrow::escrow_for(&proposal_id).is_some() {
+        if let Err(e) = escrow::refund_escrow(&proposal_id).await {
+            ic_cdk::println!("EQUITY ESCROW: Refund for cancelled {} failed: {}", proposal_id, e);
+        }
+    }
 
     Ok(())
 }
 
 #[update]
-pub fn execute_equity_transfer(proposal_id: String) -> Result<(), String> {
+pub async fn execute_equity_transfer(proposal_id: String) -> Result<(), String> {
     let caller = ic_cdk::caller();
 
     // Get proposal
@@ -207,7 +448,153 @@ pub fn execute_equity_transfer(proposal_id: String) -> Result<(), String> {
         return Err(format!("Not approved (status: {:?})", proposal.status));
     }
 
-    // MVP: Trust-based payment (no verification)
+    let now = ic_cdk::api::time();
+    if now > proposal.expires_at {
+        return Err("Proposal expired".to_string());
+    }
+
+    // The outside buyer only gets what holders left once the ROFR window closes
+    if rofr::is_window_open(&proposal, now) {
+        return Err("The right of first refusal window is still open".to_string());
+    }
+
+    // Holders took up the whole offer: nothing is left to move
+    if proposal.equity_bps == 0 {
+        proposal.status = EquityProposalStatus::Executed;
+        save_transfer_proposal(proposal);
+        return Ok(());
+    }
+
+    // Release escrowed payment and move equity together: the station stays locked
+    // while the ledger call is in flight, so the seller's equity can't change under it
+    let station_id = proposal.station_id;
+    let settlement = begin_settlement(station_id)?;
+    let settled = settle_equity_transfer(&proposal).await;
+    drop(settlement);
+    settled?;
+
+    // Mark executed
+    proposal.status = EquityProposalStatus::Executed;
+    EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow_mut().insert(
+            StorableString(proposal_id),
+            StorableCandid(proposal)
+        );
+    });
+
+    Ok(())
+}
+
+/// A station's settlement lock, released when dropped
+/// ic-cdk drops the future when a callback traps, so a trap after an await
+/// can't leave the station locked
+struct SettlementGuard(Principal);
+
+impl Drop for SettlementGuard {
+    fn drop(&mut self) {
+        SETTLING_STATIONS.with(|s| s.borrow_mut().remove(&self.0));
+    }
+}
+
+/// Take a station's settlement lock; equity can't move under a pending ledger call
+fn begin_settlement(station_id: Principal) -> Result<SettlementGuard, String> {
+    if !SETTLING_STATIONS.with(|s| s.borrow_mut().insert(station_id)) {
+        return Err("Another equity change is settling for this station".to_string());
+    }
+    Ok(SettlementGuard(station_id))
+}
+
+/// Check a station's cap table after a change and record it in the history
+fn commit_cap_table(station_id: Principal, event: CapTableEvent) {
+    assert_cap_table_invariant(station_id);
+    history::record_snapshot(station_id, event);
+}
+
+/// Every change to a cap table must leave it at exactly 100% with no empty
+/// holdings, and every holder's unvested equity covered by their holding
+fn assert_cap_table_invariant(station_id: Principal) {
+    let holdings = get_equity_holders(station_id);
+    let total: u64 = holdings.iter().map(|(_, bps)| *bps as u64).sum();
+
+    if total != TOTAL_EQUITY_BPS as u64 {
+        panic!("CRITICAL: Equity invariant violated! Total = {} bps", total);
+    }
+    if holdings.iter().any(|(_, bps)| *bps == 0) {
+        panic!("CRITICAL: Equity invariant violated! Zero holding recorded");
+    }
+
+    let now = ic_cdk::api::time();
+    for (holder, bps) in &holdings {
+        let unvested = vesting::unvested_bps(station_id, *holder, now);
+        if unvested > *bps {
+            panic!("CRITICAL: Equity invariant violated! {} has {} bps unvested of {} bps", holder, unvested, bps);
+        }
+    }
+}
+
+/// Scale holdings summing to `from_total` so they sum to exactly `to_total`
+/// Each holding is scaled pro rata; the basis points lost to rounding go to the
+/// largest remainders (ties to the larger holding, then by principal).
+/// Holdings that round to nothing stay in the result at 0.
+fn rescale_holdings(
+    holdings: &[(Principal, u32)],
+    from_total: u32,
+    to_total: u32,
+) -> Vec<(Principal, u32)> {
+    // (holder, scaled bps, remainder)
+    let mut scaled: Vec<(Principal, u32, u64)> = holdings.iter()
+        .map(|(holder, bps)| {
+            let exact = *bps as u64 * to_total as u64;
+            (*holder, (exact / from_total as u64) as u32, exact % from_total as u64)
+        })
+        .collect();
+
+    let floored: u64 = scaled.iter().map(|(_, bps, _)| *bps as u64).sum();
+    let leftover = (to_total as u64).saturating_sub(floored) as usize;
+
+    let mut order: Vec<usize> = (0..scaled.len()).collect();
+    order.sort_by(|a, b| {
+        let (ha, ba, ra) = &scaled[*a];
+        let (hb, bb, rb) = &scaled[*b];
+        rb.cmp(ra).then(bb.cmp(ba)).then(ha.cmp(hb))
+    });
+    for index in order.into_iter().take(leftover) {
+        scaled[index].1 += 1;
+    }
+
+    scaled.into_iter().map(|(holder, bps, _)| (holder, bps)).collect()
+}
+
+/// Replace a station's holdings, dropping anyone left with nothing
+fn write_holdings(station_id: Principal, old: &[(Principal, u32)], new: &[(Principal, u32)]) {
+    EQUITY_HOLDERS.with(|holders| {
+        let mut holders_map = holders.borrow_mut();
+        for (holder, _) in old {
+            holders_map.remove(&PrincipalPair(station_id, *holder));
+        }
+        for (holder, bps) in new.iter().filter(|(_, bps)| *bps > 0) {
+            holders_map.insert(PrincipalPair(station_id, *holder), *bps);
+        }
+    });
+}
+
+/// Pay the seller from escrow, then move the equity
+async fn settle_equity_transfer(proposal: &EquityTransferProposal) -> Result<(), String> {
+    let seller_equity = get_user_equity(proposal.station_id, proposal.seller);
+    if seller_equity < proposal.equity_bps {
+        return Err(format!(
+            "Seller no longer has enough equity (has {} bps, needs {} bps)",
+            seller_equity,
+            proposal.equity_bps
+        ));
+    }
+
+    if proposal.ckusdc_amount > 0 {
+        escrow::release_escrow(&proposal.proposal_id).await?;
+    }
+
+    // Only vested equity can be sold
+    let seller_unvested = vesting::unvested_bps(proposal.station_id, proposal.seller, ic_cdk::api::time());
 
     // Execute transfer
     EQUITY_HOLDERS.with(|holders| {
@@ -221,44 +608,31 @@ pub fn execute_equity_transfer(proposal_id: String) -> Result<(), String> {
             .get(&PrincipalPair(proposal.station_id, proposal.buyer))
             .unwrap_or(0);
 
-        // Sanity check: seller must STILL have enough
-        if seller_equity < proposal.percentage {
+        // Sanity check: seller must STILL have enough vested equity
+        if seller_equity.saturating_sub(seller_unvested) < proposal.equity_bps {
             return Err(format!(
-                "Seller no longer has enough equity (has {}%, needs {}%)",
+                "Seller no longer has enough equity (has {} bps, needs {} bps)",
                 seller_equity,
-                proposal.percentage
+                proposal.equity_bps
             ));
         }
 
-        // Update: Seller -= X%, Buyer += X%
-        let new_seller_equity = seller_equity - proposal.percentage;
-        let new_buyer_equity = buyer_equity + proposal.percentage;
-
-        holders_map.insert(PrincipalPair(proposal.station_id, proposal.seller), new_seller_equity);
-        holders_map.insert(PrincipalPair(proposal.station_id, proposal.buyer), new_buyer_equity);
-
-        // Invariant check: Total must = 100%
-        let total: u32 = holders_map.iter()
-            .filter(|(k, _)| k.0 == proposal.station_id)
-            .map(|(_, pct)| pct as u32)
-            .sum();
+        // Update: Seller -= X bps, Buyer += X bps
+        let new_seller_equity = seller_equity - proposal.equity_bps;
+        let new_buyer_equity = buyer_equity + proposal.equity_bps;
 
-        if total != 100 {
-            panic!("CRITICAL: Equity invariant violated! Total = {}%", total);
+        // A seller who sold everything is no longer a holder
+        if new_seller_equity == 0 {
+            holders_map.remove(&PrincipalPair(proposal.station_id, proposal.seller));
+        } else {
+            holders_map.insert(PrincipalPair(proposal.station_id, proposal.seller), new_seller_equity);
         }
+        holders_map.insert(PrincipalPair(proposal.station_id, proposal.buyer), new_buyer_equity);
 
         Ok(())
     })?;
 
-    // Mark executed
-    proposal.status = EquityProposalStatus::Executed;
-    EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
-        proposals.borrow_mut().insert(
-            StorableString(proposal_id),
-            StorableCandid(proposal)
-        );
-    });
-
+    commit_cap_table(proposal.station_id, CapTableEvent::Transfer(proposal.proposal_id.clone()));
     Ok(())
 }
 
@@ -266,8 +640,16 @@ pub fn execute_equity_transfer(proposal_id: String) -> Result<(), String> {
 // QUERY METHODS
 // ============================================================================
 
+/// A holder's vested equity in basis points (10_000 = 100%)
+/// This is what votes and can be transferred; see get_vesting_schedule for the rest
 #[query]
-pub fn get_user_equity(station_id: Principal, user: Principal) -> u8 {
+pub fn get_user_equity(station_id: Principal, user: Principal) -> u32 {
+    holding_bps(station_id, user)
+        .saturating_sub(vesting::unvested_bps(station_id, user, ic_cdk::api::time()))
+}
+
+/// A holder's recorded equity, vested or not
+pub(crate) fn holding_bps(station_id: Principal, user: Principal) -> u32 {
     EQUITY_HOLDERS.with(|holders| {
         holders.borrow()
             .get(&PrincipalPair(station_id, user))
@@ -275,13 +657,14 @@ pub fn get_user_equity(station_id: Principal, user: Principal) -> u8 {
     })
 }
 
+/// All holders of a station with their recorded equity in basis points (vested or not)
 #[query]
-pub fn get_equity_holders(station_id: Principal) -> Vec<(Principal, u8)> {
+pub fn get_equity_holders(station_id: Principal) -> Vec<(Principal, u32)> {
     EQUITY_HOLDERS.with(|holders| {
         holders.borrow()
             .iter()
             .filter(|(k, _)| k.0 == station_id)
-            .map(|(k, pct)| (k.1, pct))
+            .map(|(k, bps)| (k.1, bps))
             .collect()
     })
 }
@@ -312,3 +695,73 @@ pub fn is_equity_station(station_id: Principal) -> bool {
         stations.borrow().contains_key(&StorablePrincipal(station_id))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(id: u8) -> Principal {
+        Principal::from_slice(&[id])
+    }
+
+    #[test]
+    fn rescale_gives_leftover_to_largest_remainders() {
+        // 1/3 each of 10,000: 3,333 each plus one left over for the lowest principal
+        let holdings = [(holder(3), 1), (holder(1), 1), (holder(2), 1)];
+        let scaled = rescale_holdings(&holdings, 3, TOTAL_EQUITY_BPS);
+        assert_eq!(scaled, vec![(holder(3), 3_333), (holder(1), 3_334), (holder(2), 3_333)]);
+    }
+
+    #[test]
+    fn rescale_breaks_remainder_ties_by_holding_size() {
+        // 4 -> 10: floors 2 and 7 with equal remainders; the larger holding gets the unit
+        let holdings = [(holder(1), 1), (holder(2), 3)];
+        assert_eq!(rescale_holdings(&holdings, 4, 10), vec![(holder(1), 2), (holder(2), 8)]);
+    }
+
+    #[test]
+    fn rescale_keeps_holdings_that_round_to_nothing() {
+        let holdings = [(holder(1), 1_000_000), (holder(2), 1)];
+        let scaled = rescale_holdings(&holdings, 1_000_001, TOTAL_EQUITY_BPS);
+        assert_eq!(scaled, vec![(holder(1), TOTAL_EQUITY_BPS), (holder(2), 0)]);
+    }
+
+    #[test]
+    fn vote_approves_at_threshold() {
+        assert_eq!(vote_outcome(7_500, 0, 10_000, 7_500), Some(EquityProposalStatus::Approved));
+        assert_eq!(vote_outcome(7_499, 0, 10_000, 7_500), None);
+    }
+
+    #[test]
+    fn vote_rejects_once_threshold_is_out_of_reach() {
+        assert_eq!(vote_outcome(0, 2_500, 10_000, 7_500), None);
+        assert_eq!(vote_outcome(0, 2_501, 10_000, 7_500), Some(EquityProposalStatus::Rejected));
+    }
+
+    #[test]
+    fn tally_stops_at_full_equity() {
+        assert_eq!(add_to_tally(6_000, 3_000), 9_000);
+        assert_eq!(add_to_tally(9_000, 2_000), TOTAL_EQUITY_BPS);
+        assert_eq!(add_to_tally(u32::MAX, 1), TOTAL_EQUITY_BPS);
+    }
+
+    #[test]
+    fn excluded_parties_leave_the_rest_to_vote() {
+        assert_eq!(excluded_voting_equity(10_000, 4_000, true), Some(6_000));
+        assert_eq!(excluded_voting_equity(10_000, 4_000, false), None);
+    }
+
+    #[test]
+    fn parties_holding_everything_still_vote() {
+        // A sole holder selling to an outsider, or an issuance to the only holder
+        assert_eq!(excluded_voting_equity(10_000, 10_000, true), None);
+        assert_eq!(excluded_voting_equity(8_000, 9_000, true), None);
+    }
+
+    #[test]
+    fn vote_threshold_follows_voting_equity() {
+        // Parties excluded: 6,000 bps may vote, so 4,500 yes is 75%
+        assert_eq!(vote_outcome(4_500, 0, 6_000, 7_500), Some(EquityProposalStatus::Approved));
+        assert_eq!(vote_outcome(0, 1_501, 6_000, 7_500), Some(EquityProposalStatus::Rejected));
+    }
+}
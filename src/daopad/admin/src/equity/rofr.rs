@@ -0,0 +1,201 @@
+// Right of first refusal on equity transfers
+//
+// When a station enables it, a transfer to an outside buyer (anyone holding
+// no equity yet) first opens a window in which existing holders can take up
+// the offer pro rata at the same price. Each claim becomes its own transfer
+// from the seller to the holder, decided by the original proposal's vote. The
+// outside buyer keeps only what is left and can't pay or execute until the
+// window has closed. If the claims take the whole offer, the original transfer
+// is closed (Cancelled) once approved and the claims carry on by themselves.
+// Where a station excludes the parties from its vote, claimants count as
+// parties too, so a holder who has voted on the transfer can't then claim.
+
+use super::settings::equity_station_settings;
+use super::{escrow, get_equity_holders, get_user_equity, holding_bps, save_transfer_proposal};
+use crate::proposals::types::{EquityProposalStatus, EquityTransferProposal, RofrWindow};
+use crate::storage::state::{EQUITY_TRANSFER_PROPOSALS, EQUITY_TRANSFER_VOTES};
+use crate::types::{StorableString, StringPrincipalPair};
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::update;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Window for a new transfer, if the station has ROFR on and the buyer is an outsider
+pub(crate) fn open_window(
+    station_id: Principal,
+    buyer: Principal,
+    equity_bps: u32,
+    ckusdc_amount: u64,
+    now: u64,
+) -> Option<RofrWindow> {
+    let window_seconds = equity_station_settings(station_id).rofr_window_seconds.filter(|s| *s > 0)?;
+    if holding_bps(station_id, buyer) > 0 {
+        return None;
+    }
+    Some(RofrWindow {
+        ends_at: now + window_seconds * NANOS_PER_SEC,
+        offered_bps: equity_bps,
+        offered_ckusdc: ckusdc_amount,
+    })
+}
+
+/// Whether holders can still take up the offer
+pub(crate) fn is_window_open(proposal: &EquityTransferProposal, now: u64) -> bool {
+    proposal.rofr.as_ref().is_some_and(|window| now < window.ends_at)
+}
+
+/// When the outside buyer may pay into escrow (0 without a window)
+pub(crate) fn buyer_eligible_at(proposal: &EquityTransferProposal) -> u64 {
+    proposal.rofr.as_ref().map(|window| window.ends_at).unwrap_or(0)
+}
+
+fn claims_on(parent_id: &str) -> Vec<EquityTransferProposal> {
+    EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .iter()
+            .map(|(_, p)| p.0)
+            .filter(|p| p.rofr_parent.as_deref() == Some(parent_id))
+            .collect()
+    })
+}
+
+/// Holders who have taken up part of a transfer's offer
+pub(crate) fn claimants(parent_id: &str) -> Vec<Principal> {
+    claims_on(parent_id).into_iter().map(|claim| claim.buyer).collect()
+}
+
+/// Close an approved transfer whose offer the claims took in full
+/// Returns whether it was closed; the caller saves it
+pub(crate) fn close_if_fully_claimed(parent: &mut EquityTransferProposal) -> bool {
+    let taken = parent.rofr.is_some() && parent.equity_bps == 0 && parent.ckusdc_amount == 0;
+    if taken && parent.status == EquityProposalStatus::Approved {
+        parent.status = EquityProposalStatus::Cancelled;
+        return true;
+    }
+    false
+}
+
+/// A holder's pro rata share of the offer: the offer scaled by their vested
+/// equity over the vested equity of every holder but the seller
+fn entitlement_bps(parent: &EquityTransferProposal, window: &RofrWindow, holder: Principal) -> u32 {
+    let eligible: u64 = get_equity_holders(parent.station_id)
+        .into_iter()
+        .filter(|(h, _)| *h != parent.seller)
+        .map(|(h, _)| get_user_equity(parent.station_id, h) as u64)
+        .sum();
+    if eligible == 0 {
+        return 0;
+    }
+    let holder_equity = get_user_equity(parent.station_id, holder) as u64;
+    (window.offered_bps as u64 * holder_equity / eligible) as u32
+}
+
+/// Carry a transfer's vote outcome (or withdrawal) over to the ROFR claims on it
+pub(crate) async fn propagate_status(parent: &EquityTransferProposal) {
+    for mut claim in claims_on(&parent.proposal_id) {
+        if !matches!(claim.status, EquityProposalStatus::Proposed | EquityProposalStatus::Approved) {
+            continue;
+        }
+        claim.status = parent.status.clone();
+        claim.yes_votes_bps = parent.yes_votes_bps;
+        claim.no_votes_bps = parent.no_votes_bps;
+        let closed = matches!(
+            claim.status,
+            EquityProposalStatus::Rejected | EquityProposalStatus::Cancelled
+        );
+        let claim_id = claim.proposal_id.clone();
+        save_transfer_proposal(claim);
+
+        // Return any escrowed payment (the refund sweep retries on failure)
+        if closed && escrow::escrow_for(&claim_id).is_some() {
+            if let Err(e) = escrow::refund_escrow(&claim_id).await {
+                ic_cdk::println!("EQUITY ESCROW: Refund for ROFR claim {} failed: {}", claim_id, e);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Take up `equity_bps` of a transfer's offer at the same price (existing holders only)
+/// Returns the ID of the new transfer from the seller to the caller, which is
+/// decided by the original proposal's vote and then escrowed and executed as usual
+#[update]
+pub fn exercise_right_of_first_refusal(proposal_id: String, equity_bps: u32) -> Result<String, String> {
+    let holder = ic_cdk::caller();
+    let mut parent = EQUITY_TRANSFER_PROPOSALS.with(|proposals| {
+        proposals.borrow()
+            .get(&StorableString(proposal_id.clone()))
+            .map(|p| p.0)
+            .ok_or("Proposal not found".to_string())
+    })?;
+
+    let window = parent.rofr.clone()
+        .ok_or("This transfer has no right of first refusal".to_string())?;
+    let now = time();
+    if !is_window_open(&parent, now) {
+        return Err("The right of first refusal window has closed".to_string());
+    }
+    if !matches!(parent.status, EquityProposalStatus::Proposed | EquityProposalStatus::Approved) {
+        return Err(format!("Transfer can no longer execute (status: {:?})", parent.status));
+    }
+    if holder == parent.seller {
+        return Err("The seller can't exercise a right of first refusal on their own offer".to_string());
+    }
+
+    if equity_station_settings(parent.station_id).exclude_parties_from_vote {
+        let voted = EQUITY_TRANSFER_VOTES.with(|votes| {
+            votes.borrow().contains_key(&StringPrincipalPair(proposal_id.clone(), holder))
+        });
+        if voted {
+            return Err("You voted on this transfer, so you can't take up its offer".to_string());
+        }
+    }
+
+    let claim_id = format!("{}-rofr-{}", proposal_id, holder.to_text());
+    if EQUITY_TRANSFER_PROPOSALS.with(|p| p.borrow().contains_key(&StorableString(claim_id.clone()))) {
+        return Err("Already exercised".to_string());
+    }
+
+    let entitlement = entitlement_bps(&parent, &window, holder).min(parent.equity_bps);
+    if entitlement == 0 {
+        return Err("No vested equity to exercise with".to_string());
+    }
+    if equity_bps == 0 || equity_bps > entitlement {
+        return Err(format!("You can take up 1-{} basis points of this offer", entitlement));
+    }
+
+    // Same price per basis point as the offer, rounded up in the seller's favour
+    let price = (window.offered_ckusdc as u128 * equity_bps as u128)
+        .div_ceil(window.offered_bps as u128) as u64;
+    let price = price.min(parent.ckusdc_amount);
+
+    save_transfer_proposal(EquityTransferProposal {
+        proposal_id: claim_id.clone(),
+        station_id: parent.station_id,
+        seller: parent.seller,
+        buyer: holder,
+        equity_bps,
+        ckusdc_amount: price,
+        payment_destination: parent.payment_destination.clone(),
+        status: parent.status.clone(),
+        created_at: now,
+        expires_at: parent.expires_at,
+        yes_votes_bps: parent.yes_votes_bps,
+        no_votes_bps: parent.no_votes_bps,
+        rofr: None,
+        rofr_parent: Some(proposal_id),
+        sell_order: parent.sell_order.clone(),
+    });
+
+    // The outside buyer is left with the rest
+    parent.equity_bps -= equity_bps;
+    parent.ckusdc_amount -= price;
+    close_if_fully_claimed(&mut parent);
+    save_transfer_proposal(parent);
+
+    Ok(claim_id)
+}
@@ -0,0 +1,66 @@
+// Per-station equity governance settings
+//
+// Settings can only be changed by the equity station itself, i.e. through an
+// Orbit CallExternalCanister request approved by the equity holders. Until a
+// station sets anything, the defaults below apply.
+
+use crate::storage::state::EQUITY_SETTINGS;
+use crate::types::{StorableCandid, StorablePrincipal};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+
+/// Governance settings for one equity station
+#[derive(CandidType, Deserialize, Clone, Debug, Default)]
+pub struct EquityStationSettings {
+    // Seller and buyer can't vote on their own transfer (nor a recipient on
+    // their own issuance); the threshold then applies to everyone else's equity
+    pub exclude_parties_from_vote: bool,
+    // Transfers to outside buyers first give existing holders this long to
+    // take up the offer pro rata (None or 0 = off); see rofr.rs
+    pub rofr_window_seconds: Option<u64>,
+}
+
+/// Settings for a station, falling back to defaults
+pub fn equity_station_settings(station_id: Principal) -> EquityStationSettings {
+    EQUITY_SETTINGS.with(|settings| {
+        settings.borrow()
+            .get(&StorablePrincipal(station_id))
+            .map(|s| s.0)
+            .unwrap_or_default()
+    })
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Replace a station's equity settings
+/// ONLY the station itself can call this (via a CallExternalCanister request)
+#[update]
+pub fn set_equity_station_settings(
+    station_id: Principal,
+    settings: EquityStationSettings,
+) -> Result<(), String> {
+    if ic_cdk::caller() != station_id {
+        return Err("Only the equity station can change its settings".to_string());
+    }
+    if !super::is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+    // The buyer still needs time to pay and execute before the proposal expires
+    let window_nanos = settings.rofr_window_seconds.unwrap_or(0).checked_mul(1_000_000_000);
+    if window_nanos.is_none_or(|nanos| nanos >= super::PROPOSAL_DURATION_NANOS) {
+        return Err("Right of first refusal window must be shorter than 7 days".to_string());
+    }
+
+    EQUITY_SETTINGS.with(|all| {
+        all.borrow_mut().insert(StorablePrincipal(station_id), StorableCandid(settings));
+    });
+
+    Ok(())
+}
+
+#[query]
+pub fn get_equity_station_settings(station_id: Principal) -> EquityStationSettings {
+    equity_station_settings(station_id)
+}
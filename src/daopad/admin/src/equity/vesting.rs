@@ -0,0 +1,254 @@
+// Cliff-and-linear vesting for equity grants
+//
+// A grant marks part of a holder's recorded equity as vesting: nothing vests
+// before the cliff, then the grant vests linearly until start + duration.
+// Unvested equity stays on the cap table (holdings always sum to 100%) but is
+// left out of get_user_equity, so it can neither vote nor be transferred.
+//
+// Grants come from issuances with vesting terms or from the station itself.
+// Only the station can revoke a grant, i.e. through an Orbit request approved
+// by an equity vote; the unvested remainder is then redistributed pro rata.
+
+use super::{
+    begin_settlement, get_equity_holders, holding_bps, is_equity_station,
+    rescale_holdings, write_holdings, commit_cap_table, TOTAL_EQUITY_BPS,
+};
+use super::history::CapTableEvent;
+use crate::storage::state::VESTING_GRANTS;
+use crate::types::{StorableCandid, StorableString};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// When and how fast a grant vests
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VestingTerms {
+    pub start_at: u64,          // Nanoseconds since epoch
+    pub cliff_seconds: u64,     // Nothing vests before start + cliff
+    pub duration_seconds: u64,  // Fully vested at start + duration
+}
+
+/// Equity granted to a holder subject to vesting
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VestingGrant {
+    pub grant_id: String,
+    pub station_id: Principal,
+    pub holder: Principal,
+    pub total_bps: u32,  // Rescaled with the cap table on issuance and revocation
+    pub terms: VestingTerms,
+    pub granted_at: u64,
+    pub revoked_at: Option<u64>,  // Revoked grants keep only what had vested
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VestingGrantStatus {
+    pub grant: VestingGrant,
+    pub vested_bps: u32,
+    pub unvested_bps: u32,
+}
+
+/// A holder's equity split by vesting state
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VestingSchedule {
+    pub station_id: Principal,
+    pub holder: Principal,
+    pub holding_bps: u32,   // Recorded on the cap table
+    pub vested_bps: u32,    // Usable for voting and transfers
+    pub unvested_bps: u32,
+    pub grants: Vec<VestingGrantStatus>,
+}
+
+pub(crate) fn validate_terms(terms: &VestingTerms) -> Result<(), String> {
+    if terms.duration_seconds == 0 {
+        return Err("Vesting duration must be positive".to_string());
+    }
+    if terms.cliff_seconds > terms.duration_seconds {
+        return Err("Vesting cliff can't be longer than the duration".to_string());
+    }
+    Ok(())
+}
+
+/// Basis points of a grant vested at `now`
+fn vested_bps(grant: &VestingGrant, now: u64) -> u32 {
+    if grant.revoked_at.is_some() {
+        return grant.total_bps;
+    }
+    let elapsed = now.saturating_sub(grant.terms.start_at) / NANOS_PER_SEC;
+    if now < grant.terms.start_at || elapsed < grant.terms.cliff_seconds {
+        0
+    } else if elapsed >= grant.terms.duration_seconds {
+        grant.total_bps
+    } else {
+        (grant.total_bps as u64 * elapsed / grant.terms.duration_seconds) as u32
+    }
+}
+
+fn station_grants(station_id: Principal) -> Vec<VestingGrant> {
+    VESTING_GRANTS.with(|grants| {
+        grants.borrow()
+            .iter()
+            .map(|(_, g)| g.0)
+            .filter(|g| g.station_id == station_id)
+            .collect()
+    })
+}
+
+fn save_grant(grant: VestingGrant) {
+    VESTING_GRANTS.with(|grants| {
+        grants.borrow_mut().insert(StorableString(grant.grant_id.clone()), StorableCandid(grant));
+    });
+}
+
+/// Unvested equity of one holder
+pub(crate) fn unvested_bps(station_id: Principal, holder: Principal, now: u64) -> u32 {
+    station_grants(station_id)
+        .iter()
+        .filter(|g| g.holder == holder)
+        .map(|g| g.total_bps - vested_bps(g, now))
+        .sum()
+}
+
+/// Unvested equity across a station
+pub(crate) fn station_unvested_bps(station_id: Principal, now: u64) -> u32 {
+    station_grants(station_id)
+        .iter()
+        .map(|g| g.total_bps - vested_bps(g, now))
+        .sum()
+}
+
+/// Record a grant over equity the holder already has on the cap table
+pub(crate) fn record_grant(
+    station_id: Principal,
+    holder: Principal,
+    total_bps: u32,
+    terms: VestingTerms,
+) -> String {
+    let now = time();
+    let grant_id = format!("vest-{}-{}", now, holder.to_text());
+    save_grant(VestingGrant {
+        grant_id: grant_id.clone(),
+        station_id,
+        holder,
+        total_bps,
+        terms,
+        granted_at: now,
+        revoked_at: None,
+    });
+    grant_id
+}
+
+/// Rescale a station's grants along with its cap table (from `from_total` to
+/// `to_total` basis points). Rounding down keeps every grant within its holding.
+pub(crate) fn rescale_grants(station_id: Principal, from_total: u32, to_total: u32) {
+    for mut grant in station_grants(station_id) {
+        grant.total_bps = (grant.total_bps as u64 * to_total as u64 / from_total as u64) as u32;
+        save_grant(grant);
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Put `total_bps` of a holder's existing equity under vesting
+/// ONLY the station itself can call this (via a CallExternalCanister request)
+#[update]
+pub fn grant_vesting(
+    station_id: Principal,
+    holder: Principal,
+    total_bps: u32,
+    terms: VestingTerms,
+) -> Result<String, String> {
+    if ic_cdk::caller() != station_id {
+        return Err("Only the equity station can grant vesting".to_string());
+    }
+    if !is_equity_station(station_id) {
+        return Err("Not an equity station".to_string());
+    }
+    validate_terms(&terms)?;
+
+    // Only equity not already vesting can be placed under a new grant
+    let free = holding_bps(station_id, holder).saturating_sub(unvested_bps(station_id, holder, time()));
+    if total_bps == 0 || total_bps > free {
+        return Err(format!("Grant must be 1-{} basis points (the holder's vested equity)", free));
+    }
+
+    let grant_id = record_grant(station_id, holder, total_bps, terms);
+    commit_cap_table(station_id, CapTableEvent::VestingGranted(grant_id.clone()));
+    Ok(grant_id)
+}
+
+/// Revoke a grant: the holder keeps what has vested and the unvested
+/// remainder is redistributed to all holders pro rata
+/// ONLY the station itself can call this (via a CallExternalCanister request)
+#[update]
+pub fn revoke_vesting_grant(grant_id: String) -> Result<(), String> {
+    let mut grant = VESTING_GRANTS.with(|grants| {
+        grants.borrow()
+            .get(&StorableString(grant_id.clone()))
+            .map(|g| g.0)
+            .ok_or("Grant not found".to_string())
+    })?;
+
+    let station_id = grant.station_id;
+    if ic_cdk::caller() != station_id {
+        return Err("Only the equity station can revoke vesting".to_string());
+    }
+    if grant.revoked_at.is_some() {
+        return Err("Grant already revoked".to_string());
+    }
+
+    let now = time();
+    let vested = vested_bps(&grant, now);
+    let forfeited = grant.total_bps - vested;
+    let remaining = TOTAL_EQUITY_BPS - forfeited;
+    if remaining == 0 {
+        return Err("No other equity to redistribute the unvested grant to".to_string());
+    }
+
+    let settlement = begin_settlement(station_id)?;
+
+    grant.total_bps = vested;
+    grant.revoked_at = Some(now);
+    save_grant(grant.clone());
+
+    if forfeited > 0 {
+        let holdings: Vec<(Principal, u32)> = get_equity_holders(station_id)
+            .into_iter()
+            .map(|(h, bps)| if h == grant.holder { (h, bps.saturating_sub(forfeited)) } else { (h, bps) })
+            .collect();
+        let redistributed = rescale_holdings(&holdings, remaining, TOTAL_EQUITY_BPS);
+        write_holdings(station_id, &holdings, &redistributed);
+        rescale_grants(station_id, remaining, TOTAL_EQUITY_BPS);
+    }
+
+    drop(settlement);
+    commit_cap_table(station_id, CapTableEvent::VestingRevoked(grant_id));
+    Ok(())
+}
+
+#[query]
+pub fn get_vesting_schedule(station_id: Principal, holder: Principal) -> VestingSchedule {
+    let now = time();
+    let grants: Vec<VestingGrantStatus> = station_grants(station_id)
+        .into_iter()
+        .filter(|g| g.holder == holder)
+        .map(|grant| {
+            let vested = vested_bps(&grant, now);
+            VestingGrantStatus { unvested_bps: grant.total_bps - vested, vested_bps: vested, grant }
+        })
+        .collect();
+
+    let holding = holding_bps(station_id, holder);
+    let unvested: u32 = grants.iter().map(|g| g.unvested_bps).sum();
+    VestingSchedule {
+        station_id,
+        holder,
+        holding_bps: holding,
+        vested_bps: holding.saturating_sub(unvested),
+        unvested_bps: unvested,
+        grants,
+    }
+}
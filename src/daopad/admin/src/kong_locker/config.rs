@@ -0,0 +1,180 @@
+// Per-DAO voting power settings for token (Kong Locker) stations
+//
+// Settings can only be changed by the DAO's own Orbit Station, i.e. through a
+// community-approved CallExternalCanister request. Until a DAO sets anything,
+// the defaults below apply.
+
+use crate::storage::state::VOTING_POWER_CONFIGS;
+use crate::types::{StorableCandid, StorablePrincipal};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+
+const BACKEND_CANISTER_ID: &str = "lwsav-iiaaa-aaaap-qp2qq-cai";
+
+// TWAP window bounds: long enough to resist short price pushes,
+// short enough to stay within the price sample retention
+pub const MIN_TWAP_WINDOW_SECONDS: u64 = 60 * 60;            // 1 hour
+pub const MAX_TWAP_WINDOW_SECONDS: u64 = 7 * 24 * 60 * 60;   // 7 days
+const DEFAULT_TWAP_WINDOW_SECONDS: u64 = 24 * 60 * 60;       // 1 day
+
+// Lock age multiplier bounds, in basis points (10_000 = 1x)
+pub const BASE_MULTIPLIER_BPS: u32 = 10_000;
+const MAX_MULTIPLIER_BPS: u32 = 50_000;                        // 5x
+const MAX_LOCK_AGE_POINTS: usize = 16;
+
+// Per-voter cap bounds, as a share of a proposal's total voting power
+const MIN_VOTER_CAP_BPS: u32 = 100;                            // 1%
+const MAX_VOTER_CAP_BPS: u32 = 10_000;                         // 100% (no cap)
+
+/// One point of a lock age multiplier curve
+/// Multipliers are interpolated linearly between points and hold after the last one
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LockAgePoint {
+    pub age_seconds: u64,
+    pub multiplier_bps: u32,
+}
+
+/// What happens to a voter's power above the cap
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum VoterCapPolicy {
+    Drop,    // Excess is discarded but stays in the proposal's total (counts like an abstention)
+    Spread,  // Excess is removed from the proposal's total, raising every other holder's share
+}
+
+/// Limit on how much of a proposal's total voting power one principal can contribute
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VoterCap {
+    pub max_share_bps: u32,
+    pub policy: VoterCapPolicy,
+}
+
+/// Voting power settings for one token DAO
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VotingPowerConfig {
+    pub twap_window_seconds: u64,
+    // None = every locked position counts 1x regardless of age
+    pub lock_age_multiplier: Option<Vec<LockAgePoint>>,
+    // None = no per-voter cap
+    pub voter_cap: Option<VoterCap>,
+}
+
+impl Default for VotingPowerConfig {
+    fn default() -> Self {
+        VotingPowerConfig {
+            twap_window_seconds: DEFAULT_TWAP_WINDOW_SECONDS,
+            lock_age_multiplier: None,
+            voter_cap: None,
+        }
+    }
+}
+
+impl VotingPowerConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.twap_window_seconds < MIN_TWAP_WINDOW_SECONDS
+            || self.twap_window_seconds > MAX_TWAP_WINDOW_SECONDS
+        {
+            return Err(format!(
+                "TWAP window must be between {} and {} seconds",
+                MIN_TWAP_WINDOW_SECONDS, MAX_TWAP_WINDOW_SECONDS
+            ));
+        }
+
+        if let Some(curve) = &self.lock_age_multiplier {
+            validate_lock_age_curve(curve)?;
+        }
+
+        if let Some(cap) = &self.voter_cap {
+            if cap.max_share_bps < MIN_VOTER_CAP_BPS || cap.max_share_bps > MAX_VOTER_CAP_BPS {
+                return Err(format!(
+                    "Voter cap must be between {} and {} basis points",
+                    MIN_VOTER_CAP_BPS, MAX_VOTER_CAP_BPS
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A curve starts at age 0, has strictly increasing ages and never decreases
+fn validate_lock_age_curve(curve: &[LockAgePoint]) -> Result<(), String> {
+    if curve.is_empty() || curve.len() > MAX_LOCK_AGE_POINTS {
+        return Err(format!("Lock age curve must have 1 to {} points", MAX_LOCK_AGE_POINTS));
+    }
+    if curve[0].age_seconds != 0 {
+        return Err("Lock age curve must start at age 0".to_string());
+    }
+    if curve.iter().any(|p| p.multiplier_bps < BASE_MULTIPLIER_BPS || p.multiplier_bps > MAX_MULTIPLIER_BPS) {
+        return Err(format!(
+            "Lock age multipliers must be between {} and {} basis points",
+            BASE_MULTIPLIER_BPS, MAX_MULTIPLIER_BPS
+        ));
+    }
+    for pair in curve.windows(2) {
+        if pair[1].age_seconds <= pair[0].age_seconds {
+            return Err("Lock age curve ages must be strictly increasing".to_string());
+        }
+        if pair[1].multiplier_bps < pair[0].multiplier_bps {
+            return Err("Lock age multipliers must not decrease with age".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Settings for a token, falling back to defaults
+pub fn voting_power_config(token_canister_id: Principal) -> VotingPowerConfig {
+    VOTING_POWER_CONFIGS.with(|configs| {
+        configs.borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|c| c.0)
+            .unwrap_or_default()
+    })
+}
+
+/// Orbit Station linked to a token, as recorded by the backend
+pub(crate) async fn linked_station(token_canister_id: Principal) -> Result<Principal, String> {
+    let backend_canister = Principal::from_text(BACKEND_CANISTER_ID)
+        .map_err(|e| format!("Invalid backend ID: {}", e))?;
+
+    let station_result: Result<(Option<Principal>,), _> = ic_cdk::call(
+        backend_canister,
+        "get_orbit_station_for_token",
+        (token_canister_id,)
+    ).await;
+
+    station_result
+        .map_err(|e| format!("Failed to query backend: {:?}", e))?
+        .0
+        .ok_or(format!("No Orbit Station linked to token {}", token_canister_id))
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Replace a DAO's voting power settings
+/// ONLY the DAO's Orbit Station can call this (via a CallExternalCanister request)
+#[update]
+pub async fn set_voting_power_config(
+    token_canister_id: Principal,
+    config: VotingPowerConfig,
+) -> Result<(), String> {
+    let caller = ic_cdk::caller();
+
+    config.validate()?;
+
+    let station_id = linked_station(token_canister_id).await?;
+    if caller != station_id {
+        return Err("Only the DAO's Orbit Station can change voting power settings".to_string());
+    }
+
+    VOTING_POWER_CONFIGS.with(|configs| {
+        configs.borrow_mut().insert(StorablePrincipal(token_canister_id), StorableCandid(config));
+    });
+
+    Ok(())
+}
+
+#[query]
+pub fn get_voting_power_config(token_canister_id: Principal) -> VotingPowerConfig {
+    voting_power_config(token_canister_id)
+}
@@ -0,0 +1,239 @@
+// Background indexer for total Kong Locker voting power
+//
+// Walks every lock canister registered with the Kong Locker factory in bounded
+// batches, one batch per timer tick, and keeps a per-lock-canister, per-token VP
+// table in stable memory. Token totals are adjusted incrementally as entries
+// change, so reading a total never needs an inter-canister call. Lock canisters
+// missing from a completed pass's list are dropped from the table.
+
+use super::lock_age::record_positions;
+use super::lock_canisters::{fetch_all_lock_canisters, sync_lock_canister_index};
+use super::voting::{fetch_lp_balances, voting_power_by_token};
+use crate::storage::state::{
+    TokenVotingPowerTotal, VotingPowerIndexStatus, INDEXER_PASS_QUEUE, LOCK_CANISTER_VP,
+    TOKEN_VP_TOTALS, VP_INDEX_STATUS,
+};
+use crate::types::{PrincipalPair, StorableCandid, StorablePrincipal};
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::query;
+use ic_cdk_timers::set_timer_interval;
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+// Lock canisters queried (concurrently) per tick
+// Keeps each tick well within the instruction and outstanding-call limits
+const BATCH_SIZE: usize = 25;
+const TICK_INTERVAL_SECS: u64 = 60;
+
+thread_local! {
+    // Guards against overlapping ticks while a batch is still awaiting KongSwap
+    static TICK_IN_PROGRESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Clears TICK_IN_PROGRESS when dropped, so a tick that traps doesn't stop the indexer
+struct TickGuard;
+
+impl Drop for TickGuard {
+    fn drop(&mut self) {
+        TICK_IN_PROGRESS.with(|t| t.set(false));
+    }
+}
+
+/// Start the indexer timer (called from init and post_upgrade)
+pub fn init_voting_power_indexer() {
+    set_timer_interval(Duration::from_secs(TICK_INTERVAL_SECS), || {
+        ic_cdk::spawn(async {
+            if TICK_IN_PROGRESS.with(|t| t.replace(true)) {
+                return;
+            }
+            let _tick = TickGuard;
+            if let Err(e) = index_next_batch().await {
+                ic_cdk::println!("VP INDEXER: {}", e);
+            }
+        });
+    });
+    ic_cdk::println!("VP indexer started - {} lock canisters every {}s", BATCH_SIZE, TICK_INTERVAL_SECS);
+}
+
+/// Index the next batch of lock canisters, starting a new pass if needed
+async fn index_next_batch() -> Result<(), String> {
+    let mut status = VP_INDEX_STATUS.with(|s| s.borrow().get().0.clone());
+
+    // The queue lives on the heap, so an upgrade mid-pass restarts the pass
+    let queue_empty = INDEXER_PASS_QUEUE.with(|q| q.borrow().is_empty());
+    if status.pass_started_at.is_none() || queue_empty {
+        start_pass(&mut status).await?;
+    }
+
+    let batch: Vec<Principal> = INDEXER_PASS_QUEUE.with(|q| {
+        q.borrow()
+            .iter()
+            .skip(status.cursor as usize)
+            .take(BATCH_SIZE)
+            .map(|(_owner, lock_canister)| *lock_canister)
+            .collect()
+    });
+
+    let results = futures::future::join_all(
+        batch.iter().map(|lock_canister| fetch_lp_balances(*lock_canister))
+    ).await;
+
+    let now = time();
+    for (lock_canister, result) in batch.iter().zip(results) {
+        match result {
+            Ok(balances) => {
+                record_positions(*lock_canister, &balances, now);
+                update_lock_canister(*lock_canister, voting_power_by_token(*lock_canister, &balances), now);
+                status.pass_indexed += 1;
+            }
+            Err(e) => {
+                // Keep the previous values for this lock canister rather than zeroing them
+                ic_cdk::println!("VP INDEXER: Failed to index lock canister {}: {}", lock_canister, e);
+                status.pass_failed += 1;
+            }
+        }
+    }
+    status.cursor += batch.len() as u64;
+
+    if status.cursor >= status.pass_lock_canisters {
+        status.last_pass_completed_at = Some(now);
+        status.last_pass_lock_canisters = status.pass_lock_canisters;
+        status.last_pass_indexed = status.pass_indexed;
+        status.last_pass_failed = status.pass_failed;
+        status.pass_started_at = None;
+        prune_unlisted_lock_canisters(now);
+        INDEXER_PASS_QUEUE.with(|q| q.borrow_mut().clear());
+
+        ic_cdk::println!(
+            "VP INDEXER: Pass complete - {}/{} lock canisters indexed, {} failed",
+            status.last_pass_indexed, status.last_pass_lock_canisters, status.last_pass_failed
+        );
+    }
+
+    save_status(status);
+    Ok(())
+}
+
+/// Fetch the current lock canister list from the factory and reset pass counters
+async fn start_pass(status: &mut VotingPowerIndexStatus) -> Result<(), String> {
+    let lock_canisters = fetch_all_lock_canisters().await?;
+
+    // Keep the user → lock canister index current while we have the full list
+    sync_lock_canister_index(&lock_canisters);
+
+    status.pass_started_at = Some(time());
+    status.cursor = 0;
+    status.pass_lock_canisters = lock_canisters.len() as u64;
+    status.pass_indexed = 0;
+    status.pass_failed = 0;
+
+    INDEXER_PASS_QUEUE.with(|q| *q.borrow_mut() = lock_canisters);
+    Ok(())
+}
+
+/// Drop the VP of lock canisters the factory no longer lists, at the end of a pass
+fn prune_unlisted_lock_canisters(now: u64) {
+    let listed: BTreeSet<Principal> = INDEXER_PASS_QUEUE.with(|q| {
+        q.borrow().iter().map(|(_owner, lock_canister)| *lock_canister).collect()
+    });
+    let unlisted: BTreeSet<Principal> = LOCK_CANISTER_VP.with(|vp| {
+        vp.borrow()
+            .iter()
+            .map(|(key, _)| key.0)
+            .filter(|lock_canister| !listed.contains(lock_canister))
+            .collect()
+    });
+
+    for lock_canister in unlisted {
+        update_lock_canister(lock_canister, BTreeMap::new(), now);
+    }
+}
+
+/// Replace a lock canister's per-token VP entries and adjust token totals by the difference
+fn update_lock_canister(lock_canister: Principal, new_vp: BTreeMap<Principal, u64>, now: u64) {
+    // Current entries for this lock canister (keys share the lock canister prefix)
+    let old_vp: BTreeMap<Principal, u64> = LOCK_CANISTER_VP.with(|vp| {
+        vp.borrow()
+            .range(PrincipalPair(lock_canister, Principal::management_canister())..)
+            .take_while(|(key, _)| key.0 == lock_canister)
+            .map(|(key, amount)| (key.1, amount))
+            .collect()
+    });
+
+    let tokens: BTreeSet<Principal> = old_vp.keys().chain(new_vp.keys()).copied().collect();
+    for token in tokens {
+        let old = old_vp.get(&token).copied().unwrap_or(0);
+        let new = new_vp.get(&token).copied().unwrap_or(0);
+        if old == new {
+            continue;
+        }
+
+        LOCK_CANISTER_VP.with(|vp| {
+            let mut vp = vp.borrow_mut();
+            if new == 0 {
+                vp.remove(&PrincipalPair(lock_canister, token));
+            } else {
+                vp.insert(PrincipalPair(lock_canister, token), new);
+            }
+        });
+
+        TOKEN_VP_TOTALS.with(|totals| {
+            let mut totals = totals.borrow_mut();
+            let key = StorablePrincipal(token);
+            let mut total = totals.get(&key).map(|t| t.0).unwrap_or_default();
+
+            total.total_vp = total.total_vp.saturating_sub(old).saturating_add(new);
+            if old == 0 {
+                total.lock_canister_count += 1;
+            } else if new == 0 {
+                total.lock_canister_count = total.lock_canister_count.saturating_sub(1);
+            }
+            total.updated_at = now;
+
+            totals.insert(key, StorableCandid(total));
+        });
+    }
+}
+
+fn save_status(status: VotingPowerIndexStatus) {
+    VP_INDEX_STATUS.with(|s| {
+        s.borrow_mut()
+            .set(StorableCandid(status))
+            .expect("Failed to save VP index status");
+    });
+}
+
+// ============================================================================
+// QUERY METHODS
+// ============================================================================
+
+/// Indexer progress, freshness and coverage
+#[query]
+pub fn get_voting_power_index_status() -> VotingPowerIndexStatus {
+    VP_INDEX_STATUS.with(|s| s.borrow().get().0.clone())
+}
+
+/// Indexed total voting power for a token
+#[query]
+pub fn get_indexed_total_voting_power(token_canister_id: Principal) -> TokenVotingPowerTotal {
+    TOKEN_VP_TOTALS.with(|totals| {
+        totals.borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|t| t.0)
+            .unwrap_or_default()
+    })
+}
+
+/// Indexed voting power of every lock canister holding a position in a token
+#[query]
+pub fn list_indexed_voting_powers(token_canister_id: Principal) -> Vec<(Principal, u64)> {
+    LOCK_CANISTER_VP.with(|vp| {
+        vp.borrow()
+            .iter()
+            .filter(|(key, _)| key.1 == token_canister_id)
+            .map(|(key, amount)| (key.0, amount))
+            .collect()
+    })
+}
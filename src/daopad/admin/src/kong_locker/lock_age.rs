@@ -0,0 +1,150 @@
+// Lock age tracking for Kong Locker LP positions
+//
+// KongSwap only reports a lock canister's current LP balances, so the admin
+// canister keeps each position's history itself: whenever a position first
+// appears or its LP balance grows, the new liquidity is recorded as a tranche
+// stamped with the time it was observed. Locks are permanent, so tranches only
+// shrink when KongSwap reports a smaller balance (newest liquidity goes first).
+
+use super::config::{LockAgePoint, BASE_MULTIPLIER_BPS};
+use super::voting::{amount_to_fixed, UserBalancesReply};
+use crate::storage::state::LOCK_POSITION_TRANCHES;
+use crate::types::{StorableCandid, StringPrincipalPair};
+use candid::{CandidType, Deserialize, Principal};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Liquidity added to a position at one observation
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LockTranche {
+    pub first_seen_at: u64,
+    pub lp_amount: u128, // LP tokens, fixed-point (AMOUNT_SCALE)
+}
+
+/// Record the LP balances KongSwap currently reports for a lock canister
+/// Only positions whose balance changed are written
+pub(crate) fn record_positions(lock_canister: Principal, balances: &[UserBalancesReply], now: u64) {
+    for balance in balances {
+        let UserBalancesReply::LP(lp_reply) = balance;
+        let observed = amount_to_fixed(lp_reply.balance);
+        let key = StringPrincipalPair(lp_reply.symbol.clone(), lock_canister);
+
+        let mut tranches = position_tranches(lock_canister, &lp_reply.symbol);
+        let recorded: u128 = tranches.iter().map(|t| t.lp_amount).sum();
+
+        if observed > recorded {
+            tranches.push(LockTranche { first_seen_at: now, lp_amount: observed - recorded });
+        } else if observed < recorded {
+            let mut excess = recorded - observed;
+            while excess > 0 {
+                let Some(newest) = tranches.last_mut() else { break };
+                let taken = newest.lp_amount.min(excess);
+                newest.lp_amount -= taken;
+                excess -= taken;
+                if newest.lp_amount == 0 {
+                    tranches.pop();
+                }
+            }
+        } else {
+            continue;
+        }
+
+        LOCK_POSITION_TRANCHES.with(|positions| {
+            let mut positions = positions.borrow_mut();
+            if tranches.is_empty() {
+                positions.remove(&key);
+            } else {
+                positions.insert(key, StorableCandid(tranches));
+            }
+        });
+    }
+}
+
+/// Recorded tranches of one position, oldest first
+pub(crate) fn position_tranches(lock_canister: Principal, lp_symbol: &str) -> Vec<LockTranche> {
+    LOCK_POSITION_TRANCHES.with(|positions| {
+        positions.borrow()
+            .get(&StringPrincipalPair(lp_symbol.to_string(), lock_canister))
+            .map(|t| t.0)
+            .unwrap_or_default()
+    })
+}
+
+/// Multiplier for a given lock age, interpolated along the curve
+fn multiplier_bps_at(curve: &[LockAgePoint], age_seconds: u64) -> u32 {
+    let Some(first) = curve.first() else {
+        return BASE_MULTIPLIER_BPS;
+    };
+    if age_seconds <= first.age_seconds {
+        return first.multiplier_bps;
+    }
+
+    for pair in curve.windows(2) {
+        let (from, to) = (&pair[0], &pair[1]);
+        if age_seconds < to.age_seconds {
+            let span = (to.age_seconds - from.age_seconds) as u128;
+            let elapsed = (age_seconds - from.age_seconds) as u128;
+            let rise = (to.multiplier_bps - from.multiplier_bps) as u128;
+            return from.multiplier_bps + (rise * elapsed / span) as u32;
+        }
+    }
+
+    curve.last().map(|p| p.multiplier_bps).unwrap_or(BASE_MULTIPLIER_BPS)
+}
+
+/// Apply a lock age curve to a position's value
+/// The value is split across tranches by LP amount and each share is weighted by
+/// its own age. Positions with no recorded history count as brand new.
+pub(crate) fn age_weighted_value(
+    base_value: u64,
+    tranches: &[LockTranche],
+    curve: &[LockAgePoint],
+    now: u64,
+) -> u64 {
+    let total: u128 = tranches.iter().map(|t| t.lp_amount).sum();
+    if total == 0 {
+        let weighted = base_value as u128 * multiplier_bps_at(curve, 0) as u128
+            / BASE_MULTIPLIER_BPS as u128;
+        return u64::try_from(weighted).unwrap_or(u64::MAX);
+    }
+
+    let weighted: u128 = tranches.iter()
+        .map(|tranche| {
+            let age_seconds = now.saturating_sub(tranche.first_seen_at) / NANOS_PER_SEC;
+            let share = base_value as u128 * tranche.lp_amount / total;
+            share * multiplier_bps_at(curve, age_seconds) as u128 / BASE_MULTIPLIER_BPS as u128
+        })
+        .sum();
+
+    u64::try_from(weighted).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(age_seconds: u64, multiplier_bps: u32) -> LockAgePoint {
+        LockAgePoint { age_seconds, multiplier_bps }
+    }
+
+    #[test]
+    fn empty_curve_is_the_base_multiplier() {
+        assert_eq!(multiplier_bps_at(&[], 1_000), BASE_MULTIPLIER_BPS);
+    }
+
+    #[test]
+    fn curve_is_flat_outside_its_points() {
+        let curve = [point(100, 10_000), point(200, 20_000)];
+        assert_eq!(multiplier_bps_at(&curve, 0), 10_000);
+        assert_eq!(multiplier_bps_at(&curve, 200), 20_000);
+        assert_eq!(multiplier_bps_at(&curve, 10_000), 20_000);
+    }
+
+    #[test]
+    fn curve_interpolates_between_points() {
+        let curve = [point(0, 10_000), point(100, 20_000), point(200, 30_000)];
+        assert_eq!(multiplier_bps_at(&curve, 50), 15_000);
+        assert_eq!(multiplier_bps_at(&curve, 100), 20_000);
+        assert_eq!(multiplier_bps_at(&curve, 175), 27_500);
+    }
+}
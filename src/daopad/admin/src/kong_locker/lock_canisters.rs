@@ -0,0 +1,120 @@
+// User → lock canister index
+//
+// Mirrors the Kong Locker factory's (user, lock_canister) registry so votes don't
+// download and scan `get_all_lock_canisters` every time. The index is refreshed
+// at the start of every indexer pass, and on a lookup miss (rate limited per user).
+
+use super::voting::KONG_LOCKER_FACTORY_ID;
+use crate::storage::state::USER_LOCK_CANISTERS;
+use crate::types::StorablePrincipal;
+use candid::Principal;
+use ic_cdk::api::time;
+use ic_cdk::{call, query, update};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+// Minimum time between factory refreshes triggered by lookup misses for one user
+// A user without a lock canister would otherwise trigger a full download on every
+// call; keyed per user so one user's misses can't hold up another's lookup
+const MISS_REFRESH_COOLDOWN_NANOS: u64 = 60_000_000_000; // 1 minute
+
+thread_local! {
+    // user → last miss refresh, only for refreshes still in their cooldown
+    static LAST_MISS_REFRESH: RefCell<BTreeMap<Principal, u64>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Fetch the full (user, lock_canister) list from the Kong Locker factory
+pub(crate) async fn fetch_all_lock_canisters() -> Result<Vec<(Principal, Principal)>, String> {
+    let kong_locker_factory = Principal::from_text(KONG_LOCKER_FACTORY_ID)
+        .map_err(|e| format!("Invalid Kong Locker factory ID: {}", e))?;
+
+    let all_lock_canisters: Result<(Vec<(Principal, Principal)>,), _> =
+        call(kong_locker_factory, "get_all_lock_canisters", ()).await;
+
+    Ok(all_lock_canisters
+        .map_err(|e| format!("Failed to query Kong Locker factory: {:?}", e))?
+        .0)
+}
+
+/// Bring the index in line with the factory's registry
+/// Only changed entries are written; users the factory no longer lists are removed
+pub(crate) fn sync_lock_canister_index(lock_canisters: &[(Principal, Principal)]) {
+    USER_LOCK_CANISTERS.with(|index| {
+        let mut index = index.borrow_mut();
+
+        let stale: Vec<StorablePrincipal> = {
+            let current: std::collections::BTreeSet<Principal> =
+                lock_canisters.iter().map(|(user, _)| *user).collect();
+            index.iter()
+                .filter(|(user, _)| !current.contains(&user.0))
+                .map(|(user, _)| user)
+                .collect()
+        };
+        for user in stale {
+            index.remove(&user);
+        }
+
+        for (user, lock_canister) in lock_canisters {
+            let key = StorablePrincipal(*user);
+            if index.get(&key).map(|c| c.0) != Some(*lock_canister) {
+                index.insert(key, StorablePrincipal(*lock_canister));
+            }
+        }
+    });
+}
+
+fn indexed_lock_canister(user: Principal) -> Option<Principal> {
+    USER_LOCK_CANISTERS.with(|index| index.borrow().get(&StorablePrincipal(user)).map(|c| c.0))
+}
+
+/// Find a user's lock canister, refreshing the index from the factory on a miss
+pub async fn find_lock_canister(user: Principal) -> Result<Principal, String> {
+    if let Some(lock_canister) = indexed_lock_canister(user) {
+        return Ok(lock_canister);
+    }
+
+    let now = time();
+    let refresh = LAST_MISS_REFRESH.with(|last| {
+        let mut last = last.borrow_mut();
+        last.retain(|_, at| now.saturating_sub(*at) < MISS_REFRESH_COOLDOWN_NANOS);
+        if last.contains_key(&user) {
+            return false;
+        }
+        last.insert(user, now);
+        true
+    });
+    if refresh {
+        let lock_canisters = fetch_all_lock_canisters().await?;
+        sync_lock_canister_index(&lock_canisters);
+    }
+
+    indexed_lock_canister(user)
+        .ok_or("No Kong Locker found for user. Please create one at kong.land".to_string())
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Look up a user's lock canister (update: may refresh the index from the factory)
+#[update]
+pub async fn lookup_lock_canister(user: Principal) -> Result<Principal, String> {
+    find_lock_canister(user).await
+}
+
+/// Indexed lock canister for a user, without refreshing
+#[query]
+pub fn get_indexed_lock_canister(user: Principal) -> Option<Principal> {
+    indexed_lock_canister(user)
+}
+
+/// All indexed (user, lock_canister) pairs
+#[query]
+pub fn list_indexed_lock_canisters() -> Vec<(Principal, Principal)> {
+    USER_LOCK_CANISTERS.with(|index| {
+        index.borrow()
+            .iter()
+            .map(|(user, lock_canister)| (user.0, lock_canister.0))
+            .collect()
+    })
+}
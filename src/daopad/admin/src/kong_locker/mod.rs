@@ -1,3 +1,7 @@
+pub mod config;
+pub mod indexer;
+pub mod lock_age;
+pub mod lock_canisters;
 pub mod voting;
 
 pub use voting::*;
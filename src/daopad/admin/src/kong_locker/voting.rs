@@ -1,13 +1,15 @@
-use candid::{CandidType, Deserialize, Principal};
+use super::config::{voting_power_config, BASE_MULTIPLIER_BPS};
+use super::lock_age::{age_weighted_value, position_tranches, record_positions};
+use crate::price_oracle::{twap_price, PRICE_SCALE};
+use crate::proposals::types::VotingPowerSnapshot;
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::api::time;
 use ic_cdk::call;
+use std::collections::BTreeMap;
 
 // External canister IDs for Kong Locker integration
-const KONG_LOCKER_FACTORY_ID: &str = "eazgb-giaaa-aaaap-qqc2q-cai";
-const KONGSWAP_CANISTER_ID: &str = "2ipq2-uqaaa-aaaar-qailq-cai";
-
-// Maximum lock canisters to process per VP calculation
-// Prevents instruction limit overflow on large user bases
-const MAX_LOCK_CANISTERS: usize = 100;
+pub(crate) const KONG_LOCKER_FACTORY_ID: &str = "eazgb-giaaa-aaaap-qqc2q-cai";
+pub(crate) const KONGSWAP_CANISTER_ID: &str = "2ipq2-uqaaa-aaaar-qailq-cai";
 
 // UserBalancesReply type (copied from backend for Kong Locker queries)
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -17,38 +19,74 @@ pub enum UserBalancesReply {
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct LPBalanceReply {
+    pub symbol: String,
+    pub balance: f64,
     pub address_0: String,
+    pub amount_0: f64,
     pub address_1: String,
-    pub usd_balance: f64,
+    pub amount_1: f64,
+}
+
+// Fixed-point scale for token amounts (KongSwap reports amounts as f64 whole tokens)
+const AMOUNT_SCALE: u128 = 100_000_000; // 1e8
+
+/// Voting power one LP position contributes to a governed token
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PositionVotingPower {
+    pub lp_symbol: String,
+    pub base_voting_power: u64,       // USD value in cents
+    pub voting_power: u64,            // after the DAO's lock age multiplier
+    pub multiplier_bps: u32,          // effective multiplier (10_000 = 1x)
+    pub first_locked_at: Option<u64>, // when the admin canister first saw this position
+}
+
+/// Per-position voting power of one lock canister for one token
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VotingPowerBreakdown {
+    pub lock_canister: Principal,
+    pub base_voting_power: u64,
+    pub voting_power: u64,
+    pub positions: Vec<PositionVotingPower>,
 }
 
 // Note: Minimum VP checks are now handled directly in the proposal system
 
-/// Query Kong Locker to find user's lock canister, then get voting power
-/// No registration needed - queries Kong Locker factory directly
+/// Look up user's lock canister in the index, then get voting power
+/// No registration needed - the index mirrors the Kong Locker factory
 pub async fn get_user_voting_power_for_token(
     caller: Principal,
     token_canister_id: Principal,
 ) -> Result<u64, String> {
-    // Step 1: Query Kong Locker factory to find user's lock canister
-    let kong_locker_factory = Principal::from_text(KONG_LOCKER_FACTORY_ID)
-        .map_err(|e| format!("Invalid Kong Locker factory ID: {}", e))?;
+    Ok(get_user_voting_power_breakdown(caller, token_canister_id).await?.voting_power)
+}
 
-    let all_lock_canisters: Result<(Vec<(Principal, Principal)>,), _> =
-        call(kong_locker_factory, "get_all_lock_canisters", ()).await;
+/// Voting power for a token with the per-position lock age breakdown
+pub async fn get_user_voting_power_breakdown(
+    caller: Principal,
+    token_canister_id: Principal,
+) -> Result<VotingPowerBreakdown, String> {
+    // Step 1: Find the user's lock canister
+    let kong_locker_principal = super::lock_canisters::find_lock_canister(caller).await?;
 
-    let lock_canisters = all_lock_canisters
-        .map_err(|e| format!("Failed to query Kong Locker factory: {:?}", e))?
-        .0;
+    // Step 2: Query KongSwap with the user's lock canister ID
+    let user_balances = fetch_lp_balances(kong_locker_principal).await?;
+    record_positions(kong_locker_principal, &user_balances, time());
 
-    // Find the user's lock canister
-    let kong_locker_principal = lock_canisters
-        .iter()
-        .find(|(user, _canister)| *user == caller)
-        .map(|(_user, canister)| *canister)
-        .ok_or("No Kong Locker found for user. Please create one at kong.land")?;
+    // Step 3: Calculate voting power for this specific token
+    let positions = position_voting_powers(kong_locker_principal, &user_balances)
+        .remove(&token_canister_id)
+        .unwrap_or_default();
+
+    Ok(VotingPowerBreakdown {
+        lock_canister: kong_locker_principal,
+        base_voting_power: positions.iter().fold(0u64, |sum, p| sum.saturating_add(p.base_voting_power)),
+        voting_power: positions.iter().fold(0u64, |sum, p| sum.saturating_add(p.voting_power)),
+        positions,
+    })
+}
 
-    // Step 2: Query KongSwap with the user's lock canister ID
+/// Fetch the LP positions KongSwap reports for a lock canister
+pub(crate) async fn fetch_lp_balances(lock_canister: Principal) -> Result<Vec<UserBalancesReply>, String> {
     let kongswap_id = Principal::from_text(KONGSWAP_CANISTER_ID)
         .map_err(|e| format!("Invalid KongSwap ID: {}", e))?;
 
@@ -58,176 +96,198 @@ pub async fn get_user_voting_power_for_token(
     > = call(
         kongswap_id,
         "user_balances",
-        (kong_locker_principal.to_string(),),
+        (lock_canister.to_string(),),
     )
     .await;
 
-    let user_balances = user_balances_result
+    user_balances_result
         .map_err(|e| format!("Failed to get LP positions: {:?}", e))?
         .0
-        .map_err(|e| format!("KongSwap returned error: {}", e))?;
+        .map_err(|e| format!("KongSwap returned error: {}", e))
+}
 
-    // Step 3: Calculate voting power for this specific token
-    let token_id_str = token_canister_id.to_string();
-    let total_usd_value: f64 = user_balances
-        .iter()
-        .filter_map(|balance| {
-            let UserBalancesReply::LP(lp_reply) = balance;
-            // Count the FULL position value if this token is in the pair
-            if lp_reply.address_0 == token_id_str || lp_reply.address_1 == token_id_str {
-                // Use total USD balance of the entire LP position
-                Some(lp_reply.usd_balance)
-            } else {
-                None
-            }
+/// Voting power per token for one lock canister's LP positions
+pub(crate) fn voting_power_by_token(
+    lock_canister: Principal,
+    balances: &[UserBalancesReply],
+) -> BTreeMap<Principal, u64> {
+    let mut vp_by_token: BTreeMap<Principal, u64> = position_voting_powers(lock_canister, balances)
+        .into_iter()
+        .map(|(token, positions)| {
+            (token, positions.iter().fold(0u64, |sum, p| sum.saturating_add(p.voting_power)))
         })
-        .sum();
+        .collect();
 
-    // Multiply by 100 to get voting power (preserving 2 decimal places as integer)
-    Ok((total_usd_value * 100.0) as u64)
+    vp_by_token.retain(|_, vp| *vp > 0);
+    vp_by_token
 }
 
-/// Calculate total voting power for a token across ALL Kong Locker users
-/// Uses caching with 1-hour TTL to reduce cycles cost and latency
-pub async fn calculate_total_voting_power_for_token(
-    token_canister_id: Principal
-) -> Result<u64, String> {
-    use crate::storage::state::{TOTAL_VP_CACHE, VotingPowerCache};
-    use crate::types::StorablePrincipal;
-    use ic_cdk::api::time;
-
-    const CACHE_TTL_NANOS: u64 = 3_600_000_000_000; // 1 hour
-
+/// Voting power of each LP position, grouped by governed token
+/// The FULL position value counts towards both tokens of the pair, priced with
+/// each DAO's TWAP window so a short price push can't inflate voting power, then
+/// weighted by the DAO's lock age multiplier (if it has one)
+fn position_voting_powers(
+    lock_canister: Principal,
+    balances: &[UserBalancesReply],
+) -> BTreeMap<Principal, Vec<PositionVotingPower>> {
     let now = time();
-    let token_key = StorablePrincipal(token_canister_id);
+    let mut prices: BTreeMap<(Principal, u64), Option<u128>> = BTreeMap::new();
+    let mut twap = |token: Principal, window: u64| {
+        *prices.entry((token, window)).or_insert_with(|| twap_price(token, window))
+    };
+
+    let mut positions_by_token: BTreeMap<Principal, Vec<PositionVotingPower>> = BTreeMap::new();
+    for balance in balances {
+        let UserBalancesReply::LP(lp_reply) = balance;
+        // Non-IC tokens (e.g. pairs on other chains) have no principal and no DAO
+        let token_0 = Principal::from_text(&lp_reply.address_0).ok();
+        let token_1 = Principal::from_text(&lp_reply.address_1).ok();
+
+        let tranches = position_tranches(lock_canister, &lp_reply.symbol);
+
+        for governed in [token_0, token_1].into_iter().flatten() {
+            let config = voting_power_config(governed);
+            let window = config.twap_window_seconds;
+            let price_0 = token_0.and_then(|t| twap(t, window));
+            let price_1 = token_1.and_then(|t| twap(t, window));
+
+            let base_voting_power = lp_position_value_cents(
+                amount_to_fixed(lp_reply.amount_0), price_0,
+                amount_to_fixed(lp_reply.amount_1), price_1,
+            );
+            let voting_power = match &config.lock_age_multiplier {
+                Some(curve) => age_weighted_value(base_voting_power, &tranches, curve, now),
+                None => base_voting_power,
+            };
+            let multiplier_bps = if base_voting_power == 0 {
+                BASE_MULTIPLIER_BPS
+            } else {
+                (voting_power as u128 * BASE_MULTIPLIER_BPS as u128 / base_voting_power as u128) as u32
+            };
+
+            positions_by_token.entry(governed).or_default().push(PositionVotingPower {
+                lp_symbol: lp_reply.symbol.clone(),
+                base_voting_power,
+                voting_power,
+                multiplier_bps,
+                first_locked_at: tranches.first().map(|t| t.first_seen_at),
+            });
+        }
+    }
 
-    // Check cache first
-    let cached = TOTAL_VP_CACHE.with(|cache| {
-        cache.borrow().get(&token_key).cloned()
-    });
+    positions_by_token
+}
 
-    if let Some(cache_entry) = cached {
-        if now.saturating_sub(cache_entry.timestamp) < CACHE_TTL_NANOS {
-            ic_cdk::println!("Using cached total VP for token {}: {}", token_canister_id, cache_entry.total_vp);
-            return Ok(cache_entry.total_vp);
-        } else {
-            ic_cdk::println!("Cache expired for token {}, recalculating...", token_canister_id);
-        }
+/// Convert a KongSwap f64 amount to fixed-point (AMOUNT_SCALE)
+/// This is the only float operation; everything after it is integer arithmetic
+pub(crate) fn amount_to_fixed(amount: f64) -> u128 {
+    let scaled = (amount * AMOUNT_SCALE as f64).round();
+    if scaled.is_finite() && scaled > 0.0 && scaled <= u128::MAX as f64 {
+        scaled as u128
+    } else {
+        0
     }
+}
 
-    // Cache miss or expired - calculate fresh value
-    ic_cdk::println!("Calculating total VP for token {}...", token_canister_id);
+/// USD value of an LP position in cents (= voting power)
+/// With both TWAPs the position is priced as 2 * sqrt(amount_0 * amount_1 * price_0 * price_1):
+/// swaps move the amounts but not their product, so pushing the pool can't
+/// inflate it. A constant-product position holds equal value on both sides,
+/// so a side without a TWAP is valued at the priced side's value
+fn lp_position_value_cents(
+    amount_0: u128, price_0: Option<u128>,
+    amount_1: u128, price_1: Option<u128>,
+) -> u64 {
+    // amount * price is scaled by AMOUNT_SCALE * PRICE_SCALE; cents need a factor of 100
+    let divisor = AMOUNT_SCALE * PRICE_SCALE / 100;
+    let side_value = |amount: u128, price: u128| amount.saturating_mul(price) / divisor;
+
+    let value = match (price_0, price_1) {
+        (Some(p0), Some(p1)) => {
+            let product = Nat::from(amount_0) * Nat::from(amount_1) * Nat::from(p0) * Nat::from(p1);
+            let fair = Nat(product.0.sqrt()) * Nat::from(2u8) / Nat::from(divisor);
+            u128::try_from(fair.0).unwrap_or(u128::MAX)
+        }
+        (Some(p0), None) => side_value(amount_0, p0).saturating_mul(2),
+        (None, Some(p1)) => side_value(amount_1, p1).saturating_mul(2),
+        (None, None) => 0,
+    };
 
-    // Step 1: Query Kong Locker factory for ALL lock canisters
-    let kong_locker_factory = Principal::from_text(KONG_LOCKER_FACTORY_ID)
-        .map_err(|e| format!("Invalid Kong Locker factory ID: {}", e))?;
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
 
-    let all_lock_canisters: Result<(Vec<(Principal, Principal)>,), _> =
-        call(kong_locker_factory, "get_all_lock_canisters", ()).await;
+/// Total voting power for a token, read from the Kong Locker index
+/// The index is maintained in the background (see `kong_locker::indexer`),
+/// so this makes no inter-canister calls
+pub fn calculate_total_voting_power_for_token(
+    token_canister_id: Principal
+) -> Result<VotingPowerSnapshot, String> {
+    use crate::storage::state::{TOKEN_VP_TOTALS, VP_INDEX_STATUS};
+    use crate::types::StorablePrincipal;
 
-    let lock_canisters = all_lock_canisters
-        .map_err(|e| format!("Failed to query Kong Locker factory: {:?}", e))?
-        .0;
+    let status = VP_INDEX_STATUS.with(|s| s.borrow().get().0.clone());
 
-    let total_lock_canisters = lock_canisters.len();
-    ic_cdk::println!("Found {} lock canisters", total_lock_canisters);
+    // Until one full pass completes, totals only cover part of the lock canisters
+    let indexed_at = status.last_pass_completed_at.ok_or(
+        "Voting power index has not completed its first pass yet".to_string()
+    )?;
 
-    // Step 2: Sum voting power across all lock canisters
-    let mut total_power = 0u64;
-    let mut failed_calls = 0usize;
-    let kongswap_id = Principal::from_text(KONGSWAP_CANISTER_ID)
-        .map_err(|e| format!("Invalid KongSwap ID: {}", e))?;
+    let total_voting_power = TOKEN_VP_TOTALS.with(|totals| {
+        totals.borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|t| t.0.total_vp)
+            .unwrap_or(0)
+    });
 
-    // Limit iteration to prevent instruction overflow
-    for (_user, lock_canister) in lock_canisters.iter().take(MAX_LOCK_CANISTERS) {
-        // Query KongSwap for this lock canister's LP positions
-        let user_balances_result: Result<
-            (Result<Vec<UserBalancesReply>, String>,),
-            (ic_cdk::api::call::RejectionCode, String),
-        > = call(
-            kongswap_id,
-            "user_balances",
-            (lock_canister.to_string(),),
-        )
-        .await;
-
-        // Handle failed calls with logging
-        let user_balances = match user_balances_result {
-            Ok((Ok(balances),)) => balances,
-            Ok((Err(e),)) => {
-                failed_calls += 1;
-                ic_cdk::println!("Warning: KongSwap returned error for lock canister {}: {}", lock_canister, e);
-                continue;
-            }
-            Err((code, msg)) => {
-                failed_calls += 1;
-                ic_cdk::println!("Warning: Failed to query KongSwap for lock canister {}: {:?} - {}", lock_canister, code, msg);
-                continue;
-            }
-        };
-
-        // Calculate voting power for this specific token
-        let token_id_str = token_canister_id.to_string();
-        let user_vp: f64 = user_balances
-            .iter()
-            .filter_map(|balance| {
-                let UserBalancesReply::LP(lp_reply) = balance;
-                if lp_reply.address_0 == token_id_str || lp_reply.address_1 == token_id_str {
-                    Some(lp_reply.usd_balance)
-                } else {
-                    None
-                }
-            })
-            .sum();
-
-        // Validate VP value before conversion to prevent overflow/NaN issues
-        let vp_to_add = (user_vp * 100.0).round();
-        if vp_to_add.is_finite() && vp_to_add >= 0.0 && vp_to_add <= u64::MAX as f64 {
-            total_power = total_power.saturating_add(vp_to_add as u64);
-        } else {
-            ic_cdk::println!(
-                "Warning: Invalid VP value {} for lock canister {}. Skipping.",
-                vp_to_add, lock_canister
-            );
-        }
-    }
+    Ok(VotingPowerSnapshot {
+        total_voting_power,
+        indexed_at,
+        lock_canisters_total: status.last_pass_lock_canisters,
+        lock_canisters_indexed: status.last_pass_indexed,
+        lock_canisters_failed: status.last_pass_failed,
+    })
+}
 
-    // Warn if we hit the canister limit
-    if total_lock_canisters > MAX_LOCK_CANISTERS {
-        ic_cdk::println!(
-            "WARNING: Only calculated VP for first {} of {} lock canisters. Total VP may be underestimated.",
-            MAX_LOCK_CANISTERS, total_lock_canisters
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_oracle::PRICE_SCALE;
+
+    #[test]
+    fn lp_value_adds_both_priced_sides() {
+        // 2 tokens at $1.50 and 3 tokens at $1.00
+        let value = lp_position_value_cents(
+            2 * AMOUNT_SCALE, Some(PRICE_SCALE * 3 / 2),
+            3 * AMOUNT_SCALE, Some(PRICE_SCALE),
         );
+        assert_eq!(value, 600);
     }
 
-    // Check if too many calls failed (>20% failure rate)
-    if total_lock_canisters > 0 {
-        let failure_rate = (failed_calls as f64 / total_lock_canisters as f64) * 100.0;
-        if failure_rate > 20.0 {
-            let error_msg = format!(
-                "Too many failed calls: {}/{} ({:.1}% failure rate). Total VP calculation may be inaccurate.",
-                failed_calls, total_lock_canisters, failure_rate
-            );
-            ic_cdk::println!("ERROR: {}", error_msg);
-            return Err(error_msg);
-        } else if failed_calls > 0 {
-            ic_cdk::println!(
-                "Warning: {}/{} calls failed ({:.1}% failure rate), but within acceptable threshold",
-                failed_calls, total_lock_canisters, failure_rate
-            );
-        }
+    #[test]
+    fn lp_value_ignores_a_pushed_pool() {
+        // A swap moves the position to 1 and 6 tokens (same product); spot
+        // amounts would be worth $7.50, but the TWAPs haven't moved
+        let value = lp_position_value_cents(
+            AMOUNT_SCALE, Some(PRICE_SCALE * 3 / 2),
+            6 * AMOUNT_SCALE, Some(PRICE_SCALE),
+        );
+        assert_eq!(value, 600);
     }
 
-    ic_cdk::println!("Calculated total VP: {} (from {} lock canisters, {} failures)",
-        total_power, total_lock_canisters, failed_calls);
+    #[test]
+    fn lp_value_doubles_the_priced_side() {
+        assert_eq!(lp_position_value_cents(2 * AMOUNT_SCALE, Some(PRICE_SCALE), 5, None), 400);
+        assert_eq!(lp_position_value_cents(5, None, 2 * AMOUNT_SCALE, Some(PRICE_SCALE)), 400);
+    }
 
-    // Cache the result
-    TOTAL_VP_CACHE.with(|cache| {
-        cache.borrow_mut().insert(token_key, VotingPowerCache {
-            total_vp: total_power,
-            timestamp: now,
-        });
-    });
+    #[test]
+    fn lp_value_without_prices_is_zero() {
+        assert_eq!(lp_position_value_cents(AMOUNT_SCALE, None, AMOUNT_SCALE, None), 0);
+    }
 
-    Ok(total_power)
+    #[test]
+    fn lp_value_saturates() {
+        let value = lp_position_value_cents(u128::MAX, Some(PRICE_SCALE), u128::MAX, Some(PRICE_SCALE));
+        assert_eq!(value, u64::MAX);
+    }
 }
@@ -7,18 +7,43 @@ mod kong_locker;
 mod storage;
 mod types;
 mod equity;
+mod price_oracle;
 
-use candid::Principal;
-use ic_cdk::init;
+use candid::{Nat, Principal};
+use ic_cdk::{init, post_upgrade};
 use proposals::types::{
     ProposalId, UnifiedProposal, VoteChoice, ProposalError,
-    EquityTransferProposal, PaymentDestination
+    EquityTransferProposal, PaymentDestination, EquityEscrow, EquityIssuanceProposal,
+    PayloadHashes, SecurityImpact, TransferDetails, TransferDestination,
 };
+use storage::state::{TokenVotingPowerTotal, VotingPowerIndexStatus};
+use kong_locker::config::VotingPowerConfig;
+use price_oracle::PriceSample;
+use equity::settings::EquityStationSettings;
+use equity::vesting::{VestingTerms, VestingSchedule};
+use equity::history::CapTableSnapshot;
+use equity::dividends::DividendProposal;
+use equity::conversion::{EquityConversionProposal, StationConversion};
+use equity::market::SellOrderListing;
+use api::http::{HttpRequest, HttpResponse};
 
 #[init]
 fn init() {
     ic_cdk::println!("🔐 Admin canister initialized: {:?}", ic_cdk::id());
     ic_cdk::println!("📜 This canister handles governance and approval of Orbit requests");
+    kong_locker::indexer::init_voting_power_indexer();
+    price_oracle::init_price_oracle();
+    equity::escrow::init_escrow_refunds();
+}
+
+// Timers don't survive upgrades - restart the indexer, oracle and escrow refunds
+#[post_upgrade]
+fn post_upgrade() {
+    equity::migration::migrate_equity_to_basis_points();
+    equity::history::seed_cap_table_history();
+    kong_locker::indexer::init_voting_power_indexer();
+    price_oracle::init_price_oracle();
+    equity::escrow::init_escrow_refunds();
 }
 
 // ============================================================================
@@ -27,6 +52,7 @@ fn init() {
 
 // Ensure proposal exists (auto-creates if needed) - exported from unified.rs
 pub use proposals::unified::ensure_proposal_for_request;
+pub use proposals::unified::{ensure_hashed_proposal, ensure_simulated_proposal, ensure_transfer_proposal};
 
 // vote_on_proposal is defined in proposals::unified and automatically exported via #[update]
 pub use proposals::unified::vote_on_proposal;
@@ -51,6 +77,7 @@ pub use equity::{
     initialize_equity_station,
     create_equity_transfer_proposal,
     vote_on_equity_transfer,
+    cancel_equity_transfer,
     execute_equity_transfer,
     get_user_equity,
     get_equity_holders,
@@ -58,6 +85,28 @@ pub use equity::{
     get_equity_transfer_proposal,
     is_equity_station,
 };
+pub use equity::rofr::exercise_right_of_first_refusal;
+pub use equity::escrow::{deposit_equity_escrow, refund_equity_escrow, get_equity_escrow};
+pub use equity::settings::{set_equity_station_settings, get_equity_station_settings};
+pub use equity::issuance::{
+    create_equity_issuance_proposal, vote_on_equity_issuance, cancel_equity_issuance,
+    execute_equity_issuance, get_equity_issuance_proposals, get_equity_issuance_proposal,
+};
+pub use equity::vesting::{grant_vesting, revoke_vesting_grant, get_vesting_schedule};
+pub use equity::history::{get_cap_table_at, get_cap_table_history};
+pub use equity::conversion::{
+    create_equity_conversion_proposal, vote_on_equity_conversion, cancel_equity_conversion,
+    execute_equity_conversion, convert_to_equity_station, get_equity_conversion_proposals,
+    get_station_conversions,
+};
+pub use equity::market::{
+    create_sell_order, cancel_sell_order, accept_sell_order, get_open_sell_orders, get_sell_order,
+};
+pub use equity::dividends::{
+    create_dividend_proposal, vote_on_dividend, cancel_dividend, execute_dividend,
+    get_dividend_proposals, get_dividend_proposal,
+};
+pub use api::http::http_request;
 
 // ============================================================================
 // Unified Voting Power API - Routes by station type
@@ -65,6 +114,28 @@ pub use equity::{
 
 pub use api::voting_power::{get_voting_power_display, VotingPowerResult, VotingPowerSource};
 
+// ============================================================================
+// Kong Locker Voting Power Index
+// ============================================================================
+
+pub use kong_locker::indexer::{
+    get_voting_power_index_status,
+    get_indexed_total_voting_power,
+    list_indexed_voting_powers,
+};
+pub use kong_locker::lock_canisters::{
+    lookup_lock_canister,
+    get_indexed_lock_canister,
+    list_indexed_lock_canisters,
+};
+pub use kong_locker::config::{set_voting_power_config, get_voting_power_config};
+
+// ============================================================================
+// Price Oracle (TWAP)
+// ============================================================================
+
+pub use price_oracle::{get_twap_price, get_price_samples};
+
 // ============================================================================
 // Candid Export
 // ============================================================================
@@ -0,0 +1,306 @@
+// KongSwap price oracle
+//
+// Samples the USD price of every token with a KongSwap ckUSDT pool, and of
+// every token with indexed voting power, on a timer, and serves time-weighted
+// average prices. Voting power needs a price first, so which tokens are sampled
+// can't depend on voting power alone.
+// Prices are derived from pool reserves and ICRC-1 decimals with integer
+// arithmetic and stored as fixed-point u128 values scaled by PRICE_SCALE.
+
+use crate::kong_locker::config::MAX_TWAP_WINDOW_SECONDS;
+use crate::kong_locker::voting::KONGSWAP_CANISTER_ID;
+use crate::storage::state::{PRICE_SAMPLES, TOKEN_DECIMALS, TOKEN_VP_TOTALS};
+use crate::types::{PrincipalTimestamp, StorablePrincipal};
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::api::time;
+use ic_cdk::query;
+use ic_cdk_timers::set_timer_interval;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+/// Fixed-point scale for prices: 1 USD per whole token == PRICE_SCALE
+pub const PRICE_SCALE: u128 = 1_000_000_000_000; // 1e12
+
+const CKUSDT_CANISTER_ID: &str = "cngnf-vqaaa-aaaar-qag4q-cai";
+const CKUSDT_DECIMALS: u32 = 6;
+
+const SAMPLE_INTERVAL_SECS: u64 = 5 * 60;
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+// Decimals lookups in flight at once, well under the outstanding-call limit
+const DECIMALS_BATCH_SIZE: usize = 50;
+const MAX_SAMPLES_RETURNED: usize = 1_000;
+
+// Subset of KongSwap's PoolReply used for pricing
+#[derive(CandidType, Deserialize, Clone, Debug)]
+struct PoolReply {
+    address_0: String,
+    balance_0: Nat,
+    address_1: String,
+    balance_1: Nat,
+    is_removed: bool,
+}
+
+/// A single stored price observation
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PriceSample {
+    pub sampled_at: u64,
+    pub price: u128, // USD per whole token * PRICE_SCALE
+}
+
+/// Start the sampling timer (called from init and post_upgrade)
+pub fn init_price_oracle() {
+    set_timer_interval(Duration::from_secs(SAMPLE_INTERVAL_SECS), || {
+        ic_cdk::spawn(async {
+            if let Err(e) = sample_prices().await {
+                ic_cdk::println!("PRICE ORACLE: {}", e);
+            }
+        });
+    });
+    ic_cdk::println!("Price oracle started - sampling every {}s", SAMPLE_INTERVAL_SECS);
+}
+
+/// Record one price sample per tracked token and prune samples past retention
+async fn sample_prices() -> Result<(), String> {
+    let ckusdt = Principal::from_text(CKUSDT_CANISTER_ID)
+        .map_err(|e| format!("Invalid ckUSDT ID: {}", e))?;
+
+    let pools = fetch_pools().await?;
+    let reserves = ckusdt_reserves(&pools, ckusdt);
+
+    // Every token Kong can price, plus any indexed token (kept for pruning
+    // once its pool is gone)
+    let mut tracked: BTreeSet<Principal> = reserves.keys().copied().collect();
+    TOKEN_VP_TOTALS.with(|totals| {
+        tracked.extend(totals.borrow().iter().map(|(token, _)| token.0));
+    });
+    if tracked.is_empty() {
+        return Ok(());
+    }
+
+    cache_missing_decimals(&tracked).await;
+
+    let now = time();
+    let mut sampled = 0usize;
+    for (token, (token_reserve, usdt_reserve)) in reserves {
+        let Some(decimals) = TOKEN_DECIMALS.with(|d| d.borrow().get(&StorablePrincipal(token))) else {
+            continue;
+        };
+        if let Some(price) = price_from_reserves(&token_reserve, &usdt_reserve, decimals) {
+            PRICE_SAMPLES.with(|samples| {
+                samples.borrow_mut().insert(PrincipalTimestamp(token, now), price);
+            });
+            sampled += 1;
+        }
+    }
+
+    prune_samples(&tracked, now);
+    ic_cdk::println!("PRICE ORACLE: Sampled {}/{} tracked tokens", sampled, tracked.len());
+    Ok(())
+}
+
+async fn fetch_pools() -> Result<Vec<PoolReply>, String> {
+    let kongswap_id = Principal::from_text(KONGSWAP_CANISTER_ID)
+        .map_err(|e| format!("Invalid KongSwap ID: {}", e))?;
+
+    let result: Result<(Result<Vec<PoolReply>, String>,), _> =
+        ic_cdk::call(kongswap_id, "pools", (None::<String>,)).await;
+
+    result
+        .map_err(|e| format!("Failed to query KongSwap pools: {:?}", e))?
+        .0
+        .map_err(|e| format!("KongSwap returned error: {}", e))
+}
+
+/// (token reserve, ckUSDT reserve) of every token's ckUSDT pool
+fn ckusdt_reserves(pools: &[PoolReply], ckusdt: Principal) -> BTreeMap<Principal, (Nat, Nat)> {
+    let ckusdt_str = ckusdt.to_text();
+    let mut reserves = BTreeMap::new();
+
+    for pool in pools.iter().filter(|p| !p.is_removed) {
+        let (token_address, token_reserve, usdt_reserve) = if pool.address_1 == ckusdt_str {
+            (&pool.address_0, &pool.balance_0, &pool.balance_1)
+        } else if pool.address_0 == ckusdt_str {
+            (&pool.address_1, &pool.balance_1, &pool.balance_0)
+        } else {
+            continue;
+        };
+
+        if let Ok(token) = Principal::from_text(token_address) {
+            reserves.insert(token, (token_reserve.clone(), usdt_reserve.clone()));
+        }
+    }
+
+    reserves
+}
+
+/// price = (usdt_reserve / 10^6) / (token_reserve / 10^decimals), scaled by PRICE_SCALE
+fn price_from_reserves(token_reserve: &Nat, usdt_reserve: &Nat, decimals: u8) -> Option<u128> {
+    // Empty pool: no price
+    if token_reserve.0.bits() == 0 {
+        return None;
+    }
+
+    let ten = Nat::from(10u8).0;
+    let numerator = usdt_reserve.clone()
+        * Nat(ten.pow(decimals as u32))
+        * Nat::from(PRICE_SCALE);
+    let denominator = token_reserve.clone() * Nat(ten.pow(CKUSDT_DECIMALS));
+
+    u128::try_from((numerator / denominator).0).ok()
+}
+
+/// Fetch ICRC-1 decimals for tracked tokens that aren't cached yet
+async fn cache_missing_decimals(tracked: &BTreeSet<Principal>) {
+    let missing: Vec<Principal> = TOKEN_DECIMALS.with(|d| {
+        let d = d.borrow();
+        tracked.iter()
+            .filter(|token| !d.contains_key(&StorablePrincipal(**token)))
+            .copied()
+            .collect()
+    });
+
+    for batch in missing.chunks(DECIMALS_BATCH_SIZE) {
+        let results = futures::future::join_all(batch.iter().map(|token| async move {
+            let result: Result<(u8,), _> = ic_cdk::call(*token, "icrc1_decimals", ()).await;
+            (*token, result)
+        })).await;
+
+        for (token, result) in results {
+            match result {
+                Ok((decimals,)) => TOKEN_DECIMALS.with(|d| {
+                    d.borrow_mut().insert(StorablePrincipal(token), decimals);
+                }),
+                Err(e) => ic_cdk::println!("PRICE ORACLE: Failed to get decimals for {}: {:?}", token, e),
+            }
+        }
+    }
+}
+
+/// Drop samples older than the longest allowed TWAP window (plus one interval of carry-in)
+fn prune_samples(tracked: &BTreeSet<Principal>, now: u64) {
+    let cutoff = now.saturating_sub((MAX_TWAP_WINDOW_SECONDS + SAMPLE_INTERVAL_SECS) * NANOS_PER_SEC);
+
+    PRICE_SAMPLES.with(|samples| {
+        let mut samples = samples.borrow_mut();
+        for token in tracked {
+            let expired: Vec<PrincipalTimestamp> = samples
+                .range(PrincipalTimestamp(*token, 0)..PrincipalTimestamp(*token, cutoff))
+                .map(|(key, _)| key)
+                .collect();
+            for key in expired {
+                samples.remove(&key);
+            }
+        }
+    });
+}
+
+fn samples_between(token: Principal, from: u64, to: u64) -> Vec<PriceSample> {
+    PRICE_SAMPLES.with(|samples| {
+        samples.borrow()
+            .range(PrincipalTimestamp(token, from)..=PrincipalTimestamp(token, to))
+            .map(|(key, price)| PriceSample { sampled_at: key.1, price })
+            .collect()
+    })
+}
+
+/// Time-weighted average USD price of a token over the last `window_seconds`
+/// Each sample's price holds until the next sample (or now). Returns None when
+/// no sample covers any part of the window.
+pub fn twap_price(token: Principal, window_seconds: u64) -> Option<u128> {
+    if token.to_text() == CKUSDT_CANISTER_ID {
+        return Some(PRICE_SCALE);
+    }
+
+    let now = time();
+    let window_start = now.saturating_sub(window_seconds * NANOS_PER_SEC);
+
+    // Include the sample just before the window: its price holds into the window
+    let lookback = window_start.saturating_sub(SAMPLE_INTERVAL_SECS * NANOS_PER_SEC);
+    let samples = samples_between(token, lookback, now);
+    time_weighted_average(&samples, window_start, now)
+}
+
+/// Average of `samples` (oldest first) over [window_start, now], each price
+/// weighted by how long it held within the window
+fn time_weighted_average(samples: &[PriceSample], window_start: u64, now: u64) -> Option<u128> {
+    let mut weighted_sum: u128 = 0;
+    let mut total_weight: u128 = 0;
+    for (i, sample) in samples.iter().enumerate() {
+        let segment_end = samples.get(i + 1).map(|next| next.sampled_at).unwrap_or(now);
+        let segment_start = sample.sampled_at.max(window_start);
+        if segment_end <= segment_start {
+            continue;
+        }
+        let weight = (segment_end - segment_start) as u128;
+        weighted_sum = weighted_sum.saturating_add(sample.price.saturating_mul(weight));
+        total_weight += weight;
+    }
+
+    weighted_sum.checked_div(total_weight)
+}
+
+// ============================================================================
+// QUERY METHODS
+// ============================================================================
+
+/// Time-weighted average USD price (scaled by PRICE_SCALE) over the given window
+#[query]
+pub fn get_twap_price(token_canister_id: Principal, window_seconds: u64) -> Option<u128> {
+    twap_price(token_canister_id, window_seconds.min(MAX_TWAP_WINDOW_SECONDS))
+}
+
+/// Raw price samples for a token in [from, to] (nanosecond timestamps), oldest
+/// first and at most 1,000; page on by passing the last sampled_at + 1 as `from`
+#[query]
+pub fn get_price_samples(token_canister_id: Principal, from: u64, to: u64) -> Vec<PriceSample> {
+    if from > to {
+        return Vec::new();
+    }
+    PRICE_SAMPLES.with(|samples| {
+        samples.borrow()
+            .range(PrincipalTimestamp(token_canister_id, from)..=PrincipalTimestamp(token_canister_id, to))
+            .take(MAX_SAMPLES_RETURNED)
+            .map(|(key, price)| PriceSample { sampled_at: key.1, price })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(sampled_at: u64, price: u128) -> PriceSample {
+        PriceSample { sampled_at, price }
+    }
+
+    #[test]
+    fn price_from_reserves_scales_by_decimals() {
+        // 100 tokens (8 decimals) against 250 ckUSDT (6 decimals): 2.5 USD each
+        let price = price_from_reserves(&Nat::from(100_0000_0000u64), &Nat::from(250_000_000u64), 8);
+        assert_eq!(price, Some(PRICE_SCALE * 5 / 2));
+    }
+
+    #[test]
+    fn price_from_reserves_empty_pool_has_no_price() {
+        assert_eq!(price_from_reserves(&Nat::from(0u8), &Nat::from(1_000_000u64), 8), None);
+    }
+
+    #[test]
+    fn twap_weights_each_price_by_time_held_in_window() {
+        // 100 holds from before the window until 10, then 200 until now
+        let samples = [sample(0, 100), sample(10, 200)];
+        assert_eq!(time_weighted_average(&samples, 5, 20), Some((5 * 100 + 10 * 200) / 15));
+    }
+
+    #[test]
+    fn twap_of_a_single_sample_is_its_price() {
+        assert_eq!(time_weighted_average(&[sample(0, 42)], 5, 20), Some(42));
+    }
+
+    #[test]
+    fn twap_without_samples_is_none() {
+        assert_eq!(time_weighted_average(&[], 5, 20), None);
+        assert_eq!(time_weighted_average(&[sample(20, 42)], 5, 20), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::kong_locker::config::{VoterCap, VoterCapPolicy};
+use crate::equity::vesting::VestingTerms;
 use candid::{CandidType, Deserialize, Principal};
 use thiserror::Error;
 
@@ -213,6 +215,53 @@ pub struct TransferDetails {
     pub description: String,     // Transfer description for proposal
 }
 
+/// Address book entry a transfer pays to, shown to voters
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TransferDestination {
+    pub address_book_entry_id: String,
+    pub name: String,          // The entry's address owner
+    pub labels: Vec<String>,
+}
+
+/// The backend's policy for transfers to addresses outside the address book
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum UnlistedTransferPolicy {
+    #[default]
+    Allow,
+    RaiseThreshold(u8), // Percent of voting power the proposal needs instead
+    Reject,
+}
+
+/// SHA-256 hashes (hex) of the code and argument a request would run
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PayloadHashes {
+    pub wasm_module_hash: Option<String>,
+    pub wasm_module_size: Option<u64>,
+    pub arg_hash: Option<String>,
+    pub source_commit: Option<String>, // Link to the commit the wasm was built from
+}
+
+/// How one security check's result would change, as simulated by the backend
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CheckChange {
+    pub category: String,
+    pub name: String,
+    pub severity: String,
+    pub status_before: Option<String>, // None when the check only appears after the change
+    pub status_after: Option<String>,  // None when the check no longer applies
+    pub message: String,
+}
+
+/// Security dashboard diff for a permission or policy change
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SecurityImpact {
+    pub score_before: u8,
+    pub score_after: u8,
+    pub overall_status_before: String,
+    pub overall_status_after: String,
+    pub changed_checks: Vec<CheckChange>,
+}
+
 /// Unified proposal type for all Orbit operations
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UnifiedProposal {
@@ -230,6 +279,59 @@ pub struct UnifiedProposal {
     pub status: ProposalStatus,
     // Optional fields for specific operations
     pub transfer_details: Option<TransferDetails>,
+    // Kong Locker index coverage behind total_voting_power (None for equity stations)
+    pub vp_snapshot: Option<VotingPowerSnapshot>,
+    // DAO's per-voter cap when the proposal was created, and the weight it cut
+    pub voter_cap: Option<VoterCap>,
+    pub cut_voting_power: u64,
+    pub capped_voter_count: u32,
+    // Set for transfers to an address book entry
+    pub transfer_destination: Option<TransferDestination>,
+    // Raised threshold (percent) for transfers outside the address book, if the DAO asks for one
+    pub threshold_override: Option<u8>,
+    // Set for canister upgrades, calls and system upgrades
+    pub payload_hashes: Option<PayloadHashes>,
+    // Set for permission and request policy edits
+    pub security_impact: Option<SecurityImpact>,
+}
+
+impl UnifiedProposal {
+    /// Percentage of voting power needed to pass
+    pub fn voting_threshold(&self) -> u8 {
+        let base = self.operation_type.voting_threshold();
+        self.threshold_override.map_or(base, |t| t.max(base))
+    }
+
+    /// Split a voter's power into (counted, cut) according to the voter cap
+    pub fn apply_voter_cap(&self, voting_power: u64) -> (u64, u64) {
+        let Some(cap) = &self.voter_cap else {
+            return (voting_power, 0);
+        };
+        let max_power = (self.total_voting_power as u128 * cap.max_share_bps as u128 / 10_000) as u64;
+        let counted = voting_power.min(max_power);
+        (counted, voting_power - counted)
+    }
+
+    /// Total voting power the threshold is measured against
+    /// Under the Spread policy, weight cut by the voter cap no longer counts towards it
+    pub fn effective_total_voting_power(&self) -> u64 {
+        match &self.voter_cap {
+            Some(cap) if cap.policy == VoterCapPolicy::Spread => {
+                self.total_voting_power.saturating_sub(self.cut_voting_power)
+            }
+            _ => self.total_voting_power,
+        }
+    }
+}
+
+/// Total voting power as read from the Kong Locker index, with freshness and coverage
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VotingPowerSnapshot {
+    pub total_voting_power: u64,
+    pub indexed_at: u64,              // Completion time of the index pass used
+    pub lock_canisters_total: u64,    // Lock canisters registered with the factory
+    pub lock_canisters_indexed: u64,  // Successfully read from KongSwap in that pass
+    pub lock_canisters_failed: u64,   // Failed in that pass (previous values kept)
 }
 
 /// All possible Orbit operations in one enum
@@ -292,6 +394,48 @@ pub enum OrbitOperationType {
     Other(String),
 }
 
+impl From<&crate::api::RequestOperation> for OrbitOperationType {
+    fn from(operation: &crate::api::RequestOperation) -> Self {
+        use crate::api::RequestOperation as Op;
+        match operation {
+            Op::ManageSystemInfo(_) => Self::ManageSystemInfo,
+            Op::Transfer(_) => Self::Transfer,
+            Op::AddAccount(_) => Self::AddAccount,
+            Op::EditAccount(_) => Self::EditAccount,
+            Op::AddAddressBookEntry(_) => Self::AddAddressBookEntry,
+            Op::EditAddressBookEntry(_) => Self::EditAddressBookEntry,
+            Op::RemoveAddressBookEntry(_) => Self::RemoveAddressBookEntry,
+            Op::AddUser(_) => Self::AddUser,
+            Op::EditUser(_) => Self::EditUser,
+            Op::AddUserGroup(_) => Self::AddUserGroup,
+            Op::EditUserGroup(_) => Self::EditUserGroup,
+            Op::RemoveUserGroup(_) => Self::RemoveUserGroup,
+            Op::SystemUpgrade(_) => Self::SystemUpgrade,
+            Op::SystemRestore(_) => Self::SystemRestore,
+            Op::SetDisasterRecovery(_) => Self::SetDisasterRecovery,
+            Op::ChangeExternalCanister(_) => Self::ChangeExternalCanister,
+            Op::CreateExternalCanister(_) => Self::CreateExternalCanister,
+            Op::ConfigureExternalCanister(_) => Self::ConfigureExternalCanister,
+            Op::CallExternalCanister(_) => Self::CallExternalCanister,
+            Op::FundExternalCanister(_) => Self::FundExternalCanister,
+            Op::MonitorExternalCanister(_) => Self::MonitorExternalCanister,
+            Op::SnapshotExternalCanister(_) => Self::SnapshotExternalCanister,
+            Op::RestoreExternalCanister(_) => Self::RestoreExternalCanister,
+            Op::PruneExternalCanister(_) => Self::PruneExternalCanister,
+            Op::EditPermission(_) => Self::EditPermission,
+            Op::AddRequestPolicy(_) => Self::AddRequestPolicy,
+            Op::EditRequestPolicy(_) => Self::EditRequestPolicy,
+            Op::RemoveRequestPolicy(_) => Self::RemoveRequestPolicy,
+            Op::AddAsset(_) => Self::AddAsset,
+            Op::EditAsset(_) => Self::EditAsset,
+            Op::RemoveAsset(_) => Self::RemoveAsset,
+            Op::AddNamedRule(_) => Self::AddNamedRule,
+            Op::EditNamedRule(_) => Self::EditNamedRule,
+            Op::RemoveNamedRule(_) => Self::RemoveNamedRule,
+        }
+    }
+}
+
 impl OrbitOperationType {
     /// Get voting threshold percentage for this operation type
     pub fn voting_threshold(&self) -> u8 {
@@ -426,14 +570,43 @@ pub struct EquityTransferProposal {
     pub station_id: Principal,
     pub seller: Principal,
     pub buyer: Principal,
-    pub percentage: u8,  // 1-100
+    pub equity_bps: u32,  // Basis points, 1-10_000 (10_000 = 100%)
     pub ckusdc_amount: u64,
     pub payment_destination: PaymentDestination,
     pub status: EquityProposalStatus,
     pub created_at: u64,
     pub expires_at: u64,
-    pub yes_votes_pct: u8,  // Out of 100 total equity
-    pub no_votes_pct: u8,
+    pub yes_votes_bps: u32,  // Out of 10_000 total equity
+    pub no_votes_bps: u32,
+    pub rofr: Option<RofrWindow>,     // Set when existing holders get first refusal
+    pub rofr_parent: Option<String>,  // Set on a holder's claim: the transfer it was taken from
+    pub sell_order: Option<String>,   // Set when the transfer fills a standing sell order
+}
+
+/// Right of first refusal window on a transfer to an outside buyer
+/// equity_bps/ckusdc_amount on the transfer shrink as holders take up the offer
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RofrWindow {
+    pub ends_at: u64,
+    pub offered_bps: u32,     // Original offer
+    pub offered_ckusdc: u64,
+}
+
+/// Proposal to mint new equity to a recipient, diluting every holder pro rata
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EquityIssuanceProposal {
+    pub proposal_id: String,
+    pub station_id: Principal,
+    pub proposer: Principal,
+    pub recipient: Principal,
+    pub equity_bps: u32,     // Recipient's share of the post-issuance total, 1-9_999
+    pub ckusdc_amount: u64,  // Subscription paid into the station treasury (0 = none)
+    pub status: EquityProposalStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub yes_votes_bps: u32,  // Out of 10_000 total equity
+    pub no_votes_bps: u32,
+    pub vesting: Option<VestingTerms>,  // Issued equity vests instead of landing at once
 }
 
 /// Where payment should be sent for equity transfer
@@ -443,11 +616,39 @@ pub enum PaymentDestination {
     StationTreasury(Principal),  // Station principal
 }
 
-/// Status of equity transfer proposal
+/// ckUSDC escrowed for an equity transfer (by the buyer) or issuance (by the recipient)
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EquityEscrow {
+    pub proposal_id: String,
+    pub buyer: Principal,     // Whoever pays: transfer buyer or issuance recipient
+    pub subaccount: Vec<u8>,  // Admin canister subaccount holding the deposit
+    pub amount: u64,          // ckusdc_amount, released or refunded in full
+    pub fee: u64,             // Ledger fee deposited on top to pay for the outbound transfer
+    pub status: EscrowStatus,
+    pub deposited_at: u64,
+    pub deposit_block: Option<Nat>,
+    pub settled_at: Option<u64>,
+    pub settlement_block: Option<Nat>,
+}
+
+/// Escrow lifecycle (the *ing states guard in-flight ledger calls)
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum EscrowStatus {
+    Depositing,
+    Held,
+    Releasing,
+    Released,  // Paid to the PaymentDestination on execution
+    Refunding,
+    Refunded,  // Returned to the payer
+}
+
+/// Status of equity transfer and issuance proposals
 #[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
 pub enum EquityProposalStatus {
     Proposed,
-    Approved,  // 75% reached
-    Executed,  // Buyer executed
+    Approved,   // Threshold reached (75% transfers, 80% issuance)
+    Executed,   // Buyer (or issuance recipient/proposer) executed
     Expired,
+    Rejected,   // No-votes made the threshold unreachable
+    Cancelled,  // Withdrawn by the seller (or proposer) before approval
 }
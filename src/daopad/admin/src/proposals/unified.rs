@@ -1,6 +1,7 @@
 // Unified voting system for ALL Orbit operations
 // Admin canister version - handles voting and approval only
 
+use crate::kong_locker::config::voting_power_config;
 use crate::kong_locker::voting::{get_user_voting_power_for_token, calculate_total_voting_power_for_token};
 use crate::storage::state::{
     UNIFIED_PROPOSALS, UNIFIED_PROPOSAL_VOTES,
@@ -8,6 +9,7 @@ use crate::storage::state::{
 use crate::types::StorablePrincipal;
 use crate::proposals::types::{
     ProposalId, ProposalError, ProposalStatus, VoteChoice, UnifiedProposal, OrbitOperationType,
+    PayloadHashes, SecurityImpact, TransferDetails, TransferDestination, UnlistedTransferPolicy,
 };
 use candid::Principal;
 use ic_cdk::api::time;
@@ -86,7 +88,7 @@ pub async fn vote_on_proposal(
 
     // 6. Get voting power - ROUTE BASED ON STATION TYPE
     let voting_power = if crate::equity::is_equity_station(token_id) {
-        // EQUITY STATION: Use equity % directly (SYNC)
+        // EQUITY STATION: Use vested equity basis points directly (SYNC)
         crate::equity::get_user_equity(token_id, voter) as u64
     } else {
         // DAO STATION: Query Kong Locker (ASYNC - existing logic)
@@ -109,11 +111,17 @@ pub async fn vote_on_proposal(
         return Err(ProposalError::NoVotingPower);
     }
 
-    // 7. Record vote
+    // 7. Apply the per-voter cap, then record vote
+    let (counted_power, cut_power) = proposal.apply_voter_cap(voting_power);
+    if cut_power > 0 {
+        proposal.cut_voting_power += cut_power;
+        proposal.capped_voter_count += 1;
+    }
+
     if vote {
-        proposal.yes_votes += voting_power;
+        proposal.yes_votes += counted_power;
     } else {
-        proposal.no_votes += voting_power;
+        proposal.no_votes += counted_power;
     }
     proposal.voter_count += 1;
 
@@ -124,9 +132,10 @@ pub async fn vote_on_proposal(
         );
     });
 
-    // 8. Check threshold using proposal's snapshot of total VP
-    let threshold = proposal.operation_type.voting_threshold();
-    let required_votes = (proposal.total_voting_power * threshold as u64) / 100;
+    // 8. Check threshold using proposal's snapshot of total VP (less any spread cap cuts)
+    let threshold = proposal.voting_threshold();
+    let total_voting_power = proposal.effective_total_voting_power();
+    let required_votes = (total_voting_power * threshold as u64) / 100;
 
     if proposal.yes_votes > required_votes {
         // Query backend for station ID
@@ -161,7 +170,7 @@ pub async fn vote_on_proposal(
             let mut votes_map = votes.borrow_mut();
             votes_map.retain(|(pid, _), _| *pid != proposal_id);
         });
-    } else if proposal.no_votes > (proposal.total_voting_power - required_votes) {
+    } else if proposal.no_votes > (total_voting_power - required_votes) {
         // Rejected - impossible to reach threshold
         // Query backend for station ID
         let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
@@ -280,11 +289,15 @@ pub fn get_user_vote(
 }
 
 /// Ensure a proposal exists for an Orbit request
+/// The operation type is read from the request in Orbit; `request_type_str`
+/// is ignored and only kept for existing callers. Transfer proposals are only
+/// opened by the backend (ensure_transfer_proposal), which checks the
+/// destination against the station's allowlist policy
 #[update]
 pub async fn ensure_proposal_for_request(
     token_id: Principal,
     orbit_request_id: String,
-    request_type_str: String,
+    _request_type_str: String,
 ) -> Result<ProposalId, ProposalError> {
     let caller = ic_cdk::caller();
 
@@ -293,33 +306,107 @@ pub async fn ensure_proposal_for_request(
         return Err(ProposalError::AuthRequired);
     }
 
-    let now = time();
+    let existing = UNIFIED_PROPOSALS.with(|proposals| {
+        proposals
+            .borrow()
+            .get(&(StorablePrincipal(token_id), orbit_request_id.clone()))
+            .map(|p| p.id)
+    });
+    if let Some(existing) = existing {
+        return Ok(existing);
+    }
 
-    // Calculate actual total voting power from Kong Locker
-    let total_voting_power = match calculate_total_voting_power_for_token(token_id).await {
-        Ok(vp) => vp,
-        Err(e) => {
-            ic_cdk::println!(
-                "WARNING: Failed to calculate total VP for token {}: {}. Using fallback value of 1M.",
-                token_id, e
-            );
-            1_000_000u64 // Fallback to 1M if calculation fails
+    let station_id = station_for_token(token_id).await?;
+    let operation_type = get_orbit_request_operation(station_id, &orbit_request_id).await?;
+    if operation_type == OrbitOperationType::Transfer {
+        return Err(ProposalError::Custom(
+            "Transfer proposals are opened when DAOPad creates the transfer".to_string()
+        ));
+    }
+
+    open_proposal(token_id, orbit_request_id, operation_type, caller).await
+}
+
+/// Open a proposal for a request the backend just submitted, with the type it submitted
+/// (correcting the type if someone opened the proposal first)
+/// ONLY the backend canister can call this
+#[update]
+pub async fn ensure_typed_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+) -> Result<ProposalId, ProposalError> {
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    if ic_cdk::caller() != backend_canister {
+        return Err(ProposalError::Custom("Only the backend can set a proposal's type".to_string()));
+    }
+
+    let operation_type = OrbitOperationType::from_string(&request_type_str);
+    if operation_type == OrbitOperationType::Transfer {
+        return Err(ProposalError::Custom(
+            "Transfer proposals are opened with ensure_transfer_proposal".to_string()
+        ));
+    }
+
+    let proposal_id = open_proposal(
+        token_id,
+        orbit_request_id.clone(),
+        operation_type.clone(),
+        ic_cdk::caller(),
+    ).await?;
+
+    UNIFIED_PROPOSALS.with(|proposals| {
+        if let Some(proposal) = proposals
+            .borrow_mut()
+            .get_mut(&(StorablePrincipal(token_id), orbit_request_id))
+        {
+            set_operation_type(proposal, operation_type);
         }
+    });
+
+    Ok(proposal_id)
+}
+
+/// Create the proposal for an Orbit request unless one exists
+async fn open_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    operation_type: OrbitOperationType,
+    proposer: Principal,
+) -> Result<ProposalId, ProposalError> {
+    let now = time();
+    let key = (StorablePrincipal(token_id), orbit_request_id.clone());
+
+    // If exists, return existing ID
+    if let Some(existing) = UNIFIED_PROPOSALS.with(|proposals| proposals.borrow().get(&key).map(|p| p.id)) {
+        return Ok(existing);
+    }
+
+    // Snapshot total voting power: vested equity for equity stations, the Kong Locker index otherwise
+    // Without a real total a proposal can't be decided, so none is opened
+    let (total_voting_power, vp_snapshot) = if crate::equity::is_equity_station(token_id) {
+        (crate::equity::voting_equity_total(token_id) as u64, None)
+    } else {
+        let snapshot = calculate_total_voting_power_for_token(token_id).map_err(ProposalError::Custom)?;
+        (snapshot.total_voting_power, Some(snapshot))
     };
+    if total_voting_power == 0 {
+        return Err(ProposalError::ZeroVotingPower);
+    }
+
+    let threshold_override = address_book_threshold(token_id, &operation_type).await?;
 
-    // ATOMIC: Check-and-insert within single borrow scope
     UNIFIED_PROPOSALS.with(|proposals| {
         let mut map = proposals.borrow_mut();
-        let key = (StorablePrincipal(token_id), orbit_request_id.clone());
 
-        // If exists, return existing ID
+        // Someone may have opened it while the policy was fetched
         if let Some(existing) = map.get(&key) {
             return Ok(existing.id);
         }
 
         // Otherwise create new proposal atomically
         let proposal_id = ProposalId::new();
-        let operation_type = OrbitOperationType::from_string(&request_type_str);
         let duration_hours = operation_type.voting_duration_hours();
         let duration_nanos = duration_hours * 3600 * 1_000_000_000;
 
@@ -328,7 +415,7 @@ pub async fn ensure_proposal_for_request(
             token_canister_id: token_id,
             orbit_request_id: orbit_request_id.clone(),
             operation_type,
-            proposer: caller,
+            proposer,
             created_at: now,
             expires_at: now + duration_nanos,
             yes_votes: 0,
@@ -337,6 +424,14 @@ pub async fn ensure_proposal_for_request(
             voter_count: 0,
             status: ProposalStatus::Active,
             transfer_details: None,
+            vp_snapshot,
+            voter_cap: voting_power_config(token_id).voter_cap,
+            cut_voting_power: 0,
+            capped_voter_count: 0,
+            transfer_destination: None,
+            threshold_override,
+            payload_hashes: None,
+            security_impact: None,
         };
 
         map.insert(key, proposal);
@@ -344,12 +439,188 @@ pub async fn ensure_proposal_for_request(
     })
 }
 
+/// Threshold for adding or editing an address book entry while the token
+/// restricts unlisted transfers: listing an address must be as hard as paying
+/// it unlisted, or it would sidestep the policy
+async fn address_book_threshold(
+    token_id: Principal,
+    operation_type: &OrbitOperationType,
+) -> Result<Option<u8>, ProposalError> {
+    if !matches!(
+        operation_type,
+        OrbitOperationType::AddAddressBookEntry | OrbitOperationType::EditAddressBookEntry
+    ) || crate::equity::is_equity_station(token_id) {
+        return Ok(None);
+    }
+
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    let result: Result<(UnlistedTransferPolicy,), _> = ic_cdk::call(
+        backend_canister,
+        "get_unlisted_transfer_policy",
+        (token_id,)
+    ).await;
+    let policy = result
+        .map_err(|e| ProposalError::Custom(format!("Failed to query transfer policy: {:?}", e)))?
+        .0;
+
+    Ok(match policy {
+        UnlistedTransferPolicy::Allow => None,
+        UnlistedTransferPolicy::RaiseThreshold(threshold) => Some(threshold),
+        UnlistedTransferPolicy::Reject => Some(OrbitOperationType::Transfer.voting_threshold()),
+    })
+}
+
+/// Record the operation type the backend submitted, in case someone opened
+/// the proposal first under another one; the voting period follows the type
+fn set_operation_type(proposal: &mut UnifiedProposal, operation_type: OrbitOperationType) {
+    if proposal.operation_type != operation_type {
+        let duration_nanos = operation_type.voting_duration_hours() * 3600 * 1_000_000_000;
+        proposal.expires_at = proposal.created_at + duration_nanos;
+        proposal.operation_type = operation_type;
+    }
+}
+
+/// Create the proposal for a transfer request the backend just submitted,
+/// recording what it pays and where
+/// ONLY the backend canister can call this
+#[update]
+pub async fn ensure_transfer_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    details: TransferDetails,
+    destination: Option<TransferDestination>,
+    threshold_override: Option<u8>,
+) -> Result<ProposalId, ProposalError> {
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    if ic_cdk::caller() != backend_canister {
+        return Err(ProposalError::Custom("Only the backend can annotate transfer proposals".to_string()));
+    }
+    if threshold_override.is_some_and(|t| t == 0 || t >= 100) {
+        return Err(ProposalError::Custom("Threshold must be between 1 and 99 percent".to_string()));
+    }
+
+    let proposal_id = open_proposal(
+        token_id,
+        orbit_request_id.clone(),
+        OrbitOperationType::Transfer,
+        ic_cdk::caller(),
+    ).await?;
+
+    UNIFIED_PROPOSALS.with(|proposals| {
+        if let Some(proposal) = proposals
+            .borrow_mut()
+            .get_mut(&(StorablePrincipal(token_id), orbit_request_id))
+        {
+            set_operation_type(proposal, OrbitOperationType::Transfer);
+            proposal.transfer_details = Some(details);
+            proposal.transfer_destination = destination;
+            proposal.threshold_override = threshold_override;
+        }
+    });
+
+    Ok(proposal_id)
+}
+
+/// Open a proposal and attach the hashes of the wasm module and argument it runs
+/// ONLY the backend can call this, after hashing the payload it submitted to Orbit
+#[update]
+pub async fn ensure_hashed_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+    hashes: PayloadHashes,
+) -> Result<ProposalId, ProposalError> {
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    if ic_cdk::caller() != backend_canister {
+        return Err(ProposalError::Custom("Only the backend can attach payload hashes".to_string()));
+    }
+
+    let operation_type = OrbitOperationType::from_string(&request_type_str);
+    let proposal_id = open_proposal(
+        token_id,
+        orbit_request_id.clone(),
+        operation_type.clone(),
+        ic_cdk::caller(),
+    ).await?;
+
+    UNIFIED_PROPOSALS.with(|proposals| {
+        if let Some(proposal) = proposals
+            .borrow_mut()
+            .get_mut(&(StorablePrincipal(token_id), orbit_request_id))
+        {
+            set_operation_type(proposal, operation_type);
+            proposal.payload_hashes = Some(hashes);
+        }
+    });
+
+    Ok(proposal_id)
+}
+
+/// Open a proposal and attach the backend's simulation of its security impact
+/// ONLY the backend can call this, after simulating the change it submitted to Orbit
+#[update]
+pub async fn ensure_simulated_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+    impact: SecurityImpact,
+) -> Result<ProposalId, ProposalError> {
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    if ic_cdk::caller() != backend_canister {
+        return Err(ProposalError::Custom("Only the backend can attach a security impact".to_string()));
+    }
+
+    let operation_type = OrbitOperationType::from_string(&request_type_str);
+    let proposal_id = open_proposal(
+        token_id,
+        orbit_request_id.clone(),
+        operation_type.clone(),
+        ic_cdk::caller(),
+    ).await?;
+
+    UNIFIED_PROPOSALS.with(|proposals| {
+        if let Some(proposal) = proposals
+            .borrow_mut()
+            .get_mut(&(StorablePrincipal(token_id), orbit_request_id))
+        {
+            set_operation_type(proposal, operation_type);
+            proposal.security_impact = Some(impact);
+        }
+    });
+
+    Ok(proposal_id)
+}
+
 // ============================================================================
 // Internal helper functions
 // ============================================================================
 
+/// Orbit Station behind a token, or the station itself for equity stations
+async fn station_for_token(token_id: Principal) -> Result<Principal, ProposalError> {
+    if crate::equity::is_equity_station(token_id) {
+        return Ok(token_id);
+    }
+
+    let backend_canister = Principal::from_text("lwsav-iiaaa-aaaap-qp2qq-cai")
+        .map_err(|e| ProposalError::Custom(format!("Invalid backend ID: {}", e)))?;
+    let station_result: Result<(Option<Principal>,), _> = ic_cdk::call(
+        backend_canister,
+        "get_orbit_station_for_token",
+        (token_id,)
+    ).await;
+
+    station_result
+        .map_err(|e| ProposalError::Custom(format!("Failed to query backend: {:?}", e)))?
+        .0
+        .ok_or(ProposalError::NoStationLinked(token_id))
+}
+
 /// Approve a request in Orbit Station
-async fn approve_orbit_request(station_id: Principal, request_id: &str) -> Result<(), ProposalError> {
+pub(crate) async fn approve_orbit_request(station_id: Principal, request_id: &str) -> Result<(), ProposalError> {
     use crate::api::{SubmitRequestApprovalInput, SubmitRequestApprovalResult, RequestApprovalStatus};
 
     let input = SubmitRequestApprovalInput {
@@ -380,6 +651,57 @@ async fn approve_orbit_request(station_id: Principal, request_id: &str) -> Resul
     }
 }
 
+/// Current status of a request in Orbit Station
+pub(crate) async fn get_orbit_request_status(
+    station_id: Principal,
+    request_id: &str,
+) -> Result<crate::api::RequestStatus, ProposalError> {
+    get_orbit_request(station_id, request_id).await.map(|request| request.status)
+}
+
+/// Operation type of a request in Orbit Station
+async fn get_orbit_request_operation(
+    station_id: Principal,
+    request_id: &str,
+) -> Result<OrbitOperationType, ProposalError> {
+    get_orbit_request(station_id, request_id)
+        .await
+        .map(|request| OrbitOperationType::from(&request.operation))
+}
+
+async fn get_orbit_request(
+    station_id: Principal,
+    request_id: &str,
+) -> Result<crate::api::RequestWithStatus, ProposalError> {
+    use crate::api::{GetRequestInput, GetRequestResult};
+
+    let input = GetRequestInput {
+        request_id: request_id.to_string(),
+        with_full_info: Some(false),
+    };
+
+    let result: Result<(GetRequestResult,), _> =
+        ic_cdk::call(station_id, "get_request", (input,)).await;
+
+    match result {
+        Ok((GetRequestResult::Ok(response),)) => Ok(response.request),
+        Ok((GetRequestResult::Err(e),)) => Err(ProposalError::OrbitError {
+            code: e.code.clone(),
+            message: e.message.clone().unwrap_or_else(|| "No message provided".to_string()),
+            details: e.details.as_ref().map(|d| {
+                d.iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+        }),
+        Err((code, msg)) => Err(ProposalError::IcCallFailed {
+            code: code as i32,
+            message: msg,
+        }),
+    }
+}
+
 /// Reject a request in Orbit Station
 async fn reject_orbit_request(
     station_id: Principal,
@@ -1,16 +1,26 @@
 use crate::proposals::types::{
     ProposalId, UnifiedProposal, VoteChoice,
-    EquityStationConfig, EquityTransferProposal
+    EquityStationConfig, EquityTransferProposal, EquityEscrow, EquityIssuanceProposal
 };
+use crate::equity::migration::LegacyEquityTransferProposal;
+use crate::equity::settings::EquityStationSettings;
+use crate::equity::history::CapTableSnapshot;
+use crate::equity::vesting::VestingGrant;
+use crate::equity::dividends::DividendProposal;
+use crate::equity::conversion::{EquityConversionProposal, StationConversion};
+use crate::equity::market::EquitySellOrder;
+use crate::kong_locker::config::VotingPowerConfig;
+use crate::kong_locker::lock_age::LockTranche;
 use crate::types::{
     StorablePrincipal, StorableCandid, PrincipalPair,
-    StorableString, StringPrincipalPair
+    StorableString, StringPrincipalPair, PrincipalTimestamp
 };
+use candid::Principal;
 use candid::{CandidType, Deserialize};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use ic_stable_structures::{
-    StableBTreeMap, DefaultMemoryImpl,
+    StableBTreeMap, StableCell, DefaultMemoryImpl,
     memory_manager::{MemoryId, MemoryManager, VirtualMemory}
 };
 
@@ -22,11 +32,29 @@ thread_local! {
         RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
 }
 
-// Cache entry for total voting power with timestamp
-#[derive(Clone, Debug, CandidType, Deserialize)]
-pub struct VotingPowerCache {
+// Indexed total voting power for one token, maintained incrementally by the indexer
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct TokenVotingPowerTotal {
     pub total_vp: u64,
-    pub timestamp: u64,
+    pub lock_canister_count: u64, // Lock canisters with a non-zero position in this token
+    pub updated_at: u64,
+}
+
+// Progress and coverage of the Kong Locker voting power indexer
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct VotingPowerIndexStatus {
+    // Pass currently in progress
+    pub pass_started_at: Option<u64>,
+    pub cursor: u64,
+    pub pass_lock_canisters: u64,
+    pub pass_indexed: u64,
+    pub pass_failed: u64,
+
+    // Last fully completed pass (what proposals snapshot)
+    pub last_pass_completed_at: Option<u64>,
+    pub last_pass_lock_canisters: u64,
+    pub last_pass_indexed: u64,
+    pub last_pass_failed: u64,
 }
 
 thread_local! {
@@ -50,13 +78,71 @@ thread_local! {
         VoteChoice
     >> = RefCell::new(BTreeMap::new());
 
-    // Total voting power cache with TTL (1 hour = 3,600,000,000,000 nanoseconds)
-    // Key: token_canister_id
-    // Reduces expensive inter-canister calls by caching total VP
-    pub static TOTAL_VP_CACHE: RefCell<BTreeMap<
-        StorablePrincipal,
-        VotingPowerCache
-    >> = RefCell::new(BTreeMap::new());
+    // Lock canisters (owner, lock_canister) for the indexer pass in progress
+    // Re-fetched from the Kong Locker factory at the start of every pass
+    pub static INDEXER_PASS_QUEUE: RefCell<Vec<(Principal, Principal)>> = const { RefCell::new(Vec::new()) };
+}
+
+thread_local! {
+    // ====================================================================
+    // KONG LOCKER VOTING POWER INDEX (STABLE)
+    // ====================================================================
+    // - ID 0: LOCK_CANISTER_VP (per-lock-canister, per-token VP)
+    // - ID 1: TOKEN_VP_TOTALS (running totals per token)
+    // - ID 2: VP_INDEX_STATUS (indexer progress and coverage)
+    // - ID 3: USER_LOCK_CANISTERS (user → lock canister lookup)
+    // - ID 4: VOTING_POWER_CONFIGS (per-DAO voting power settings)
+    // - ID 5: PRICE_SAMPLES (KongSwap price samples for TWAP)
+    // - ID 6: TOKEN_DECIMALS (ICRC-1 decimals cache for pricing)
+    // - ID 7: LOCK_POSITION_TRANCHES (when each LP position was first seen and grew)
+
+    // (lock_canister, token) → voting power from that lock canister's LP positions
+    pub static LOCK_CANISTER_VP: RefCell<StableBTreeMap<PrincipalPair, u64, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(0)))
+        ));
+
+    // token → sum of LOCK_CANISTER_VP entries for that token
+    pub static TOKEN_VP_TOTALS: RefCell<StableBTreeMap<StorablePrincipal, StorableCandid<TokenVotingPowerTotal>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(1)))
+        ));
+
+    pub static VP_INDEX_STATUS: RefCell<StableCell<StorableCandid<VotingPowerIndexStatus>, Memory>> =
+        RefCell::new(StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(2))),
+            StorableCandid(VotingPowerIndexStatus::default()),
+        ).expect("Failed to init VP_INDEX_STATUS"));
+
+    // user → Kong Locker lock canister, mirrored from the factory
+    pub static USER_LOCK_CANISTERS: RefCell<StableBTreeMap<StorablePrincipal, StorablePrincipal, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(3)))
+        ));
+
+    // token → voting power settings chosen by that DAO
+    pub static VOTING_POWER_CONFIGS: RefCell<StableBTreeMap<StorablePrincipal, StorableCandid<VotingPowerConfig>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(4)))
+        ));
+
+    // (token, sampled_at) → USD price per whole token, fixed-point (see price_oracle::PRICE_SCALE)
+    pub static PRICE_SAMPLES: RefCell<StableBTreeMap<PrincipalTimestamp, u128, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(5)))
+        ));
+
+    // token → ICRC-1 decimals (immutable per ledger, cached forever)
+    pub static TOKEN_DECIMALS: RefCell<StableBTreeMap<StorablePrincipal, u8, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(6)))
+        ));
+
+    // (LP token symbol, lock_canister) → tranches of that position, oldest first
+    pub static LOCK_POSITION_TRANCHES: RefCell<StableBTreeMap<StringPrincipalPair, StorableCandid<Vec<LockTranche>>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(7)))
+        ));
 }
 
 thread_local! {
@@ -66,12 +152,29 @@ thread_local! {
     // Survives canister upgrades (unlike BTreeMap above)
     //
     // MEMORY ID ALLOCATION (to prevent collisions):
-    // - IDs 0-9: Reserved for future non-equity stable storage
+    // - IDs 0-2: Kong Locker voting power index (see above)
+    // - ID 3: Kong Locker user → lock canister index (see above)
+    // - IDs 4-6: Per-DAO voting power config and price oracle (see above)
+    // - ID 7: Kong Locker position lock ages (see above)
+    // - IDs 8-9: Reserved for future non-equity stable storage
     // - ID 10: EQUITY_STATIONS (station configs)
-    // - ID 11: EQUITY_HOLDERS (equity ownership percentages)
-    // - ID 12: EQUITY_TRANSFER_PROPOSALS (transfer proposals)
-    // - ID 13: EQUITY_TRANSFER_VOTES (votes on transfers)
-    // - IDs 14+: Available for future expansion
+    // - ID 11: LEGACY_EQUITY_HOLDERS (whole percents, migrated to ID 14 on upgrade)
+    // - ID 12: LEGACY_EQUITY_TRANSFER_PROPOSALS (whole percents, migrated to ID 15 on upgrade)
+    // - ID 13: EQUITY_TRANSFER_VOTES (votes on transfers and issuances)
+    // - ID 14: EQUITY_HOLDERS (equity ownership in basis points)
+    // - ID 15: EQUITY_TRANSFER_PROPOSALS (transfer proposals, basis points)
+    // - ID 16: EQUITY_ESCROWS (ckUSDC deposits for transfers and issuances)
+    // - ID 17: EQUITY_ESCROW_NONCE (next escrow subaccount number)
+    // - ID 18: EQUITY_SETTINGS (per-station governance settings)
+    // - ID 19: EQUITY_ISSUANCE_PROPOSALS (new equity proposals, basis points)
+    // - ID 20: VESTING_GRANTS (vesting grants on holders' equity)
+    // - ID 21: CAP_TABLE_HISTORY (cap table snapshot after every change)
+    // - ID 22: DIVIDEND_PROPOSALS (treasury payouts to holders)
+    // - ID 23: EQUITY_CONVERSION_PROPOSALS (moves to token governance)
+    // - ID 24: STATION_CONVERSIONS (completed equity/token conversions)
+    // - ID 25: EQUITY_SELL_ORDERS (standing offers to sell equity)
+    // - ID 26: EQUITY_VOTE_WEIGHTS (holders' vested equity when a proposal opened)
+    // - IDs 27+: Available for future expansion
 
     // Marks station as equity-based: station_id → EquityStationConfig
     pub static EQUITY_STATIONS: RefCell<StableBTreeMap<StorablePrincipal, StorableCandid<EquityStationConfig>, Memory>> =
@@ -79,21 +182,101 @@ thread_local! {
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(10)))
         ));
 
-    // Equity ownership: (station_id, holder) → percentage (1-100)
-    pub static EQUITY_HOLDERS: RefCell<StableBTreeMap<PrincipalPair, u8, Memory>> =
+    // Pre-basis-point equity ownership: (station_id, holder) → percentage (1-100)
+    // Read only by equity::migration; empty after the first upgrade that includes it
+    pub static LEGACY_EQUITY_HOLDERS: RefCell<StableBTreeMap<PrincipalPair, u8, Memory>> =
         RefCell::new(StableBTreeMap::init(
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(11)))
         ));
 
-    // Active proposals: proposal_id → EquityTransferProposal
-    pub static EQUITY_TRANSFER_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquityTransferProposal>, Memory>> =
+    // Pre-basis-point proposals: proposal_id → LegacyEquityTransferProposal
+    pub static LEGACY_EQUITY_TRANSFER_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<LegacyEquityTransferProposal>, Memory>> =
         RefCell::new(StableBTreeMap::init(
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(12)))
         ));
 
-    // Votes: (proposal_id, voter) → VoteChoice
+    // Votes: (proposal_id, voter) → VoteChoice (transfer and issuance IDs never collide)
     pub static EQUITY_TRANSFER_VOTES: RefCell<StableBTreeMap<StringPrincipalPair, StorableCandid<VoteChoice>, Memory>> =
         RefCell::new(StableBTreeMap::init(
             MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(13)))
         ));
+
+    // Equity ownership: (station_id, holder) → basis points (1-10_000, 10_000 = 100%)
+    pub static EQUITY_HOLDERS: RefCell<StableBTreeMap<PrincipalPair, u32, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(14)))
+        ));
+
+    // Active proposals: proposal_id → EquityTransferProposal
+    pub static EQUITY_TRANSFER_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquityTransferProposal>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(15)))
+        ));
+
+    // Escrowed payments: proposal_id → EquityEscrow (kept after settlement as a record)
+    pub static EQUITY_ESCROWS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquityEscrow>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(16)))
+        ));
+
+    // Never reused, so every escrow gets its own subaccount
+    pub static EQUITY_ESCROW_NONCE: RefCell<StableCell<u64, Memory>> =
+        RefCell::new(StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(17))),
+            0,
+        ).expect("Failed to init EQUITY_ESCROW_NONCE"));
+
+    // station_id → settings chosen by that station's holders
+    pub static EQUITY_SETTINGS: RefCell<StableBTreeMap<StorablePrincipal, StorableCandid<EquityStationSettings>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(18)))
+        ));
+
+    // Issuance proposals: proposal_id → EquityIssuanceProposal
+    pub static EQUITY_ISSUANCE_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquityIssuanceProposal>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(19)))
+        ));
+
+    // Vesting grants: grant_id → VestingGrant (revoked grants kept as a record)
+    pub static VESTING_GRANTS: RefCell<StableBTreeMap<StorableString, StorableCandid<VestingGrant>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(20)))
+        ));
+
+    // Cap table history: (station_id, snapshot time) → CapTableSnapshot
+    pub static CAP_TABLE_HISTORY: RefCell<StableBTreeMap<PrincipalTimestamp, StorableCandid<CapTableSnapshot>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(21)))
+        ));
+
+    // Dividend proposals: proposal_id → DividendProposal (with per-holder payout status)
+    pub static DIVIDEND_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<DividendProposal>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(22)))
+        ));
+
+    // Conversion proposals: proposal_id → EquityConversionProposal
+    pub static EQUITY_CONVERSION_PROPOSALS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquityConversionProposal>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(23)))
+        ));
+
+    // Conversion log: (station_id, conversion time) → StationConversion
+    pub static STATION_CONVERSIONS: RefCell<StableBTreeMap<PrincipalTimestamp, StorableCandid<StationConversion>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(24)))
+        ));
+
+    // Sell orders: order_id → EquitySellOrder (fills are transfers with sell_order set)
+    pub static EQUITY_SELL_ORDERS: RefCell<StableBTreeMap<StorableString, StorableCandid<EquitySellOrder>, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(25)))
+        ));
+
+    // Vote weights: (proposal_id, holder) → vested bps when the proposal opened
+    pub static EQUITY_VOTE_WEIGHTS: RefCell<StableBTreeMap<StringPrincipalPair, u32, Memory>> =
+        RefCell::new(StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(26)))
+        ));
 }
@@ -109,3 +109,31 @@ impl Storable for StringPrincipalPair {
         is_fixed_size: false,
     };
 }
+
+// Time series key: (principal, timestamp in nanoseconds)
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrincipalTimestamp(pub Principal, pub u64);
+
+impl Storable for PrincipalTimestamp {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        let p_bytes = self.0.as_slice();
+        let mut bytes = Vec::with_capacity(1 + p_bytes.len() + 8);
+        bytes.push(p_bytes.len() as u8);  // Length prefix for principal
+        bytes.extend_from_slice(p_bytes);
+        bytes.extend_from_slice(&self.1.to_be_bytes());
+        Cow::Owned(bytes)
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        let len = bytes[0] as usize;
+        let p = Principal::from_slice(&bytes[1..1 + len]);
+        let mut ts = [0u8; 8];
+        ts.copy_from_slice(&bytes[1 + len..1 + len + 8]);
+        Self(p, u64::from_be_bytes(ts))
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 38, // 1 (length prefix) + 29 (principal) + 8 (timestamp)
+        is_fixed_size: false,
+    };
+}
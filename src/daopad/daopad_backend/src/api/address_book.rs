@@ -1,5 +1,7 @@
-use candid::{CandidType, Deserialize};
-use ic_cdk::{query, update};
+use crate::api::orbit::get_station_id_for_token_or_equity;
+use crate::proposals::unified::{create_orbit_request_with_proposal, OrbitOperation};
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::update;
 use serde::Serialize;
 
 // Type definitions matching the candid interface
@@ -70,23 +72,9 @@ pub enum ListAddressBookEntriesResult {
     Err(crate::types::Error),
 }
 
-// Request creation types
+// Governed address book changes
 #[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct CreateRequestInput {
-    pub operation: RequestOperationInput,
-    pub title: Option<String>,
-    pub summary: Option<String>,
-    pub execution_plan: Option<RequestExecutionPlan>,
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub enum RequestExecutionPlan {
-    Immediate,
-    Scheduled { execution_time: String },
-}
-
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub enum RequestOperationInput {
+pub enum AddressBookOperationInput {
     AddAddressBookEntry(AddAddressBookEntryOperationInput),
     EditAddressBookEntry(EditAddressBookEntryOperationInput),
     RemoveAddressBookEntry(RemoveAddressBookEntryOperationInput),
@@ -122,107 +110,109 @@ pub struct RemoveAddressBookEntryOperationInput {
     pub address_book_entry_id: UUID,
 }
 
-#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
-pub struct AddressBookRequest {
-    pub id: String,
-    pub title: String,
-    pub status: String,
-}
-
-#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
-pub enum CreateRequestResult {
-    Ok { request: AddressBookRequest },
-    Err(crate::types::Error),
+/// Every address book entry of an Orbit station, following pagination
+pub(crate) async fn list_station_address_book(
+    station_id: candid::Principal,
+) -> Result<Vec<AddressBookEntry>, String> {
+    let mut entries = Vec::new();
+    let mut offset = None;
+    loop {
+        let input = ListAddressBookEntriesInput {
+            ids: None,
+            addresses: None,
+            blockchain: None,
+            labels: None,
+            paginate: Some(crate::types::PaginationInput { limit: Some(100), offset }),
+            address_formats: None,
+            search_term: None,
+        };
+
+        let result: Result<(ListAddressBookEntriesResult,), _> =
+            ic_cdk::call(station_id, "list_address_book_entries", (input,)).await;
+        match result {
+            Ok((ListAddressBookEntriesResult::Ok(page),)) => {
+                entries.extend(page.address_book_entries);
+                match page.next_offset {
+                    Some(next) => offset = Some(next),
+                    None => return Ok(entries),
+                }
+            }
+            Ok((ListAddressBookEntriesResult::Err(e),)) => {
+                return Err(format!("Orbit error: {} - {}", e.code, e.message.unwrap_or_default()))
+            }
+            Err((code, msg)) => return Err(format!("Failed to list address book: {:?} - {}", code, msg)),
+        }
+    }
 }
 
 // Implementation of address book methods
 
-#[query]
-async fn get_address_book_entry(_input: GetAddressBookEntryInput) -> GetAddressBookEntryResult {
-    // For now, we'll proxy this to Orbit Station
-    // In a real implementation, we'd check if we have an Orbit station configured
-
-    // Get the default orbit station (we'll use the first one for now)
-    let stations = crate::api::orbit::list_all_orbit_stations();
-    if stations.is_empty() {
-        return GetAddressBookEntryResult::Err(crate::types::Error {
-            code: "NO_ORBIT_STATION".to_string(),
-            message: Some("No Orbit Station configured".to_string()),
-            details: None,
-        });
+#[update] // MUST be update, not query for cross-canister calls
+pub async fn get_address_book_entry(
+    token_canister_id: Principal,
+    input: GetAddressBookEntryInput,
+) -> GetAddressBookEntryResult {
+    let station_id = match get_station_id_for_token_or_equity(token_canister_id).await {
+        Ok(station_id) => station_id,
+        Err(e) => return GetAddressBookEntryResult::Err(no_station_error(e)),
+    };
+
+    let result: Result<(GetAddressBookEntryResult,), _> =
+        ic_cdk::call(station_id, "get_address_book_entry", (input,)).await;
+    match result {
+        Ok((result,)) => result,
+        Err((code, msg)) => GetAddressBookEntryResult::Err(call_error(code, msg)),
     }
+}
 
-    let _station_id = stations[0].1;
-
-    // For the MVP, we return a mock entry
-    // In production, this would query Orbit Station
-    GetAddressBookEntryResult::Err(crate::types::Error {
-        code: "NOT_IMPLEMENTED".to_string(),
-        message: Some("Address book entry retrieval not yet implemented".to_string()),
-        details: None,
-    })
-}
-
-#[query]
-pub async fn list_address_book_entries(_input: ListAddressBookEntriesInput) -> ListAddressBookEntriesResult {
-    // For now, return an empty list
-    // In a real implementation, we'd query Orbit Station for address book entries
-
-    let stations = crate::api::orbit::list_all_orbit_stations();
-    if stations.is_empty() {
-        return ListAddressBookEntriesResult::Ok(ListAddressBookEntriesResponse {
-            address_book_entries: vec![],
-            privileges: vec![],
-            total: 0,
-            next_offset: None,
-        });
+#[update] // MUST be update, not query for cross-canister calls
+pub async fn list_address_book_entries(
+    token_canister_id: Principal,
+    input: ListAddressBookEntriesInput,
+) -> ListAddressBookEntriesResult {
+    let station_id = match get_station_id_for_token_or_equity(token_canister_id).await {
+        Ok(station_id) => station_id,
+        Err(e) => return ListAddressBookEntriesResult::Err(no_station_error(e)),
+    };
+
+    let result: Result<(ListAddressBookEntriesResult,), _> =
+        ic_cdk::call(station_id, "list_address_book_entries", (input,)).await;
+    match result {
+        Ok((result,)) => result,
+        Err((code, msg)) => ListAddressBookEntriesResult::Err(call_error(code, msg)),
     }
-
-    // For MVP, return empty list
-    ListAddressBookEntriesResult::Ok(ListAddressBookEntriesResponse {
-        address_book_entries: vec![],
-        privileges: vec![],
-        total: 0,
-        next_offset: None,
-    })
 }
 
+/// Propose an address book change; returns the Orbit request ID voted on
+/// Changes pass at the address book threshold (30% of voting power)
 #[update]
-async fn create_address_book_request(input: CreateRequestInput) -> CreateRequestResult {
-    // Get the first configured orbit station
-    let stations = crate::api::orbit::list_all_orbit_stations();
-    if stations.is_empty() {
-        return CreateRequestResult::Err(crate::types::Error {
-            code: "NO_ORBIT_STATION".to_string(),
-            message: Some("No Orbit Station configured".to_string()),
-            details: None,
-        });
+pub async fn create_address_book_request(
+    token_canister_id: Principal,
+    operation: AddressBookOperationInput,
+) -> Result<String, String> {
+    let operation = match operation {
+        AddressBookOperationInput::AddAddressBookEntry(input) => OrbitOperation::AddAddressBookEntry(input),
+        AddressBookOperationInput::EditAddressBookEntry(input) => OrbitOperation::EditAddressBookEntry(input),
+        AddressBookOperationInput::RemoveAddressBookEntry(input) => OrbitOperation::RemoveAddressBookEntry(input),
+    };
+
+    create_orbit_request_with_proposal(token_canister_id, operation)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn no_station_error(message: String) -> crate::types::Error {
+    crate::types::Error {
+        code: "NO_ORBIT_STATION".to_string(),
+        message: Some(message),
+        details: None,
     }
+}
 
-    let _station_id = stations[0].1;
-
-    // Convert our request operation to Orbit's format
-    // For now, we'll return a mock response
-    // In production, this would create a request in Orbit Station
-
-    let request_title = input.title.unwrap_or_else(|| match &input.operation {
-        RequestOperationInput::AddAddressBookEntry(op) => {
-            format!("Add address book entry: {}", op.address_owner)
-        }
-        RequestOperationInput::EditAddressBookEntry(_) => {
-            "Edit address book entry".to_string()
-        }
-        RequestOperationInput::RemoveAddressBookEntry(_) => {
-            "Remove address book entry".to_string()
-        }
-    });
-
-    // Return a mock successful response
-    CreateRequestResult::Ok {
-        request: AddressBookRequest {
-            id: "mock-request-id".to_string(),
-            title: request_title,
-            status: "Created".to_string(),
-        }
+fn call_error(code: ic_cdk::api::call::RejectionCode, msg: String) -> crate::types::Error {
+    crate::types::Error {
+        code: "CALL_FAILED".to_string(),
+        message: Some(format!("{:?} - {}", code, msg)),
+        details: None,
     }
-}
\ No newline at end of file
+}
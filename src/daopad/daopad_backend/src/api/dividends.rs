@@ -0,0 +1,184 @@
+// Dividend payouts for equity stations
+//
+// The admin canister decides dividends by equity vote and then asks us to
+// create the Orbit Transfer requests, one per holder; it approves them itself.
+// Each request ID is stored against (dividend, holder) as soon as it is
+// created, so a retried batch returns the existing request instead of paying
+// the holder twice. Only a request Orbit rejected, cancelled or failed is
+// replaced. A holder is paid to an ICRC-1 address book entry whose account is
+// owned by their principal; the entry's owner name and metadata are free text
+// anyone with address book rights can set, so they are never trusted. Holders
+// without one are reported back so the admin can retry once they add it.
+
+use crate::api::address_book::{list_station_address_book, AddressBookEntry};
+use crate::api::orbit_requests::{GetRequestInput, GetRequestResult, RequestStatus};
+use crate::proposals::unified::create_transfer_request_in_orbit;
+use crate::proposals::TransferDetails;
+use crate::storage::state::DIVIDEND_PAYOUT_REQUESTS;
+use crate::types::{StorablePrincipal, StorableString};
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::update;
+
+// Mirror the types from admin canister for dividend payouts
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendTransfersInput {
+    pub station_id: Principal,
+    pub dividend_id: String,
+    pub from_account_id: String,
+    pub from_asset_id: String,
+    pub payouts: Vec<(Principal, Nat)>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum DividendTransferResult {
+    Requested(String),  // Orbit request ID
+    MissingAddress,
+    Failed(String),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DividendTransferOutcome {
+    pub holder: Principal,
+    pub result: DividendTransferResult,
+}
+
+/// Owner of an ICRC-1 textual account: `<principal>` or `<principal>-<checksum>.<subaccount>`
+fn icrc1_owner(address: &str) -> Option<Principal> {
+    let owner = match address.rsplit_once('.') {
+        Some((prefix, _subaccount)) => prefix.rsplit_once('-')?.0,
+        None => address,
+    };
+    Principal::from_text(owner).ok()
+}
+
+/// The holder's ICRC-1 address on the station's address book, if they have one
+fn payout_address(entries: &[AddressBookEntry], holder: Principal) -> Option<String> {
+    entries.iter()
+        .filter(|e| e.blockchain == "icp" && e.address_format == "icrc1_account")
+        .find(|e| icrc1_owner(&e.address) == Some(holder))
+        .map(|e| e.address.clone())
+}
+
+/// Whether an existing payout request can no longer pay the holder
+async fn request_failed(station_id: Principal, request_id: &str) -> Result<bool, String> {
+    let input = GetRequestInput {
+        request_id: request_id.to_string(),
+        with_full_info: Some(false),
+    };
+    let result: Result<(GetRequestResult,), _> =
+        ic_cdk::call(station_id, "get_request", (input,)).await;
+    match result {
+        Ok((GetRequestResult::Ok(response),)) => Ok(matches!(
+            response.request.status,
+            RequestStatus::Rejected | RequestStatus::Cancelled { .. } | RequestStatus::Failed { .. }
+        )),
+        Ok((GetRequestResult::Err(e),)) => Err(format!("Orbit error: {:?}", e.message)),
+        Err((code, msg)) => Err(format!("Cross-canister call failed: {:?} - {}", code, msg)),
+    }
+}
+
+/// Create the Orbit transfers for a dividend approved by equity holders
+/// ONLY the admin canister can call this
+#[update]
+pub async fn create_dividend_transfers(
+    input: DividendTransfersInput,
+) -> Result<Vec<DividendTransferOutcome>, String> {
+    let admin_canister = Principal::from_text("odkrm-viaaa-aaaap-qp2oq-cai")
+        .map_err(|e| format!("Invalid admin principal: {}", e))?;
+    if ic_cdk::caller() != admin_canister {
+        return Err("Only the admin canister can create dividend transfers".to_string());
+    }
+
+    let entries = list_station_address_book(input.station_id).await?;
+
+    let mut outcomes = Vec::with_capacity(input.payouts.len());
+    for (holder, amount) in input.payouts {
+        let key = (StorableString(input.dividend_id.clone()), StorablePrincipal(holder));
+        let existing = DIVIDEND_PAYOUT_REQUESTS.with(|requests| requests.borrow().get(&key)).map(|id| id.0);
+        if let Some(request_id) = existing {
+            match request_failed(input.station_id, &request_id).await {
+                Ok(false) => {
+                    outcomes.push(DividendTransferOutcome {
+                        holder,
+                        result: DividendTransferResult::Requested(request_id),
+                    });
+                    continue;
+                }
+                Ok(true) => {}
+                Err(e) => {
+                    // Unknown state: creating another request could pay twice
+                    outcomes.push(DividendTransferOutcome { holder, result: DividendTransferResult::Failed(e) });
+                    continue;
+                }
+            }
+        }
+
+        let result = match payout_address(&entries, holder) {
+            None => DividendTransferResult::MissingAddress,
+            Some(to) => {
+                let details = TransferDetails {
+                    from_account_id: input.from_account_id.clone(),
+                    from_asset_id: input.from_asset_id.clone(),
+                    to,
+                    amount,
+                    memo: None,
+                    title: format!("Dividend payout to {}", holder),
+                    description: format!("Share of dividend {} approved by equity vote", input.dividend_id),
+                };
+                match create_transfer_request_in_orbit(input.station_id, details).await {
+                    Ok(request_id) => {
+                        DIVIDEND_PAYOUT_REQUESTS.with(|requests| {
+                            requests.borrow_mut().insert(key, StorableString(request_id.clone()));
+                        });
+                        DividendTransferResult::Requested(request_id)
+                    }
+                    Err(e) => DividendTransferResult::Failed(e.to_string()),
+                }
+            }
+        };
+        outcomes.push(DividendTransferOutcome { holder, result });
+    }
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::address_book::MetadataItem;
+
+    const HOLDER: &str = "rrkah-fqaaa-aaaaa-aaaaq-cai";
+    const OTHER: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai";
+
+    fn entry(address: &str, owner: &str) -> AddressBookEntry {
+        AddressBookEntry {
+            id: "id".to_string(),
+            address_owner: owner.to_string(),
+            address: address.to_string(),
+            address_format: "icrc1_account".to_string(),
+            blockchain: "icp".to_string(),
+            labels: vec![],
+            metadata: vec![MetadataItem { key: "principal".to_string(), value: owner.to_string() }],
+            last_modification_timestamp: String::new(),
+        }
+    }
+
+    #[test]
+    fn owner_is_read_from_the_account() {
+        let holder = Principal::from_text(HOLDER).unwrap();
+        assert_eq!(icrc1_owner(HOLDER), Some(holder));
+        assert_eq!(icrc1_owner(&format!("{}-6cc627i.1", HOLDER)), Some(holder));
+        assert_eq!(icrc1_owner("not an account"), None);
+    }
+
+    #[test]
+    fn payout_ignores_entries_only_labelled_with_the_holder() {
+        let holder = Principal::from_text(HOLDER).unwrap();
+        // Someone else's account, named and tagged as the holder's
+        let entries = vec![entry(OTHER, HOLDER)];
+        assert_eq!(payout_address(&entries, holder), None);
+
+        let entries = vec![entry(OTHER, HOLDER), entry(HOLDER, "anything")];
+        assert_eq!(payout_address(&entries, holder), Some(HOLDER.to_string()));
+    }
+}
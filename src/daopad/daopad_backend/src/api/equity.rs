@@ -8,10 +8,28 @@ pub enum VotingPowerSource {
     KongLocker,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PositionVotingPower {
+    pub lp_symbol: String,
+    pub base_voting_power: u64,
+    pub voting_power: u64,
+    pub multiplier_bps: u32,
+    pub first_locked_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VotingPowerBreakdown {
+    pub lock_canister: Principal,
+    pub base_voting_power: u64,
+    pub voting_power: u64,
+    pub positions: Vec<PositionVotingPower>,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct VotingPowerResult {
     pub voting_power: u64,
     pub source: VotingPowerSource,
+    pub breakdown: Option<VotingPowerBreakdown>,
 }
 
 #[update]
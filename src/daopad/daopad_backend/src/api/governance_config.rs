@@ -1,5 +1,5 @@
-use crate::storage::state::{TOKEN_ORBIT_STATIONS, VOTING_THRESHOLDS};
-use crate::types::{StorablePrincipal, VotingThresholds};
+use crate::storage::state::{TOKEN_ORBIT_STATIONS, TRANSFER_ALLOWLIST_POLICIES, VOTING_THRESHOLDS};
+use crate::types::{StorablePrincipal, UnlistedTransferPolicy, VotingThresholds};
 use candid::{CandidType, Deserialize, Principal};
 use ic_cdk::{query, update};
 
@@ -172,39 +172,66 @@ pub fn has_proposal_passed(
     Ok(yes_percentage >= config.required_threshold as u64)
 }
 
+/// Set how transfers to addresses outside the address book are handled
+/// ONLY the token's Orbit Station can call this, i.e. through a passed request
+#[update]
+pub fn set_unlisted_transfer_policy(
+    token_canister_id: Principal,
+    policy: UnlistedTransferPolicy,
+) -> Result<String, String> {
+    let station = TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations
+            .borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|s| s.0)
+    })
+    .ok_or_else(|| "No Orbit Station linked to this token".to_string())?;
+
+    if ic_cdk::caller() != station {
+        return Err("Only the token's Orbit Station can change the transfer policy".to_string());
+    }
+    if let UnlistedTransferPolicy::RaiseThreshold(threshold) = policy {
+        if threshold == 0 || threshold > 99 {
+            return Err("Raised threshold must be between 1 and 99".to_string());
+        }
+    }
+
+    TRANSFER_ALLOWLIST_POLICIES.with(|policies| {
+        policies
+            .borrow_mut()
+            .insert(StorablePrincipal(token_canister_id), policy);
+    });
+
+    Ok(format!(
+        "Unlisted transfer policy updated for token {}",
+        token_canister_id
+    ))
+}
+
+#[query]
+pub fn get_unlisted_transfer_policy(token_canister_id: Principal) -> UnlistedTransferPolicy {
+    unlisted_transfer_policy(token_canister_id)
+}
+
+pub(crate) fn unlisted_transfer_policy(token_canister_id: Principal) -> UnlistedTransferPolicy {
+    TRANSFER_ALLOWLIST_POLICIES.with(|policies| {
+        policies
+            .borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .unwrap_or_default()
+    })
+}
+
 /// Get total voting power for a token across all Kong Locker users
 ///
-/// This sums up the voting power of all registered users for a specific token.
+/// Read from the admin canister's index, which covers every lock canister.
 /// Used by frontend to show user's VP as a percentage of total.
 #[update]
 pub async fn get_total_voting_power_for_token(
     token_canister_id: Principal
 ) -> Result<u64, String> {
-    // Import the calculation function from proposals module
-    use crate::kong_locker::voting::calculate_voting_power_for_token;
-    use crate::storage::state::KONG_LOCKER_PRINCIPALS;
-
-    // Get all registered Kong Locker principals
-    let all_kong_lockers = KONG_LOCKER_PRINCIPALS.with(|principals| {
-        principals
-            .borrow()
-            .iter()
-            .map(|(_, locker)| locker.0)
-            .collect::<Vec<Principal>>()
-    });
-
-    // Calculate total voting power across all registered users
-    let mut total_power = 0u64;
-
-    for kong_locker in all_kong_lockers {
-        // Get voting power for this specific token
-        match calculate_voting_power_for_token(kong_locker, token_canister_id).await {
-            Ok(power) => total_power += power,
-            Err(_) => continue, // Skip users with errors (e.g., no LP positions)
-        }
-    }
-
-    Ok(total_power)
+    // Maintained in the background by the admin canister's Kong Locker index
+    crate::kong_locker::voting::get_total_indexed_voting_power(token_canister_id).await
 }
 
 #[derive(CandidType, Deserialize, Clone, serde::Serialize)]
@@ -230,38 +257,32 @@ pub struct AllVotingPowersResponse {
 pub async fn get_all_voting_powers_for_token(
     token_canister_id: Principal
 ) -> Result<AllVotingPowersResponse, String> {
-    use crate::kong_locker::voting::calculate_voting_power_for_token;
-    use crate::storage::state::KONG_LOCKER_PRINCIPALS;
-
-    // 1. Get all registered Kong Locker principals
-    let all_registrations = KONG_LOCKER_PRINCIPALS.with(|principals| {
-        principals
-            .borrow()
-            .iter()
-            .map(|(user, locker)| (user.0, locker.0))
-            .collect::<Vec<(Principal, Principal)>>()
-    });
-
-    // 2. Calculate voting power for each user
+    use crate::kong_locker::registration::list_kong_locker_registrations;
+    use crate::kong_locker::voting::list_indexed_voting_powers;
+    use std::collections::BTreeMap;
+
+    // 1. Map lock canisters back to their owners
+    let owners: BTreeMap<Principal, Principal> = list_kong_locker_registrations()
+        .await?
+        .into_iter()
+        .map(|(user, locker)| (locker, user))
+        .collect();
+
+    // 2. Indexed voting power for each lock canister (only non-zero entries are indexed)
     let mut entries: Vec<VotingPowerEntry> = Vec::new();
     let mut total_power = 0u64;
 
-    for (user_principal, kong_locker_principal) in all_registrations {
-        // Get voting power for this specific token
-        match calculate_voting_power_for_token(kong_locker_principal, token_canister_id).await {
-            Ok(power) => {
-                if power > 0 {
-                    entries.push(VotingPowerEntry {
-                        user_principal,
-                        kong_locker_principal,
-                        voting_power: power,
-                        equity_percentage: 0.0, // Will calculate after total is known
-                    });
-                    total_power += power;
-                }
-            }
-            Err(_) => continue, // Skip users with errors (e.g., no LP positions)
-        }
+    for (kong_locker_principal, power) in list_indexed_voting_powers(token_canister_id).await? {
+        let Some(user_principal) = owners.get(&kong_locker_principal).copied() else {
+            continue;
+        };
+        entries.push(VotingPowerEntry {
+            user_principal,
+            kong_locker_principal,
+            voting_power: power,
+            equity_percentage: 0.0, // Will calculate after total is known
+        });
+        total_power += power;
     }
 
     // 3. Calculate equity percentages
@@ -0,0 +1,197 @@
+// Governance profiles
+//
+// A profile is a named target configuration for a DAO's Orbit Station. We
+// compare it with the station's current permissions and request policies and
+// turn every difference into a governed request, so holders review and vote
+// on the whole change set as one batch of proposals.
+
+use crate::api::security::governance_checks::{fetch_permissions, fetch_policies};
+use crate::api::security::security_utils::format_resource_for_reference;
+use crate::api::security::{analyze_policy_rule, format_request_specifier};
+use crate::proposals::unified::{create_governed_request, OrbitOperation, OrbitOperationType};
+use crate::storage::state::TOKEN_ORBIT_STATIONS;
+use crate::types::orbit::{
+    AuthScope, EditPermissionOperationInput, EditRequestPolicyOperationInput, ExternalCanisterAction,
+    NotificationAction, Permission, PermissionAction, Quorum, RequestAction, RequestPolicy,
+    RequestPolicyRule, Resource, ResourceAction, SystemAction, UserAction, UserSpecifier,
+};
+use crate::types::StorablePrincipal;
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::update;
+
+pub(crate) const ADMIN_GROUP_ID: &str = "00000000-0000-4000-8000-000000000000";
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum GovernanceProfile {
+    // Only the Admin group (the DAOPad backend, acting on passed votes) can
+    // change anything, and no policy lets anyone else approve a request
+    FullyDecentralized,
+}
+
+/// One change a profile would make
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GovernanceProfileChange {
+    pub title: String,
+    pub reason: String,
+    pub operation_type: OrbitOperationType,
+}
+
+/// What happened to one change when the profile was proposed
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GovernanceProfileOutcome {
+    pub change: GovernanceProfileChange,
+    pub result: Result<String, String>, // Orbit request ID
+}
+
+/// Resources that change the station rather than just read it
+fn is_write_resource(resource: &Resource) -> bool {
+    match resource {
+        Resource::Account(action) | Resource::AddressBook(action) | Resource::Asset(action)
+        | Resource::NamedRule(action) | Resource::RequestPolicy(action) | Resource::UserGroup(action) => {
+            !matches!(action, ResourceAction::Read(_) | ResourceAction::List)
+        }
+        Resource::ExternalCanister(action) => {
+            !matches!(action, ExternalCanisterAction::Read(_) | ExternalCanisterAction::List)
+        }
+        Resource::Permission(action) => *action == PermissionAction::Update,
+        Resource::System(action) => !matches!(action, SystemAction::Capabilities | SystemAction::SystemInfo),
+        Resource::User(action) => !matches!(action, UserAction::Read(_) | UserAction::List),
+        Resource::Request(RequestAction::Read(_) | RequestAction::List) => false,
+        Resource::Notification(action) => !matches!(action, NotificationAction::Read | NotificationAction::List),
+    }
+}
+
+/// Whether only the Admin group holds a permission
+pub(crate) fn admin_only(permission: &Permission) -> bool {
+    matches!(permission.allow.auth_scope, AuthScope::Restricted)
+        && permission.allow.users.is_empty()
+        && permission.allow.user_groups.iter().all(|g| g == ADMIN_GROUP_ID)
+}
+
+/// Restrict a permission to the Admin group, so only passed votes can use it
+pub(crate) fn restrict_to_admin(permission: &Permission) -> (GovernanceProfileChange, OrbitOperation) {
+    let name = format_resource_for_reference(&permission.resource);
+    (
+        GovernanceProfileChange {
+            title: format!("Restrict {} to Admin group", name),
+            reason: format!(
+                "{} is open to {:?} access, {} user(s) and {} group(s) besides Admin",
+                name,
+                permission.allow.auth_scope,
+                permission.allow.users.len(),
+                permission.allow.user_groups.iter().filter(|g| *g != ADMIN_GROUP_ID).count(),
+            ),
+            operation_type: OrbitOperationType::EditPermission,
+        },
+        OrbitOperation::EditPermission(EditPermissionOperationInput {
+            resource: permission.resource.clone(),
+            auth_scope: Some(AuthScope::Restricted),
+            users: Some(vec![]),
+            user_groups: Some(vec![ADMIN_GROUP_ID.to_string()]),
+        }),
+    )
+}
+
+/// Replace a policy rule that lets someone other than Admin approve
+/// AutoApproved is how DAOPad executes passed votes, so it is left alone
+pub(crate) fn require_admin_approval(policy: &RequestPolicy) -> Option<(GovernanceProfileChange, OrbitOperation)> {
+    let analysis = analyze_policy_rule(&policy.rule);
+    if !analysis.has_bypass || analysis.is_auto_approved {
+        return None;
+    }
+    let name = format_request_specifier(&policy.specifier);
+    Some((
+        GovernanceProfileChange {
+            title: format!("Require Admin approval for {}", name),
+            reason: analysis.bypass_reason.unwrap_or_else(|| "Policy allows bypassing admin approval".to_string()),
+            operation_type: OrbitOperationType::EditRequestPolicy,
+        },
+        OrbitOperation::EditRequestPolicy(EditRequestPolicyOperationInput {
+            policy_id: policy.id.clone(),
+            specifier: None,
+            rule: Some(RequestPolicyRule::Quorum(Quorum {
+                approvers: UserSpecifier::Group(vec![ADMIN_GROUP_ID.to_string()]),
+                min_approved: 1,
+            })),
+        }),
+    ))
+}
+
+fn fully_decentralized_changes(
+    permissions: Vec<Permission>,
+    policies: Vec<RequestPolicy>,
+) -> Vec<(GovernanceProfileChange, OrbitOperation)> {
+    permissions
+        .iter()
+        .filter(|p| is_write_resource(&p.resource) && !admin_only(p))
+        .map(restrict_to_admin)
+        .chain(policies.iter().filter_map(require_admin_approval))
+        .collect()
+}
+
+async fn profile_changes(
+    token_canister_id: Principal,
+    profile: &GovernanceProfile,
+) -> Result<Vec<(GovernanceProfileChange, OrbitOperation)>, String> {
+    let station_id = TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations
+            .borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|s| s.0)
+    })
+    .ok_or_else(|| "No Orbit Station linked to this token".to_string())?;
+
+    let permissions = fetch_permissions(station_id).await?.permissions;
+    let policies = fetch_policies(station_id).await?;
+
+    Ok(match profile {
+        GovernanceProfile::FullyDecentralized => fully_decentralized_changes(permissions, policies),
+    })
+}
+
+/// The changes a profile would propose for a token's station
+#[update] // MUST be update, not query for cross-canister calls
+pub async fn preview_governance_profile(
+    token_canister_id: Principal,
+    profile: GovernanceProfile,
+) -> Result<Vec<GovernanceProfileChange>, String> {
+    Ok(profile_changes(token_canister_id, &profile)
+        .await?
+        .into_iter()
+        .map(|(change, _)| change)
+        .collect())
+}
+
+/// Propose every change of a profile, one governed request each
+/// A change that fails doesn't stop the rest; each outcome says what happened
+#[update]
+pub async fn apply_governance_profile(
+    token_canister_id: Principal,
+    profile: GovernanceProfile,
+) -> Result<Vec<GovernanceProfileOutcome>, String> {
+    let changes = profile_changes(token_canister_id, &profile).await?;
+    if changes.is_empty() {
+        return Err("The station already matches this profile".to_string());
+    }
+
+    let total = changes.len();
+    let mut outcomes = Vec::with_capacity(total);
+    for (index, (change, operation)) in changes.into_iter().enumerate() {
+        let summary = format!(
+            "{:?} governance profile, change {} of {}: {}",
+            profile, index + 1, total, change.reason
+        );
+        let result = create_governed_request(
+            token_canister_id,
+            operation,
+            Some(change.title.clone()),
+            Some(summary),
+            None,
+        )
+        .await
+        .map_err(|e| e.to_string());
+        outcomes.push(GovernanceProfileOutcome { change, result });
+    }
+
+    Ok(outcomes)
+}
@@ -1,55 +1,27 @@
-use crate::kong_locker::{get_all_locked_tokens, get_user_voting_power_for_token, register_with_kong_locker_internal};
-use crate::storage::state::KONG_LOCKER_PRINCIPALS;
-use crate::types::{StorablePrincipal, TokenInfo};
+use crate::kong_locker::{
+    get_all_locked_tokens, get_or_lookup_kong_locker, get_user_voting_power_for_token,
+    list_kong_locker_registrations,
+};
+use crate::types::LockedTokensPage;
 use candid::Principal;
-use ic_cdk::{query, update};
+use ic_cdk::update;
 
+/// Get the caller's Kong Locker canister from the admin canister's index
+/// Update call: the lookup is an inter-canister call and may refresh the index
 #[update]
-pub async fn register_with_kong_locker(kong_locker_principal: Principal) -> Result<String, String> {
+pub async fn get_my_kong_locker_canister() -> Option<Principal> {
     let caller = ic_cdk::caller();
 
     if caller == Principal::anonymous() {
-        return Err("Authentication required".to_string());
+        return None;
     }
 
-    register_with_kong_locker_internal(caller, kong_locker_principal).await
-}
-
-#[query]
-pub fn get_my_kong_locker_canister() -> Option<Principal> {
-    let caller = ic_cdk::caller();
-    KONG_LOCKER_PRINCIPALS.with(|p| p.borrow().get(&StorablePrincipal(caller)).map(|sp| sp.0))
-}
-
-#[query]
-pub fn list_all_kong_locker_registrations() -> Vec<(Principal, Principal)> {
-    KONG_LOCKER_PRINCIPALS.with(|p| {
-        p.borrow()
-            .iter()
-            .map(|(user, canister)| (user.0, canister.0))
-            .collect()
-    })
+    get_or_lookup_kong_locker(caller).await.ok()
 }
 
 #[update]
-pub fn unregister_kong_locker() -> Result<String, String> {
-    let caller = ic_cdk::caller();
-
-    if caller == Principal::anonymous() {
-        return Err("Authentication required".to_string());
-    }
-
-    KONG_LOCKER_PRINCIPALS.with(|principals| {
-        if principals
-            .borrow_mut()
-            .remove(&StorablePrincipal(caller))
-            .is_some()
-        {
-            Ok("Kong Locker canister unregistered successfully".to_string())
-        } else {
-            Err("No Kong Locker canister registered for your principal".to_string())
-        }
-    })
+pub async fn list_all_kong_locker_registrations() -> Result<Vec<(Principal, Principal)>, String> {
+    list_kong_locker_registrations().await
 }
 
 #[update]
@@ -74,9 +46,10 @@ pub async fn get_my_voting_power_for_token(token_canister_id: Principal) -> Resu
     }
 }
 
-/// Get all unique tokens that have locked liquidity (for public dashboard)
+/// Get the unique tokens that have locked liquidity (for public dashboard)
+/// Pages through the lock canisters `limit` (at most 50) at a time from `offset`
 /// This is an update call because it queries KongSwap for each lock canister
 #[update]
-pub async fn list_all_locked_tokens() -> Result<Vec<TokenInfo>, String> {
-    get_all_locked_tokens().await
+pub async fn list_all_locked_tokens(offset: u64, limit: u64) -> Result<LockedTokensPage, String> {
+    get_all_locked_tokens(offset, limit).await
 }
@@ -1,7 +1,9 @@
 mod address_book;
 mod agreement_snapshot;
+mod dividends;
 mod equity;
 mod governance_config;
+mod governance_profiles;
 mod kong_locker;
 mod orbit;
 mod orbit_accounts;
@@ -14,7 +16,10 @@ mod orbit_security;
 mod orbit_transfers;
 mod orbit_users;
 pub mod security;  // New security module
+mod security_audits;
+mod security_remediation;
 mod stations;
+mod system_upgrade;
 mod utils;
 
 pub use address_book::*;
@@ -24,6 +29,7 @@ pub use agreement_snapshot::{
     get_agreement_by_station,
 };
 pub use governance_config::*;
+pub use governance_profiles::*;
 pub use kong_locker::*;
 pub use orbit::*;
 pub use orbit_accounts::*;
@@ -31,6 +37,7 @@ pub use orbit_assets::{
     add_treasury_asset,
     edit_treasury_asset,
     remove_treasury_asset,
+    preview_treasury_asset,
     list_treasury_assets,
     AddAssetInput,
     AssetResponse,
@@ -40,7 +47,10 @@ pub use orbit_overview::*;
 pub use orbit_permissions::*;
 pub use orbit_requests::*;
 pub use orbit_security::*;
-pub use security::{SecurityCheck, EnhancedSecurityDashboard};
+pub use security_audits::*;
+pub use security_remediation::*;
+pub use system_upgrade::*;
+pub use security::{SecurityCheck, EnhancedSecurityDashboard, SecurityImpact};
 pub use orbit_transfers::{
     // ❌ REMOVED: approve_orbit_request - replaced by liquid democracy voting
     get_transfer_requests_from_orbit,
@@ -53,11 +63,13 @@ pub use orbit_transfers::{
     AssetWithBalance,
     AccountAssetInfo,
 };
+pub(crate) use orbit_transfers::asset_blockchain;
 pub use orbit_users::{
     create_remove_admin_request,
     create_remove_multiple_admins_request,
     list_orbit_users,
 };
+pub use dividends::*;
 pub use equity::*;
-pub use stations::link_orbit_station;
+pub use stations::{link_orbit_station, link_converted_station, unlink_converted_station};
 pub use utils::*;
@@ -1,86 +1,121 @@
+use crate::proposals::unified::{create_orbit_request_with_proposal, OrbitOperation};
 use crate::storage::state::TOKEN_ORBIT_STATIONS;
 use crate::types::orbit::*;
 use crate::types::StorablePrincipal;
-use candid::{CandidType, Deserialize, Principal};
+use candid::{CandidType, Deserialize, Nat, Principal};
 use ic_cdk::{call, update};
 use serde::Serialize;
 
-// Add asset input from frontend (uses shared AssetMetadata from orbit.rs)
+// Add asset input from frontend: everything else is read from the ledger
 #[derive(CandidType, Deserialize, Debug)]
 pub struct AddAssetInput {
-    pub blockchain: String,
-    pub standards: Vec<String>,
-    pub symbol: String,
-    pub name: String,
-    pub metadata: Vec<AssetMetadata>,
-    pub decimals: u32,
+    pub ledger_canister_id: Principal,
+    pub index_canister_id: Option<Principal>,
+    pub name: Option<String>, // Overrides the ledger's icrc1:name
 }
 
-// Add asset operation with governance
-#[update]
-pub async fn add_treasury_asset(
-    token_canister_id: Principal,
-    asset_input: AddAssetInput,
-) -> Result<String, String> {
-    // 1. Get station ID for token
-    let station_id = TOKEN_ORBIT_STATIONS
-        .with(|s| {
-            s.borrow()
-                .get(&StorablePrincipal(token_canister_id))
-                .map(|s| s.0)
-        })
-        .ok_or_else(|| "No Orbit Station found for token".to_string())?;
+// ICRC-1 metadata value (icrc1_metadata)
+#[derive(CandidType, Deserialize, Debug)]
+enum Icrc1MetadataValue {
+    Nat(Nat),
+    Int(candid::Int),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+struct SupportedStandard {
+    name: String,
+    url: String,
+}
+
+/// Build the Orbit asset for an ICRC-1 ledger from its own metadata
+async fn icrc1_asset(input: &AddAssetInput) -> Result<AddAssetOperationInput, String> {
+    let ledger = input.ledger_canister_id;
+
+    let (metadata,): (Vec<(String, Icrc1MetadataValue)>,) = call(ledger, "icrc1_metadata", ())
+        .await
+        .map_err(|(code, msg)| format!("Failed to read ledger metadata: {:?} - {}", code, msg))?;
+    let (supported,): (Vec<SupportedStandard>,) = call(ledger, "icrc1_supported_standards", ())
+        .await
+        .map_err(|(code, msg)| format!("Failed to read ledger standards: {:?} - {}", code, msg))?;
 
-    // 2. Build operation
-    let operation = RequestOperationInput::AddAsset(AddAssetOperationInput {
-        blockchain: asset_input.blockchain.clone(),
-        standards: asset_input.standards.clone(),
-        symbol: asset_input.symbol.clone(),
-        name: asset_input.name.clone(),
-        metadata: asset_input.metadata,
-        decimals: asset_input.decimals,
+    let text = |key: &str| metadata.iter().find_map(|(k, v)| match v {
+        Icrc1MetadataValue::Text(t) if k == key => Some(t.clone()),
+        _ => None,
+    });
+    let nat = |key: &str| metadata.iter().find_map(|(k, v)| match v {
+        Icrc1MetadataValue::Nat(n) if k == key => Some(n.clone()),
+        _ => None,
     });
 
-    // Create request input
-    let request_input = CreateRequestInput {
-        operation,
-        title: Some(format!("Add {} Asset", asset_input.symbol)),
-        summary: Some(format!(
-            "Add support for {} ({}) token on {} blockchain",
-            asset_input.name, asset_input.symbol, asset_input.blockchain
-        )),
-        execution_plan: Some(RequestExecutionSchedule::Immediate),
-        expiration_dt: None,
+    let symbol = text("icrc1:symbol").ok_or("Ledger metadata has no icrc1:symbol")?;
+    let decimals = nat("icrc1:decimals")
+        .and_then(|d| u32::try_from(d.0).ok())
+        .ok_or("Ledger metadata has no valid icrc1:decimals")?;
+    let name = match &input.name {
+        Some(name) => name.clone(),
+        None => text("icrc1:name").unwrap_or_else(|| symbol.clone()),
     };
 
-    // 3. Call Orbit Station to create request
-    let result: Result<(CreateRequestResult,), _> =
-        call(station_id, "create_request", (request_input,)).await;
+    // Orbit only knows the ICRC-1 standard for ledgers it reads through
+    if !supported.iter().any(|s| s.name == "ICRC-1") {
+        return Err(format!("{} does not support ICRC-1", ledger));
+    }
 
-    match result {
-        Ok((CreateRequestResult::Ok(response),)) => {
-            let request_id = response.request.id.clone();
+    let mut asset_metadata = vec![AssetMetadata {
+        key: "ledger_canister_id".to_string(),
+        value: ledger.to_text(),
+    }];
+    if let Some(index) = input.index_canister_id {
+        asset_metadata.push(AssetMetadata {
+            key: "index_canister_id".to_string(),
+            value: index.to_text(),
+        });
+    }
+    if let Some(fee) = nat("icrc1:fee") {
+        asset_metadata.push(AssetMetadata {
+            key: "fee".to_string(),
+            value: fee.0.to_string(),
+        });
+    }
 
-            // 4. CRITICAL: Auto-create proposal for governance
-            use crate::proposals::ensure_proposal_for_request;
+    Ok(AddAssetOperationInput {
+        blockchain: "icp".to_string(),
+        standards: vec!["icrc1".to_string()],
+        symbol,
+        name,
+        metadata: asset_metadata,
+        decimals,
+    })
+}
 
-            match ensure_proposal_for_request(
-                token_canister_id,
-                request_id.clone(),
-                "AddAsset".to_string(), // 40% threshold
-            )
-            .await
-            {
-                Ok(_proposal_id) => Ok(request_id),
-                Err(e) => Err(format!(
-                    "GOVERNANCE VIOLATION: Failed to create proposal for asset addition: {:?}",
-                    e
-                )),
-            }
-        }
-        Ok((CreateRequestResult::Err(err),)) => Err(format!("Orbit error: {}", err)),
-        Err((code, msg)) => Err(format!("Call failed: {:?} - {}", code, msg)),
-    }
+/// What `add_treasury_asset` would propose for a ledger, for review before proposing
+#[update]
+pub async fn preview_treasury_asset(input: AddAssetInput) -> Result<AssetResponse, String> {
+    let asset = icrc1_asset(&input).await?;
+    Ok(AssetResponse {
+        id: String::new(),
+        symbol: asset.symbol,
+        name: asset.name,
+        blockchain: asset.blockchain,
+        standards: asset.standards,
+        decimals: asset.decimals,
+        metadata: asset.metadata,
+    })
+}
+
+// Add asset operation with governance
+#[update]
+pub async fn add_treasury_asset(
+    token_canister_id: Principal,
+    asset_input: AddAssetInput,
+) -> Result<String, String> {
+    let asset = icrc1_asset(&asset_input).await?;
+
+    create_orbit_request_with_proposal(token_canister_id, OrbitOperation::AddAsset(asset))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // Edit asset operation with governance
@@ -91,56 +126,18 @@ pub async fn edit_treasury_asset(
     name: Option<String>,
     metadata: Option<Vec<AssetMetadata>>,
 ) -> Result<String, String> {
-    let station_id = TOKEN_ORBIT_STATIONS
-        .with(|s| {
-            s.borrow()
-                .get(&StorablePrincipal(token_canister_id))
-                .map(|s| s.0)
-        })
-        .ok_or_else(|| "No Orbit Station found for token".to_string())?;
-
-    let change_metadata = metadata.map(|m| ChangeMetadata::ReplaceAllBy(m));
-
-    let operation = RequestOperationInput::EditAsset(EditAssetOperationInput {
-        asset_id: asset_id.clone(),
-        name: name.clone(),
+    let operation = OrbitOperation::EditAsset(EditAssetOperationInput {
+        asset_id,
+        name,
         blockchain: None,
         standards: None,
         symbol: None,
-        change_metadata,
+        change_metadata: metadata.map(ChangeMetadata::ReplaceAllBy),
     });
 
-    let request_input = CreateRequestInput {
-        operation,
-        title: Some(format!("Edit Asset {}", asset_id)),
-        summary: Some("Update asset metadata".to_string()),
-        execution_plan: Some(RequestExecutionSchedule::Immediate),
-        expiration_dt: None,
-    };
-
-    let result: Result<(CreateRequestResult,), _> =
-        call(station_id, "create_request", (request_input,)).await;
-
-    match result {
-        Ok((CreateRequestResult::Ok(response),)) => {
-            let request_id = response.request.id.clone();
-
-            use crate::proposals::ensure_proposal_for_request;
-
-            match ensure_proposal_for_request(
-                token_canister_id,
-                request_id.clone(),
-                "EditAsset".to_string(),
-            )
-            .await
-            {
-                Ok(_) => Ok(request_id),
-                Err(e) => Err(format!("GOVERNANCE VIOLATION: {:?}", e)),
-            }
-        }
-        Ok((CreateRequestResult::Err(err),)) => Err(format!("Orbit error: {}", err)),
-        Err((code, msg)) => Err(format!("Call failed: {:?} - {}", code, msg)),
-    }
+    create_orbit_request_with_proposal(token_canister_id, operation)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // Remove asset operation with governance
@@ -149,49 +146,11 @@ pub async fn remove_treasury_asset(
     token_canister_id: Principal,
     asset_id: String,
 ) -> Result<String, String> {
-    let station_id = TOKEN_ORBIT_STATIONS
-        .with(|s| {
-            s.borrow()
-                .get(&StorablePrincipal(token_canister_id))
-                .map(|s| s.0)
-        })
-        .ok_or_else(|| "No Orbit Station found for token".to_string())?;
-
-    let operation = RequestOperationInput::RemoveAsset(RemoveAssetOperationInput {
-        asset_id: asset_id.clone(),
-    });
-
-    let request_input = CreateRequestInput {
-        operation,
-        title: Some(format!("Remove Asset {}", asset_id)),
-        summary: Some("Remove asset from treasury".to_string()),
-        execution_plan: Some(RequestExecutionSchedule::Immediate),
-        expiration_dt: None,
-    };
+    let operation = OrbitOperation::RemoveAsset(RemoveAssetOperationInput { asset_id });
 
-    let result: Result<(CreateRequestResult,), _> =
-        call(station_id, "create_request", (request_input,)).await;
-
-    match result {
-        Ok((CreateRequestResult::Ok(response),)) => {
-            let request_id = response.request.id.clone();
-
-            use crate::proposals::ensure_proposal_for_request;
-
-            match ensure_proposal_for_request(
-                token_canister_id,
-                request_id.clone(),
-                "RemoveAsset".to_string(),
-            )
-            .await
-            {
-                Ok(_) => Ok(request_id),
-                Err(e) => Err(format!("GOVERNANCE VIOLATION: {:?}", e)),
-            }
-        }
-        Ok((CreateRequestResult::Err(err),)) => Err(format!("Orbit error: {}", err)),
-        Err((code, msg)) => Err(format!("Call failed: {:?} - {}", code, msg)),
-    }
+    create_orbit_request_with_proposal(token_canister_id, operation)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // List all treasury assets (query through backend as admin proxy)
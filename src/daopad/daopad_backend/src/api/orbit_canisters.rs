@@ -1,13 +1,15 @@
-// ⚠️ IMPORTANT: Direct canister operations have been disabled to enforce proposal-based governance.
-// All canister management operations (create, change, configure, fund, etc.) must now go through
-// the proposal voting system to prevent admin bypass of community governance.
+// Canister management operations (create, change, configure, fund, etc.) go through the
+// proposal voting system: each endpoint creates the Orbit request and its admin proposal
+// together, so admins can't bypass community governance.
 // Required voting power: 25,000 VP minimum for canister operations (higher due to risk).
 
 use candid::Principal;
 use ic_cdk::{api::call::CallResult, call};
 
 use crate::api::orbit::get_station_id_for_token_or_equity;
+use crate::proposals::unified::{create_governed_request, OrbitOperation};
 use crate::types::orbit::{
+    CallExternalCanisterOperationInput, CanisterMethod,
     ChangeExternalCanisterOperationInput, ConfigureExternalCanisterOperationInput,
     CreateExternalCanisterOperationInput, ExternalCanisterCallerMethodCallInput,
     FundExternalCanisterOperationInput, GetExternalCanisterResult,
@@ -16,7 +18,6 @@ use crate::types::orbit::{
     ListExternalCanistersInputMinimal,
     MonitorExternalCanisterOperationInput, PruneExternalCanisterOperationInput,
     RestoreExternalCanisterOperationInput, SnapshotExternalCanisterOperationInput,
-    SubmitRequestResult,
 };
 
 // ===== LIST CANISTERS =====
@@ -71,145 +72,139 @@ async fn get_orbit_canister(
 
 #[ic_cdk::update]
 async fn create_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: CreateExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister creation is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for canister creation. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: CreateExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::CreateExternalCanister(config), title, summary).await
 }
 
 // ===== CHANGE CANISTER (Upgrade, Settings, etc.) =====
 
 #[ic_cdk::update]
 async fn change_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: ChangeExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister changes are disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for canister changes. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: ChangeExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::ChangeExternalCanister(config), title, summary).await
 }
 
 // ===== CONFIGURE CANISTER (Permissions, Policies) =====
 
 #[ic_cdk::update]
 async fn configure_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: ConfigureExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister configuration is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for canister configuration. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: ConfigureExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::ConfigureExternalCanister(config), title, summary).await
 }
 
 // ===== CALL CANISTER METHOD =====
 
 #[ic_cdk::update]
 async fn call_orbit_canister_method_request(
-    _token_canister_id: Principal,
-    _external_canister_id: String,
-    _method_call: ExternalCanisterCallerMethodCallInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister method calls are disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for canister method calls. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    external_canister_id: Principal,
+    method_call: ExternalCanisterCallerMethodCallInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    let execution_method_cycles = method_call.cycles
+        .map(|cycles| u64::try_from(cycles.0).map_err(|_| "Cycles amount is too large".to_string()))
+        .transpose()?;
+
+    let input = CallExternalCanisterOperationInput {
+        validation_method: method_call.validation_method.map(|v| CanisterMethod {
+            canister_id: external_canister_id,
+            method_name: v.method_name,
+        }),
+        execution_method: CanisterMethod {
+            canister_id: external_canister_id,
+            method_name: method_call.method_name,
+        },
+        arg: method_call.arg,
+        execution_method_cycles,
+    };
+
+    propose(token_canister_id, OrbitOperation::CallExternalCanister(input), title, summary).await
 }
 
 // ===== FUND CANISTER (Add Cycles) =====
 
 #[ic_cdk::update]
 async fn fund_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: FundExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister funding is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for canister funding. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: FundExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::FundExternalCanister(config), title, summary).await
 }
 
 // ===== MONITOR CANISTER (Auto-funding) =====
 
 #[ic_cdk::update]
 async fn monitor_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: MonitorExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister monitoring setup is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for monitoring setup. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: MonitorExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::MonitorExternalCanister(config), title, summary).await
 }
 
 // ===== SNAPSHOT CANISTER =====
 
 #[ic_cdk::update]
 async fn snapshot_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: SnapshotExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister snapshots are disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for taking snapshots. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: SnapshotExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::SnapshotExternalCanister(config), title, summary).await
 }
 
 // ===== RESTORE FROM SNAPSHOT =====
 
 #[ic_cdk::update]
 async fn restore_orbit_canister_request(
-    _token_canister_id: Principal,
-    _config: RestoreExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct canister restore is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for restoring from snapshots. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: RestoreExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::RestoreExternalCanister(config), title, summary).await
 }
 
 // ===== PRUNE SNAPSHOTS =====
 
 #[ic_cdk::update]
 async fn prune_orbit_canister_snapshots_request(
-    _token_canister_id: Principal,
-    _config: PruneExternalCanisterOperationInput,
-    _title: String,
-    _summary: Option<String>,
-) -> Result<SubmitRequestResult, String> {
-    Err(
-        "Direct snapshot pruning is disabled. Canister operations must go through the proposal system. \
-        Please create a governance proposal for pruning snapshots. \
-        Required voting power: 25,000 VP minimum.".to_string()
-    )
+    token_canister_id: Principal,
+    config: PruneExternalCanisterOperationInput,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    propose(token_canister_id, OrbitOperation::PruneExternalCanister(config), title, summary).await
+}
+
+/// Create the Orbit request and its admin proposal together
+/// Needs 25,000 VP; upgrades and calls show their wasm and argument hashes to voters
+async fn propose(
+    token_canister_id: Principal,
+    operation: OrbitOperation,
+    title: String,
+    summary: Option<String>,
+) -> Result<String, String> {
+    create_governed_request(token_canister_id, operation, Some(title), summary, None)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // ===== GET CANISTER STATUS (from IC management canister) =====
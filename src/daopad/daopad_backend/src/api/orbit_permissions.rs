@@ -1,15 +1,18 @@
-use candid::Principal;
+use candid::{CandidType, Deserialize, Principal};
+use crate::proposals::unified::{
+    create_governed_request, create_orbit_request_with_proposal, OrbitOperation,
+};
 use crate::types::orbit::{
     ListPermissionsInput, ListPermissionsResult,
     GetPermissionInput,
     Permission, Resource, AuthScope, UUID,
     EditPermissionOperationInput,
-    RequestOperationInput,
-    CreateRequestInput, CreateRequestResult,
+    AddRequestPolicyOperationInput, EditRequestPolicyOperationInput,
+    RemoveRequestPolicyOperationInput,
     ListUserGroupsInput, ListUserGroupsResult,
     UserGroup,
 };
-use crate::storage::state::TOKEN_ORBIT_STATIONS;
+use crate::storage::state::{STATION_TO_TOKEN, TOKEN_ORBIT_STATIONS};
 use crate::types::StorablePrincipal;
 
 /// List all permissions for a station (admin proxy)
@@ -61,9 +64,8 @@ pub async fn get_station_permission(
 
 /// Create edit permission request
 ///
-/// Creates a request in Orbit Station to modify a permission's access control.
-/// Note: This creates a REQUEST, not an immediate change. The request must be
-/// approved according to the station's request policies.
+/// Creates a request in Orbit Station to modify a permission's access control,
+/// together with its community proposal (70% of voting power to pass).
 ///
 /// Returns the request ID if successful.
 #[ic_cdk::update]
@@ -74,63 +76,23 @@ pub async fn create_edit_permission_request(
     users: Option<Vec<UUID>>,
     user_groups: Option<Vec<UUID>>
 ) -> Result<String, String> {
-    // Construct the EditPermission operation
-    let edit_perm_input = EditPermissionOperationInput {
+    // Proposals are per token, so find the token this station belongs to
+    let token_canister_id = STATION_TO_TOKEN.with(|s2t| {
+        s2t.borrow()
+            .get(&StorablePrincipal(station_id))
+            .map(|sp| sp.0)
+    }).ok_or("Station not linked to any token")?;
+
+    let operation = OrbitOperation::EditPermission(EditPermissionOperationInput {
         resource,
         auth_scope,
         users,
         user_groups,
-    };
+    });
 
-    // Construct the create request input
-    let create_input = CreateRequestInput {
-        operation: RequestOperationInput::EditPermission(edit_perm_input),
-        title: Some("Edit Permission Request".to_string()),
-        summary: None,
-        execution_plan: None,
-        expiration_dt: None,
-    };
-
-    // Submit the request to Orbit Station
-    let result: (CreateRequestResult,) = ic_cdk::call(
-        station_id,
-        "create_request",
-        (create_input,)
-    )
-    .await
-    .map_err(|e| format!("Failed to create permission edit request: {:?}", e))?;
-
-    // Extract request ID from result
-    match result.0 {
-        CreateRequestResult::Ok(response) => {
-            let request_id = response.request.id;
-
-            // CRITICAL: Auto-create proposal for governance
-            // Note: For permission edits, we need to determine which token this station belongs to
-            // Since we're called with station_id, we need to find the corresponding token
-            use crate::storage::state::STATION_TO_TOKEN;
-
-            let token_canister_id = STATION_TO_TOKEN.with(|s2t| {
-                s2t.borrow()
-                    .get(&StorablePrincipal(station_id))
-                    .map(|sp| sp.0)
-            }).ok_or("Station not linked to any token")?;
-
-            use crate::proposals::ensure_proposal_for_request;
-
-            match ensure_proposal_for_request(
-                token_canister_id,
-                request_id.clone(),
-                "EditPermission".to_string(),  // 70% threshold
-            ).await {
-                Ok(_proposal_id) => Ok(request_id),
-                Err(e) => Err(format!("GOVERNANCE VIOLATION: Created Orbit request but failed to create proposal: {:?}", e))
-            }
-        }
-        CreateRequestResult::Err(e) => {
-            Err(format!("Orbit returned error: {:?}", e))
-        }
-    }
+    create_orbit_request_with_proposal(token_canister_id, operation)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Remove dangerous permission from Operator group
@@ -162,52 +124,48 @@ pub async fn remove_permission_from_operator_group(
         .collect();
 
     // Create edit request with filtered groups (keep auth_scope and users unchanged)
-    let edit_perm_input = EditPermissionOperationInput {
+    let operation = OrbitOperation::EditPermission(EditPermissionOperationInput {
         resource,
         auth_scope: Some(current.allow.auth_scope),
         users: Some(current.allow.users),
         user_groups: Some(filtered_groups),
-    };
-
-    // Construct the create request input
-    let create_input = CreateRequestInput {
-        operation: RequestOperationInput::EditPermission(edit_perm_input),
-        title: Some(format!("Remove dangerous permissions from Operator group")),
-        summary: Some(format!("Security fix: Removing Operator group from permission to prevent unauthorized treasury operations")),
-        execution_plan: None,
-        expiration_dt: None,
-    };
-
-    // Submit the request to Orbit Station
-    let result: (CreateRequestResult,) = ic_cdk::call(
-        station_id,
-        "create_request",
-        (create_input,)
+    });
+
+    create_governed_request(
+        token_canister_id,
+        operation,
+        Some("Remove dangerous permissions from Operator group".to_string()),
+        Some("Security fix: Removing Operator group from permission to prevent unauthorized treasury operations".to_string()),
+        None,
     )
     .await
-    .map_err(|e| format!("Failed to create permission removal request: {:?}", e))?;
+    .map_err(|e| e.to_string())
+}
 
-    // Extract request ID from result and create proposal
-    match result.0 {
-        CreateRequestResult::Ok(response) => {
-            let request_id = response.request.id;
-
-            // CRITICAL: Auto-create proposal for governance
-            use crate::proposals::ensure_proposal_for_request;
-
-            match ensure_proposal_for_request(
-                token_canister_id,
-                request_id.clone(),
-                "EditPermission".to_string(),  // 70% threshold
-            ).await {
-                Ok(_proposal_id) => Ok(request_id),
-                Err(e) => Err(format!("GOVERNANCE VIOLATION: Created Orbit request but failed to create proposal: {:?}", e))
-            }
-        }
-        CreateRequestResult::Err(e) => {
-            Err(format!("Orbit returned error: {:?}", e))
-        }
-    }
+// Governed request policy changes
+#[derive(CandidType, Deserialize, Debug)]
+pub enum RequestPolicyOperationInput {
+    AddRequestPolicy(AddRequestPolicyOperationInput),
+    EditRequestPolicy(EditRequestPolicyOperationInput),
+    RemoveRequestPolicy(RemoveRequestPolicyOperationInput),
+}
+
+/// Propose adding, editing or removing a request policy; returns the Orbit request ID
+/// Changes pass at the governance threshold (70% of voting power)
+#[ic_cdk::update]
+pub async fn create_request_policy_request(
+    token_canister_id: Principal,
+    operation: RequestPolicyOperationInput,
+) -> Result<String, String> {
+    let operation = match operation {
+        RequestPolicyOperationInput::AddRequestPolicy(input) => OrbitOperation::AddRequestPolicy(input),
+        RequestPolicyOperationInput::EditRequestPolicy(input) => OrbitOperation::EditRequestPolicy(input),
+        RequestPolicyOperationInput::RemoveRequestPolicy(input) => OrbitOperation::RemoveRequestPolicy(input),
+    };
+
+    create_orbit_request_with_proposal(token_canister_id, operation)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// List all user groups in a station (admin proxy)
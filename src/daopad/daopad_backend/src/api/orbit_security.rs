@@ -3,10 +3,11 @@ use ic_cdk;
 
 // Import all submodules from parent directory
 use super::security::{
-    admin_checks, governance_checks, treasury_checks, system_checks, security_utils,
-    SecurityCheck, EnhancedSecurityDashboard, create_error_check, Severity,
+    admin_checks, governance_checks, treasury_checks, system_checks, security_utils, simulation,
+    SecurityCheck, EnhancedSecurityDashboard, SecurityImpact,
     build_dashboard,
 };
+use crate::proposals::unified::OrbitOperation;
 
 // ===== API ENDPOINTS =====
 
@@ -93,7 +94,14 @@ pub async fn check_system_restore(station_id: Principal) -> Result<Vec<SecurityC
 pub async fn check_addressbook_injection(station_id: Principal) -> Result<Vec<SecurityCheck>, String> {
     let perms_data = governance_checks::fetch_permissions(station_id).await?;
     let policies_data = governance_checks::fetch_policies(station_id).await?;
-    Ok(governance_checks::check_addressbook_injection_impl(&perms_data.permissions, &policies_data, &perms_data.user_groups))
+    Ok(governance_checks::check_addressbook_injection_impl(&perms_data.permissions, &policies_data, &perms_data.user_groups, transfer_allowlist_enabled(station_id)))
+}
+
+/// DAOPad's own allowlist for treasury transfers relies on the address book too
+pub(crate) fn transfer_allowlist_enabled(station_id: Principal) -> bool {
+    crate::api::get_token_for_station(station_id)
+        .map(crate::api::unlisted_transfer_policy)
+        .is_some_and(|policy| policy != crate::types::UnlistedTransferPolicy::Allow)
 }
 
 /// Check monitoring cycle drain
@@ -131,134 +139,11 @@ pub async fn check_account_autoapproved_status(station_id: Principal) -> Result<
     treasury_checks::check_account_autoapproved_status(station_id).await
 }
 
-/// Comprehensive check: runs all individual security checks and returns a combined result
+/// Comprehensive check: fetches the station's data once and runs every security check on it
 #[ic_cdk::update]
 pub async fn perform_all_security_checks(station_id: Principal) -> Result<Vec<SecurityCheck>, String> {
-    // Call all 17 individual checks sequentially
-    let mut all_checks = Vec::new();
-
-    // Existing 8 checks
-    match check_admin_control(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Admin Control", "Admin Control", Severity::Critical, e
-        )),
-    }
-
-    match check_treasury_control(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Treasury Control", "Treasury Control", Severity::Critical, e
-        )),
-    }
-
-    match check_governance_permissions(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Governance Control", "Governance Control", Severity::Critical, e
-        )),
-    }
-
-    match check_proposal_policies(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Request Policies", "Request Policies", Severity::High, e
-        )),
-    }
-
-    match check_external_canisters(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "External Canister Control", "External Canister Control", Severity::Medium, e
-        )),
-    }
-
-    match check_asset_management(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Asset Management", "Asset Management", Severity::Medium, e
-        )),
-    }
-
-    match check_system_configuration(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "System Configuration", "System Configuration", Severity::High, e
-        )),
-    }
-
-    match check_operational_permissions(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Operational Access", "Operational Access", Severity::Low, e
-        )),
-    }
-
-    // Additional 9 bypass detection checks
-    match check_controller_manipulation(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Controller Manipulation", "Controller Manipulation", Severity::Critical, e
-        )),
-    }
-
-    match check_external_canister_calls(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "External Canister Calls", "External Canister Calls", Severity::Critical, e
-        )),
-    }
-
-    match check_system_restore(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "System Restore", "System Restore", Severity::Critical, e
-        )),
-    }
-
-    match check_addressbook_injection(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "AddressBook Injection", "AddressBook Injection", Severity::High, e
-        )),
-    }
-
-    match check_monitoring_drain(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Monitoring Cycle Drain", "Monitoring Cycle Drain", Severity::High, e
-        )),
-    }
-
-    match check_snapshot_operations(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Snapshot Operations", "Snapshot Operations", Severity::Medium, e
-        )),
-    }
-
-    match check_named_rule_bypass(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Named Rule Bypass", "Named Rule Bypass", Severity::Medium, e
-        )),
-    }
-
-    match check_remove_operations(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Remove Operations", "Remove Operations", Severity::Medium, e
-        )),
-    }
-
-    match check_account_autoapproved_status(station_id).await {
-        Ok(checks) => all_checks.extend(checks),
-        Err(ref e) => all_checks.push(create_error_check(
-            "Treasury Setup", "Account AutoApproved Status", Severity::Critical, e
-        )),
-    }
-
-    Ok(all_checks)
+    let data = simulation::fetch_station_data(station_id).await?;
+    Ok(data.run_checks())
 }
 
 /// Perform comprehensive security analysis and return dashboard with score
@@ -272,6 +157,19 @@ pub async fn perform_security_check(station_id: Principal) -> Result<EnhancedSec
     build_dashboard(station_id, checks)
 }
 
+/// Preview how a permission or policy change would move the station's dashboard
+/// The change is applied to the fetched station data in memory only
+#[ic_cdk::update]
+pub async fn preview_security_impact(
+    station_id: Principal,
+    operation: OrbitOperation,
+) -> Result<SecurityImpact, String> {
+    if !matches!(operation, OrbitOperation::EditPermission(_) | OrbitOperation::EditRequestPolicy(_)) {
+        return Err("Only EditPermission and EditRequestPolicy can be simulated".to_string());
+    }
+    simulation::simulate_security_impact(station_id, &operation).await
+}
+
 // ===== REQUEST POLICIES DETAILS ENDPOINT =====
 
 use crate::types::orbit::{
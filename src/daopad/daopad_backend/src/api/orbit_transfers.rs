@@ -151,6 +151,21 @@ pub async fn get_account_with_assets(
     }
 }
 
+/// Blockchain an asset lives on ("icp", "eth", ...)
+pub(crate) async fn asset_blockchain(station_id: Principal, asset_id: &str) -> Result<String, String> {
+    let result: Result<(GetAssetResult,), _> = ic_cdk::call(
+        station_id,
+        "get_asset",
+        (GetAssetInput { asset_id: asset_id.to_string() },)
+    ).await;
+
+    match result {
+        Ok((GetAssetResult::Ok { asset },)) => Ok(asset.blockchain),
+        Ok((GetAssetResult::Err(e),)) => Err(format!("Failed to get asset: {:?}", e)),
+        Err(e) => Err(format!("Call failed: {:?}", e)),
+    }
+}
+
 // List all available assets in a station
 #[ic_cdk::update]
 pub async fn list_station_assets(
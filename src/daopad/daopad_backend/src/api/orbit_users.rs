@@ -103,9 +103,9 @@ pub async fn create_remove_admin_request(
 
             // CRITICAL: Auto-create DAOPad proposal for community voting
             // This ensures ALL admin removal requests go through governance
-            use crate::proposals::ensure_proposal_for_request;
+            use crate::proposals::ensure_typed_proposal;
 
-            match ensure_proposal_for_request(
+            match ensure_typed_proposal(
                 token_canister_id,
                 request_id.clone(),
                 "EditUser".to_string(),
@@ -241,7 +241,8 @@ pub fn check_proposal_policies_impl(policies: &Vec<RequestPolicy>) -> Vec<Securi
 pub fn check_addressbook_injection_impl(
     permissions: &Vec<Permission>,
     policies: &Vec<RequestPolicy>,
-    user_groups: &Vec<UserGroup>
+    user_groups: &Vec<UserGroup>,
+    transfer_allowlist: bool, // DAOPad rejects or raises the bar for transfers outside the address book
 ) -> Vec<SecurityCheck> {
     let mut checks = Vec::new();
 
@@ -250,6 +251,19 @@ pub fn check_addressbook_injection_impl(
         policy_uses_allowlisted_metadata(&policy.rule)
     });
 
+    if transfer_allowlist {
+        checks.push(check_permission_by_resource(
+            permissions,
+            user_groups,
+            "AddressBook Injection",
+            "AddressBook Creation with Transfer Allowlist",
+            |resource| matches!(resource, Resource::AddressBook(ResourceAction::Create)),
+            Severity::High,
+            "Non-admin groups can add address book entries directly - an unlisted destination can be listed without a community vote",
+            "Restrict AddressBook.Create to Admin so new entries go through address book proposals",
+        ));
+    }
+
     if !has_allowlisted_policies {
         // No AllowListed policies, so this attack vector doesn't apply
         checks.push(SecurityCheck {
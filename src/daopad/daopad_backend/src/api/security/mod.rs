@@ -4,11 +4,12 @@ pub mod governance_checks;
 pub mod treasury_checks;
 pub mod system_checks;
 pub mod security_utils;
+pub mod simulation;
 
 // Re-export commonly used types
 pub use security_utils::{
     SecurityCheck, CheckStatus, Severity, EnhancedSecurityDashboard,
-    RelatedPermission, RiskWeights,
-    calculate_risk_score, build_dashboard, create_error_check,
+    RelatedPermission, RiskWeights, CheckChange, SecurityImpact,
+    calculate_risk_score, build_dashboard, create_error_check, diff_dashboards,
     analyze_policy_rule, format_request_specifier,
 };
\ No newline at end of file
@@ -8,7 +8,7 @@ use crate::types::orbit::{
 
 // ===== TYPE DEFINITIONS =====
 
-#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub enum CheckStatus {
     Pass,
     Warn,
@@ -16,7 +16,7 @@ pub enum CheckStatus {
     Error,
 }
 
-#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub enum Severity {
     None,
     Low,
@@ -56,6 +56,28 @@ pub struct EnhancedSecurityDashboard {
     pub recommended_actions: Vec<String>,
 }
 
+/// How one check's result differs between two dashboards
+/// A status is None when the check only appears on one side
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct CheckChange {
+    pub category: String,
+    pub name: String,
+    pub severity: String,
+    pub status_before: Option<String>,
+    pub status_after: Option<String>,
+    pub message: String, // From the dashboard after the change
+}
+
+/// Before/after diff of a station's dashboard for a pending change
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct SecurityImpact {
+    pub score_before: u8,
+    pub score_after: u8,
+    pub overall_status_before: String,
+    pub overall_status_after: String,
+    pub changed_checks: Vec<CheckChange>,
+}
+
 // Helper struct for risk analysis
 pub struct RiskWeights {
     pub critical_admin_control: f64,
@@ -460,4 +482,48 @@ pub fn build_dashboard(station_id: Principal, checks: Vec<SecurityCheck>) -> Res
         critical_issues,
         recommended_actions,
     })
-}
\ No newline at end of file
+}
+
+pub fn diff_dashboards(before: &EnhancedSecurityDashboard, after: &EnhancedSecurityDashboard) -> SecurityImpact {
+    let status = |check: &SecurityCheck| format!("{:?}", check.status);
+    let find = |dashboard: &EnhancedSecurityDashboard, check: &SecurityCheck| {
+        dashboard.checks.iter()
+            .find(|c| c.category == check.category && c.name == check.name)
+            .map(status)
+    };
+
+    let mut changed_checks = Vec::new();
+    for check in &after.checks {
+        let status_before = find(before, check);
+        if status_before.as_deref() != Some(status(check).as_str()) {
+            changed_checks.push(CheckChange {
+                category: check.category.clone(),
+                name: check.name.clone(),
+                severity: format!("{:?}", check.severity.clone().unwrap_or(Severity::None)),
+                status_before,
+                status_after: Some(status(check)),
+                message: check.message.clone(),
+            });
+        }
+    }
+    for check in &before.checks {
+        if find(after, check).is_none() {
+            changed_checks.push(CheckChange {
+                category: check.category.clone(),
+                name: check.name.clone(),
+                severity: format!("{:?}", check.severity.clone().unwrap_or(Severity::None)),
+                status_before: Some(status(check)),
+                status_after: None,
+                message: "Check no longer applies".to_string(),
+            });
+        }
+    }
+
+    SecurityImpact {
+        score_before: before.decentralization_score,
+        score_after: after.decentralization_score,
+        overall_status_before: before.overall_status.clone(),
+        overall_status_after: after.overall_status.clone(),
+        changed_checks,
+    }
+}
@@ -0,0 +1,145 @@
+use super::admin_checks::{self, fetch_users};
+use super::governance_checks::{self, fetch_permissions, fetch_policies};
+use super::security_utils::{build_dashboard, diff_dashboards, SecurityCheck, SecurityImpact};
+use super::system_checks::{self, fetch_system_info};
+use super::treasury_checks::{self, fetch_accounts};
+use crate::api::orbit_security::transfer_allowlist_enabled;
+use crate::proposals::unified::OrbitOperation;
+use crate::types::orbit::{
+    AccountMinimal, Permission, RequestPolicy, SystemInfoMinimal, UserDTO, UserGroup,
+};
+use candid::Principal;
+
+const OPERATOR_GROUP_ID: &str = "00000000-0000-4000-8000-000000000001";
+
+// ===== DATA STRUCTURES =====
+
+/// Everything the security checks look at, fetched once so checks can be
+/// re-run against a modified copy without calling the station again
+pub struct StationSecurityData {
+    pub permissions: Vec<Permission>,
+    pub user_groups: Vec<UserGroup>,
+    pub policies: Vec<RequestPolicy>,
+    pub users: Vec<UserDTO>,
+    pub system: SystemInfoMinimal,
+    pub accounts: Vec<AccountMinimal>,
+    pub transfer_allowlist: bool,
+    pub backend_principal: Principal,
+}
+
+// ===== DATA FETCHING =====
+
+pub async fn fetch_station_data(station_id: Principal) -> Result<StationSecurityData, String> {
+    let perms_data = fetch_permissions(station_id).await?;
+    Ok(StationSecurityData {
+        permissions: perms_data.permissions,
+        user_groups: perms_data.user_groups,
+        policies: fetch_policies(station_id).await?,
+        users: fetch_users(station_id).await?,
+        system: fetch_system_info(station_id).await?,
+        accounts: fetch_accounts(station_id).await?,
+        transfer_allowlist: transfer_allowlist_enabled(station_id),
+        backend_principal: ic_cdk::id(),
+    })
+}
+
+impl StationSecurityData {
+    /// Every security check, in dashboard order
+    pub fn run_checks(&self) -> Vec<SecurityCheck> {
+        let perms = &self.permissions;
+        let groups = &self.user_groups;
+        let mut checks = admin_checks::check_admin_control_layer(&self.users, self.backend_principal);
+        checks.extend(treasury_checks::check_treasury_control_impl(perms, groups));
+        checks.extend(governance_checks::check_governance_permissions_impl(perms, groups));
+        checks.extend(governance_checks::check_proposal_policies_impl(&self.policies));
+        checks.extend(system_checks::check_external_canister_control_impl(perms, groups));
+        checks.extend(treasury_checks::check_asset_management_impl(perms, groups));
+        checks.extend(system_checks::check_system_configuration_impl(&self.system, perms, groups));
+        checks.extend(system_checks::check_operational_permissions_impl(perms));
+        checks.extend(system_checks::check_controller_manipulation_impl(perms, groups));
+        checks.extend(system_checks::check_external_canister_calls_impl(perms, groups));
+        checks.extend(system_checks::check_system_restore_impl(perms, groups));
+        checks.extend(governance_checks::check_addressbook_injection_impl(
+            perms, &self.policies, groups, self.transfer_allowlist,
+        ));
+        checks.extend(governance_checks::check_monitoring_drain_impl(perms, groups));
+        checks.extend(governance_checks::check_snapshot_operations_impl(perms, groups));
+        checks.extend(governance_checks::check_named_rule_bypass_impl(perms, &self.policies, groups));
+        checks.extend(governance_checks::check_remove_operations_impl(perms, groups));
+        checks.extend(treasury_checks::check_account_autoapproved_impl(&self.accounts));
+        checks
+    }
+
+    /// Apply an operation as Orbit would once the request executes
+    /// Operations the checks don't look at leave the data unchanged
+    pub fn apply(&mut self, operation: &OrbitOperation) {
+        match operation {
+            OrbitOperation::EditPermission(input) => {
+                let Some(permission) = self.permissions.iter_mut().find(|p| p.resource == input.resource) else {
+                    return;
+                };
+                if let Some(auth_scope) = &input.auth_scope {
+                    permission.allow.auth_scope = auth_scope.clone();
+                }
+                if let Some(users) = &input.users {
+                    permission.allow.users = users.clone();
+                }
+                if let Some(user_groups) = &input.user_groups {
+                    permission.allow.user_groups = user_groups.clone();
+                }
+            }
+            OrbitOperation::EditRequestPolicy(input) => {
+                let Some(policy) = self.policies.iter_mut().find(|p| p.id == input.policy_id) else {
+                    return;
+                };
+                if let Some(specifier) = &input.specifier {
+                    policy.specifier = specifier.clone();
+                }
+                if let Some(rule) = &input.rule {
+                    policy.rule = rule.clone();
+                }
+            }
+            OrbitOperation::RemoveAdmin { user_id, .. } => {
+                self.set_user_groups(user_id, &[OPERATOR_GROUP_ID.to_string()]);
+            }
+            OrbitOperation::EditUser(input) => {
+                if let Some(groups) = &input.groups {
+                    self.set_user_groups(&input.id, groups);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_user_groups(&mut self, user_id: &str, group_ids: &[String]) {
+        let Some(user) = self.users.iter_mut().find(|u| u.id == user_id) else {
+            return;
+        };
+        user.groups = group_ids
+            .iter()
+            .map(|id| UserGroup {
+                id: id.clone(),
+                name: self
+                    .user_groups
+                    .iter()
+                    .find(|g| &g.id == id)
+                    .map(|g| g.name.clone())
+                    .unwrap_or_else(|| id.clone()),
+            })
+            .collect();
+    }
+}
+
+// ===== IMPACT SIMULATION =====
+
+/// Dashboard diff for a station once the operation executes
+pub async fn simulate_security_impact(
+    station_id: Principal,
+    operation: &OrbitOperation,
+) -> Result<SecurityImpact, String> {
+    let mut data = fetch_station_data(station_id).await?;
+    let before = build_dashboard(station_id, data.run_checks())?;
+    data.apply(operation);
+    let after = build_dashboard(station_id, data.run_checks())?;
+    Ok(diff_dashboards(&before, &after))
+}
@@ -0,0 +1,241 @@
+// Scheduled security audits
+//
+// A timer audits every linked Orbit Station once a day (and once right after
+// install or upgrade) and keeps the score and failing checks of each audit for
+// a year. An audit that scores lower than the previous one, or finds Critical
+// checks the previous one didn't, raises an alert, kept for as long.
+
+use crate::api::security::simulation::fetch_station_data;
+use crate::api::security::{build_dashboard, CheckStatus, EnhancedSecurityDashboard, Severity};
+use crate::storage::state::{SECURITY_ALERTS, SECURITY_HISTORY, TOKEN_ORBIT_STATIONS};
+use crate::types::{FailingCheck, SecurityAlert, SecurityAuditRecord, StorablePrincipal};
+use candid::Principal;
+use ic_cdk::query;
+use ic_cdk_timers::{set_timer, set_timer_interval};
+use std::cell::Cell;
+use std::ops::Bound;
+use std::time::Duration;
+
+const AUDIT_INTERVAL_SECS: u64 = 24 * 60 * 60;
+const HISTORY_RETENTION_NANOS: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+const MAX_ALERTS_RETURNED: usize = 100;
+
+thread_local! {
+    // Guards against overlapping runs while a pass is still awaiting stations
+    static AUDIT_IN_PROGRESS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Start the audit timer (called from init and post_upgrade)
+/// The first pass runs right away rather than a day later
+pub fn init_security_audits() {
+    set_timer(Duration::ZERO, || ic_cdk::spawn(run_audits()));
+    set_timer_interval(Duration::from_secs(AUDIT_INTERVAL_SECS), || ic_cdk::spawn(run_audits()));
+    ic_cdk::println!("Security audits scheduled every {}s", AUDIT_INTERVAL_SECS);
+}
+
+/// Clears AUDIT_IN_PROGRESS when dropped, so a pass that traps doesn't stop later ones
+struct AuditGuard;
+
+impl Drop for AuditGuard {
+    fn drop(&mut self) {
+        AUDIT_IN_PROGRESS.with(|a| a.set(false));
+    }
+}
+
+async fn run_audits() {
+    if AUDIT_IN_PROGRESS.with(|a| a.replace(true)) {
+        return;
+    }
+    let _audit = AuditGuard;
+    audit_all_stations().await;
+}
+
+async fn audit_all_stations() {
+    let stations: Vec<Principal> = TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations.borrow().iter().map(|(_, station)| station.0).collect()
+    });
+
+    for station_id in stations {
+        // A station that can't be read is skipped rather than recorded as a regression
+        match audit_station(station_id).await {
+            Ok(dashboard) => record_audit(station_id, &dashboard),
+            Err(e) => ic_cdk::println!("SECURITY AUDIT: station {}: {}", station_id, e),
+        }
+    }
+}
+
+async fn audit_station(station_id: Principal) -> Result<EnhancedSecurityDashboard, String> {
+    let data = fetch_station_data(station_id).await?;
+    build_dashboard(station_id, data.run_checks())
+}
+
+fn record_audit(station_id: Principal, dashboard: &EnhancedSecurityDashboard) {
+    let key = StorablePrincipal(station_id);
+    let record = SecurityAuditRecord {
+        station_id,
+        audited_at: dashboard.last_checked,
+        score: dashboard.decentralization_score,
+        overall_status: dashboard.overall_status.clone(),
+        failing_checks: dashboard
+            .checks
+            .iter()
+            .filter(|c| matches!(c.status, CheckStatus::Fail | CheckStatus::Warn))
+            .map(|c| FailingCheck {
+                category: c.category.clone(),
+                name: c.name.clone(),
+                status: c.status.clone(),
+                severity: c.severity.clone().unwrap_or(Severity::None),
+            })
+            .collect(),
+    };
+
+    SECURITY_HISTORY.with(|history| {
+        let mut history = history.borrow_mut();
+        let previous = history
+            .range((key.clone(), 0)..=(key.clone(), u64::MAX))
+            .next_back()
+            .map(|(_, record)| record);
+
+        let cutoff = record.audited_at.saturating_sub(HISTORY_RETENTION_NANOS);
+        let alert = previous.and_then(|previous| regression(&previous, &record));
+        SECURITY_ALERTS.with(|alerts| {
+            let mut alerts = alerts.borrow_mut();
+            if let Some(alert) = alert {
+                alerts.insert((key.clone(), record.audited_at), alert);
+            }
+            let expired: Vec<(StorablePrincipal, u64)> = alerts
+                .range((key.clone(), 0)..(key.clone(), cutoff))
+                .map(|(k, _)| k)
+                .collect();
+            for k in expired {
+                alerts.remove(&k);
+            }
+        });
+
+        let expired: Vec<(StorablePrincipal, u64)> = history
+            .range((key.clone(), 0)..(key.clone(), cutoff))
+            .map(|(k, _)| k)
+            .collect();
+        for k in expired {
+            history.remove(&k);
+        }
+
+        history.insert((key, record.audited_at), record);
+    });
+}
+
+fn regression(previous: &SecurityAuditRecord, current: &SecurityAuditRecord) -> Option<SecurityAlert> {
+    let is_critical = |c: &FailingCheck| c.status == CheckStatus::Fail && c.severity == Severity::Critical;
+    let new_critical_checks: Vec<FailingCheck> = current
+        .failing_checks
+        .iter()
+        .filter(|c| is_critical(c))
+        .filter(|c| {
+            !previous
+                .failing_checks
+                .iter()
+                .any(|p| is_critical(p) && p.category == c.category && p.name == c.name)
+        })
+        .cloned()
+        .collect();
+
+    if current.score >= previous.score && new_critical_checks.is_empty() {
+        return None;
+    }
+    Some(SecurityAlert {
+        station_id: current.station_id,
+        raised_at: current.audited_at,
+        score_before: previous.score,
+        score_after: current.score,
+        new_critical_checks,
+    })
+}
+
+/// Stored audits of a station between two timestamps (nanoseconds, inclusive)
+#[query]
+pub fn get_security_history(station_id: Principal, from: u64, to: u64) -> Vec<SecurityAuditRecord> {
+    if from > to {
+        return Vec::new();
+    }
+    let key = StorablePrincipal(station_id);
+    SECURITY_HISTORY.with(|history| {
+        history
+            .borrow()
+            .range((key.clone(), from)..=(key, to))
+            .map(|(_, record)| record)
+            .collect()
+    })
+}
+
+/// Security alerts raised before `before` (nanoseconds, exclusive; default
+/// now), newest first and at most 100, for one station or all of them.
+/// Page on by passing the last alert's raised_at as `before`
+#[query]
+pub fn get_security_alerts(station_id: Option<Principal>, before: Option<u64>) -> Vec<SecurityAlert> {
+    let before = before.unwrap_or(u64::MAX);
+    SECURITY_ALERTS.with(|alerts| {
+        let alerts = alerts.borrow();
+        let newest = |station: &StorablePrincipal| -> Vec<SecurityAlert> {
+            alerts
+                .range((station.clone(), 0)..(station.clone(), before))
+                .rev()
+                .take(MAX_ALERTS_RETURNED)
+                .map(|(_, alert)| alert)
+                .collect()
+        };
+
+        let mut list = match station_id {
+            Some(station_id) => newest(&StorablePrincipal(station_id)),
+            None => {
+                // At most a page from each station, stepping from one station's
+                // keys to the next without reading the rest
+                let mut list = Vec::new();
+                let mut next = alerts.first_key_value().map(|((station, _), _)| station);
+                while let Some(station) = next {
+                    list.extend(newest(&station));
+                    let after = (Bound::Excluded((station, u64::MAX)), Bound::Unbounded);
+                    next = alerts.range(after).next().map(|((station, _), _)| station);
+                }
+                list
+            }
+        };
+        list.sort_by_key(|alert| std::cmp::Reverse(alert.raised_at));
+        list.truncate(MAX_ALERTS_RETURNED);
+        list
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, status: CheckStatus, severity: Severity) -> FailingCheck {
+        FailingCheck { category: "Admin Control".to_string(), name: name.to_string(), status, severity }
+    }
+
+    fn audit(score: u8, failing_checks: Vec<FailingCheck>) -> SecurityAuditRecord {
+        SecurityAuditRecord {
+            station_id: Principal::anonymous(),
+            audited_at: 0,
+            score,
+            overall_status: String::new(),
+            failing_checks,
+        }
+    }
+
+    #[test]
+    fn only_new_critical_failures_raise_an_alert() {
+        let before = audit(80, vec![check("Backend Admin Status", CheckStatus::Fail, Severity::Critical)]);
+
+        let unchanged = audit(80, vec![
+            check("Backend Admin Status", CheckStatus::Fail, Severity::Critical),
+            check("Operator Group", CheckStatus::Warn, Severity::Critical),
+        ]);
+        assert!(regression(&before, &unchanged).is_none());
+
+        let worse = audit(80, vec![check("Admin Users Present", CheckStatus::Fail, Severity::Critical)]);
+        let alert = regression(&before, &worse).unwrap();
+        assert_eq!(alert.new_critical_checks.len(), 1);
+        assert_eq!(alert.new_critical_checks[0].name, "Admin Users Present");
+    }
+}
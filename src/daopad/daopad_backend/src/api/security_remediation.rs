@@ -0,0 +1,231 @@
+// Security remediation
+//
+// Turns failed security checks into the governed requests that fix them. Each
+// request is linked to the check it fixes, and the checks are re-run against
+// the station as it will look once the proposed requests execute.
+
+use crate::api::governance_profiles::{
+    admin_only, require_admin_approval, restrict_to_admin, GovernanceProfileChange, ADMIN_GROUP_ID,
+};
+use crate::api::security::simulation::{fetch_station_data, StationSecurityData};
+use crate::api::security::{CheckStatus, SecurityCheck};
+use crate::proposals::unified::{create_governed_request, OrbitOperation, OrbitOperationType};
+use crate::storage::state::TOKEN_ORBIT_STATIONS;
+use crate::types::orbit::UserGroup;
+use crate::types::StorablePrincipal;
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::update;
+
+/// A proposed fix and the check it belongs to
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemediationProposal {
+    pub check_name: String,
+    pub change: GovernanceProfileChange,
+    pub result: Result<String, String>, // Orbit request ID
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UnremediatedCheck {
+    pub check_name: String,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemediationReport {
+    pub proposals: Vec<RemediationProposal>,
+    pub unremediated: Vec<UnremediatedCheck>,
+    pub projected_checks: Vec<SecurityCheck>, // The affected checks once the proposals execute
+}
+
+type Fix = (GovernanceProfileChange, OrbitOperation);
+
+fn needs_fix(check: &SecurityCheck) -> bool {
+    matches!(check.status, CheckStatus::Fail | CheckStatus::Warn)
+}
+
+/// Whether two operations change the same permission, policy or user
+fn same_target(a: &OrbitOperation, b: &OrbitOperation) -> bool {
+    match (a, b) {
+        (OrbitOperation::EditPermission(a), OrbitOperation::EditPermission(b)) => a.resource == b.resource,
+        (OrbitOperation::EditRequestPolicy(a), OrbitOperation::EditRequestPolicy(b)) => a.policy_id == b.policy_id,
+        (OrbitOperation::RemoveAdmin { user_id: a, .. }, OrbitOperation::RemoveAdmin { user_id: b, .. }) => a == b,
+        _ => false,
+    }
+}
+
+fn related_count(data: &StationSecurityData, check_name: &str) -> usize {
+    data.run_checks()
+        .into_iter()
+        .find(|c| c.name == check_name)
+        .and_then(|c| c.related_permissions)
+        .map_or(0, |related| related.len())
+}
+
+/// Restrict every permission the check flags
+/// The check only reports resource names, so each permission not yet limited
+/// to Admin is restricted in turn and kept if the check then flags fewer grants
+fn permission_fixes(data: &mut StationSecurityData, check_name: &str) -> Vec<Fix> {
+    let before = related_count(data, check_name);
+    let mut fixes = Vec::new();
+
+    for index in 0..data.permissions.len() {
+        if admin_only(&data.permissions[index]) {
+            continue;
+        }
+        let fix = restrict_to_admin(&data.permissions[index]);
+        let original = data.permissions[index].clone();
+        data.apply(&fix.1);
+        let after = related_count(data, check_name);
+        data.permissions[index] = original;
+
+        if after < before {
+            fixes.push(fix);
+        }
+    }
+
+    fixes
+}
+
+/// Move every admin other than the DAOPad backend to the Operator group
+fn admin_fixes(data: &StationSecurityData) -> Result<Vec<Fix>, String> {
+    let is_admin = |groups: &[UserGroup]| groups.iter().any(|g| g.id == ADMIN_GROUP_ID);
+    let backend_is_admin = data
+        .users
+        .iter()
+        .any(|u| is_admin(&u.groups) && u.identities.contains(&data.backend_principal));
+    if !backend_is_admin {
+        return Err("DAOPad backend must be an admin before other admins can be removed".to_string());
+    }
+
+    Ok(data
+        .users
+        .iter()
+        .filter(|u| is_admin(&u.groups) && !u.identities.contains(&data.backend_principal))
+        .map(|u| {
+            (
+                GovernanceProfileChange {
+                    title: format!("Remove {} from Admin group", u.name),
+                    reason: "Admins other than DAOPad can act without a community vote".to_string(),
+                    operation_type: OrbitOperationType::EditUser,
+                },
+                OrbitOperation::RemoveAdmin {
+                    user_id: u.id.clone(),
+                    user_name: u.name.clone(),
+                },
+            )
+        })
+        .collect())
+}
+
+fn check_fixes(data: &mut StationSecurityData, check: &SecurityCheck) -> Result<Vec<Fix>, String> {
+    match check.name.as_str() {
+        "Admin User Count" => admin_fixes(data),
+        "Policy Bypass Detection" => Ok(data.policies.iter().filter_map(require_admin_approval).collect()),
+        _ if check.related_permissions.is_some() => Ok(permission_fixes(data, &check.name)),
+        _ => Err(check
+            .recommendation
+            .clone()
+            .unwrap_or_else(|| "No automatic fix for this check".to_string())),
+    }
+}
+
+fn plan_fixes(
+    data: &mut StationSecurityData,
+    targets: &[&SecurityCheck],
+) -> (Vec<(String, Fix)>, Vec<UnremediatedCheck>) {
+    let mut fixes: Vec<(String, Fix)> = Vec::new();
+    let mut unremediated = Vec::new();
+
+    for check in targets {
+        if !needs_fix(check) {
+            unremediated.push(UnremediatedCheck {
+                check_name: check.name.clone(),
+                reason: "Check already passes".to_string(),
+            });
+            continue;
+        }
+        match check_fixes(data, check) {
+            Ok(check_fixes) if !check_fixes.is_empty() => {
+                for fix in check_fixes {
+                    // Checks can share a permission, e.g. AddressBook.Create
+                    if !fixes.iter().any(|(_, (_, op))| same_target(op, &fix.1)) {
+                        fixes.push((check.name.clone(), fix));
+                    }
+                }
+            }
+            Ok(_) => unremediated.push(UnremediatedCheck {
+                check_name: check.name.clone(),
+                reason: "No station change found that fixes this check".to_string(),
+            }),
+            Err(reason) => unremediated.push(UnremediatedCheck {
+                check_name: check.name.clone(),
+                reason,
+            }),
+        }
+    }
+
+    (fixes, unremediated)
+}
+
+/// Propose the requests that fix the named security checks
+/// An empty list means every failing or warning check. Each proposal is
+/// linked to its check, and the checks are re-run with the proposed changes
+/// applied to confirm they pass once the requests execute
+#[update] // MUST be update, not query for cross-canister calls
+pub async fn generate_remediation_proposals(
+    token_canister_id: Principal,
+    check_names: Vec<String>,
+) -> Result<RemediationReport, String> {
+    let station_id = TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations
+            .borrow()
+            .get(&StorablePrincipal(token_canister_id))
+            .map(|s| s.0)
+    })
+    .ok_or_else(|| "No Orbit Station linked to this token".to_string())?;
+
+    let mut data = fetch_station_data(station_id).await?;
+    let checks = data.run_checks();
+
+    let targets: Vec<&SecurityCheck> = if check_names.is_empty() {
+        checks.iter().filter(|c| needs_fix(c)).collect()
+    } else {
+        let mut targets = Vec::new();
+        for name in &check_names {
+            match checks.iter().find(|c| &c.name == name) {
+                Some(check) => targets.push(check),
+                None => return Err(format!("Unknown security check: {}", name)),
+            }
+        }
+        targets
+    };
+
+    let (fixes, unremediated) = plan_fixes(&mut data, &targets);
+
+    let mut proposals = Vec::with_capacity(fixes.len());
+    for (check_name, (change, operation)) in fixes {
+        let summary = format!("Fixes security check \"{}\": {}", check_name, change.reason);
+        let result = create_governed_request(
+            token_canister_id,
+            operation.clone(),
+            Some(change.title.clone()),
+            Some(summary),
+            None,
+        )
+        .await
+        .map_err(|e| e.to_string());
+        if result.is_ok() {
+            data.apply(&operation);
+        }
+        proposals.push(RemediationProposal { check_name, change, result });
+    }
+
+    let target_names: Vec<String> = targets.iter().map(|c| c.name.clone()).collect();
+    let projected_checks = data
+        .run_checks()
+        .into_iter()
+        .filter(|c| target_names.contains(&c.name))
+        .collect();
+
+    Ok(RemediationReport { proposals, unremediated, projected_checks })
+}
@@ -74,6 +74,75 @@ pub async fn link_orbit_station(
     Ok(())
 }
 
+/// Link a token to a former equity station as part of a governed conversion
+/// ONLY the admin canister can call this, after the holders' supermajority vote
+#[update]
+pub async fn link_converted_station(
+    token_canister_id: Principal,
+    station_id: Principal,
+) -> Result<(), String> {
+    require_admin_canister()?;
+
+    if TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations.borrow().contains_key(&StorablePrincipal(token_canister_id))
+    }) {
+        return Err("An Orbit Station is already linked to this token".to_string());
+    }
+    if STATION_TO_TOKEN.with(|stations| stations.borrow().contains_key(&StorablePrincipal(station_id))) {
+        return Err("This Orbit Station is already linked to a token".to_string());
+    }
+
+    // The token must be a live ICRC-1 ledger for Kong Locker voting power to mean anything
+    let symbol: Result<(String,), _> = ic_cdk::call(token_canister_id, "icrc1_symbol", ()).await;
+    if let Err((code, msg)) = symbol {
+        return Err(format!("{} is not an ICRC-1 token: {:?} - {}", token_canister_id, code, msg));
+    }
+
+    verify_backend_is_admin(station_id).await?;
+
+    TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations.borrow_mut().insert(StorablePrincipal(token_canister_id), StorablePrincipal(station_id));
+    });
+    STATION_TO_TOKEN.with(|stations| {
+        stations.borrow_mut().insert(StorablePrincipal(station_id), StorablePrincipal(token_canister_id));
+    });
+
+    ic_cdk::println!("Equity station {} converted to token {}", station_id, token_canister_id);
+    Ok(())
+}
+
+/// Unlink a token from its station so the station can become an equity station
+/// ONLY the admin canister can call this, on the station's own request
+#[update]
+pub fn unlink_converted_station(
+    token_canister_id: Principal,
+    station_id: Principal,
+) -> Result<(), String> {
+    require_admin_canister()?;
+
+    let linked = TOKEN_ORBIT_STATIONS.with(|stations| {
+        stations.borrow().get(&StorablePrincipal(token_canister_id)).map(|s| s.0)
+    });
+    if linked != Some(station_id) {
+        return Err(format!("Station {} is not linked to token {}", station_id, token_canister_id));
+    }
+
+    TOKEN_ORBIT_STATIONS.with(|stations| stations.borrow_mut().remove(&StorablePrincipal(token_canister_id)));
+    STATION_TO_TOKEN.with(|stations| stations.borrow_mut().remove(&StorablePrincipal(station_id)));
+
+    ic_cdk::println!("Token {} unlinked from station {} for equity conversion", token_canister_id, station_id);
+    Ok(())
+}
+
+fn require_admin_canister() -> Result<(), String> {
+    let admin_canister = Principal::from_text("odkrm-viaaa-aaaap-qp2oq-cai")
+        .map_err(|e| format!("Invalid admin principal: {}", e))?;
+    if ic_cdk::caller() != admin_canister {
+        return Err("Only the admin canister can convert stations".to_string());
+    }
+    Ok(())
+}
+
 /// Verify DAOPad backend is admin of the Orbit Station
 async fn verify_backend_is_admin(station_id: Principal) -> Result<bool, String> {
     use crate::types::orbit::{MeResult, UserPrivilege};
@@ -0,0 +1,462 @@
+// Verified system upgrades of a DAO's Orbit Station
+//
+// A proposer uploads the station (or upgrader) wasm in chunks, claiming the
+// SHA-256 of its reproducible build. Once every byte is in, we hash the
+// staged module ourselves and only create Orbit's SystemUpgrade request
+// (and its 90% proposal) if the hashes match. Voters see the hash, the size
+// and the source commit; the chunks stay staged until the proposal finishes
+// so anyone can fetch the module and check it.
+//
+// Orbit gets the first chunk inline and fetches the rest through
+// module_extra_chunks: we serve every later chunk as an asset named after the
+// upload, through the asset canister `get`/`get_chunk` queries. While a
+// proposal is being created its upload is frozen: no chunk writes, deletes or
+// second proposals.
+
+use crate::kong_locker::voting::get_user_voting_power_for_token;
+use crate::proposals::types::ProposalStatus;
+use crate::proposals::unified::{create_governed_request, OrbitOperation, OrbitOperationType};
+use crate::storage::memory::ADMIN_CANISTER;
+use crate::storage::state::{TOKEN_ORBIT_STATIONS, UPGRADE_CHUNKS, UPGRADE_UPLOADS};
+use crate::types::orbit::{SystemUpgradeOperationInput, SystemUpgradeTarget, WasmModuleExtraChunks};
+use crate::types::{StorablePrincipal, StorableString, UpgradeUpload};
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::api::time;
+use ic_cdk::{query, update};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+// Chunked installs take modules up to 100 MB; the argument and first chunk
+// go to Orbit inline, so they must fit in one call together
+const MAX_MODULE_BYTES: u64 = 100_000_000;
+const MAX_ARG_BYTES: u64 = 1_000_000;
+const MAX_CHUNK_BYTES: usize = 1_000_000;
+const ABANDONED_UPLOAD_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000; // 1 day
+
+thread_local! {
+    // Uploads whose proposal is being created
+    static PROPOSING_UPLOADS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Marks an upload as being proposed until dropped, including when the call traps
+struct ProposingGuard(String);
+
+impl Drop for ProposingGuard {
+    fn drop(&mut self) {
+        PROPOSING_UPLOADS.with(|p| p.borrow_mut().remove(&self.0));
+    }
+}
+
+fn is_proposing(upload_id: &str) -> bool {
+    PROPOSING_UPLOADS.with(|p| p.borrow().contains(upload_id))
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StartSystemUpgradeInput {
+    pub target: SystemUpgradeTarget,
+    pub expected_sha256: String,  // Hex hash of the reproducible build
+    pub source_commit: String,    // https link to the commit it was built from
+    pub total_size: u64,          // Module size in bytes
+    pub arg: Option<Vec<u8>>,
+    pub take_backup_snapshot: Option<bool>,
+}
+
+/// Upload progress, as shown to the proposer and voters
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SystemUpgradeUploadStatus {
+    pub upload: UpgradeUpload,
+    pub received_bytes: u64,
+    pub chunk_count: u32,
+}
+
+// Asset canister types for the module_extra_chunks queries
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GetAssetArg {
+    pub key: String,
+    pub accept_encodings: Vec<String>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EncodedAsset {
+    pub content: Vec<u8>,
+    pub content_type: String,
+    pub content_encoding: String,
+    pub sha256: Option<Vec<u8>>,
+    pub total_length: Nat,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct GetAssetChunkArg {
+    pub key: String,
+    pub content_encoding: String,
+    pub index: Nat,
+    pub sha256: Option<Vec<u8>>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AssetChunk {
+    pub content: Vec<u8>,
+}
+
+// The parts of the admin canister's proposal we need for cleanup
+#[derive(CandidType, Deserialize)]
+struct ProposalState {
+    status: ProposalStatus,
+    expires_at: u64,
+}
+
+fn load_upload(upload_id: &str) -> Result<UpgradeUpload, String> {
+    UPGRADE_UPLOADS.with(|uploads| {
+        uploads
+            .borrow()
+            .get(&StorableString(upload_id.to_string()))
+            .ok_or_else(|| "Upload not found".to_string())
+    })
+}
+
+fn save_upload(upload: UpgradeUpload) {
+    UPGRADE_UPLOADS.with(|uploads| {
+        uploads
+            .borrow_mut()
+            .insert(StorableString(upload.upload_id.clone()), upload);
+    });
+}
+
+/// Size of a staged module, for the hashes shown on its proposal
+pub(crate) fn upload_total_size(upload_id: &str) -> Option<u64> {
+    load_upload(upload_id).ok().map(|upload| upload.total_size)
+}
+
+/// Record the Orbit request on an upload that still exists
+fn set_orbit_request(upload_id: &str, request_id: &str) {
+    UPGRADE_UPLOADS.with(|uploads| {
+        let mut uploads = uploads.borrow_mut();
+        let key = StorableString(upload_id.to_string());
+        if let Some(mut upload) = uploads.get(&key) {
+            upload.orbit_request_id = Some(request_id.to_string());
+            uploads.insert(key, upload);
+        }
+    });
+}
+
+fn chunk_keys(upload_id: &str) -> Vec<(StorableString, u32)> {
+    let start = (StorableString(upload_id.to_string()), 0);
+    UPGRADE_CHUNKS.with(|chunks| {
+        chunks
+            .borrow()
+            .range(start..)
+            .take_while(|((id, _), _)| id.0 == upload_id)
+            .map(|(key, _)| key)
+            .collect()
+    })
+}
+
+fn delete_upload(upload_id: &str) {
+    if is_proposing(upload_id) {
+        return;
+    }
+    for key in chunk_keys(upload_id) {
+        UPGRADE_CHUNKS.with(|chunks| chunks.borrow_mut().remove(&key));
+    }
+    UPGRADE_UPLOADS.with(|uploads| uploads.borrow_mut().remove(&StorableString(upload_id.to_string())));
+}
+
+fn upload_status(upload: UpgradeUpload) -> SystemUpgradeUploadStatus {
+    let start = (StorableString(upload.upload_id.clone()), 0);
+    let (received_bytes, chunk_count) = UPGRADE_CHUNKS.with(|chunks| {
+        chunks
+            .borrow()
+            .range(start..)
+            .take_while(|((id, _), _)| id.0 == upload.upload_id)
+            .fold((0u64, 0u32), |(bytes, count), (_, chunk)| (bytes + chunk.len() as u64, count + 1))
+    });
+    SystemUpgradeUploadStatus { upload, received_bytes, chunk_count }
+}
+
+/// SHA-256 (hex) of the staged module and its first chunk, if every chunk
+/// from 0 up is present. Hashed chunk by chunk so the module is never assembled
+fn verify_module(upload: &UpgradeUpload) -> Result<(String, Vec<u8>), String> {
+    use sha2::{Digest, Sha256};
+
+    let start = (StorableString(upload.upload_id.clone()), 0);
+    let mut hasher = Sha256::new();
+    let mut first_chunk = Vec::new();
+    let mut size = 0u64;
+    UPGRADE_CHUNKS.with(|chunks| {
+        for (expected, ((id, index), chunk)) in chunks.borrow().range(start..).enumerate() {
+            if id.0 != upload.upload_id {
+                break;
+            }
+            if index as usize != expected {
+                return Err(format!("Chunk {} is missing", expected));
+            }
+            hasher.update(&chunk);
+            size += chunk.len() as u64;
+            if index == 0 {
+                first_chunk = chunk;
+            }
+        }
+        Ok(())
+    })?;
+
+    if size != upload.total_size {
+        return Err(format!(
+            "Upload incomplete: have {} of {} bytes",
+            size,
+            upload.total_size
+        ));
+    }
+    Ok((hex::encode(hasher.finalize()), first_chunk))
+}
+
+/// Chunk `index` of the upload's extra chunks asset (staged chunk index + 1)
+fn extra_chunk(upload_id: &str, index: u32) -> Vec<u8> {
+    let Some(staged) = index.checked_add(1) else {
+        ic_cdk::trap("chunk not found");
+    };
+    UPGRADE_CHUNKS
+        .with(|chunks| chunks.borrow().get(&(StorableString(upload_id.to_string()), staged)))
+        .unwrap_or_else(|| ic_cdk::trap("chunk not found"))
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Start uploading a system upgrade for a token's station; returns the upload ID
+/// Replaces any of the caller's earlier uploads for this token that weren't proposed
+#[update]
+pub async fn start_system_upgrade_upload(
+    token_canister_id: Principal,
+    input: StartSystemUpgradeInput,
+) -> Result<String, String> {
+    let caller = ic_cdk::caller();
+
+    if !TOKEN_ORBIT_STATIONS.with(|s| s.borrow().contains_key(&StorablePrincipal(token_canister_id))) {
+        return Err("No Orbit Station linked to this token".to_string());
+    }
+
+    let expected_sha256 = input.expected_sha256.trim().to_lowercase();
+    if expected_sha256.len() != 64 || !expected_sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Expected hash must be a 64 character hex SHA-256".to_string());
+    }
+    if !input.source_commit.starts_with("https://") || input.source_commit.len() > 300 {
+        return Err("Source commit must be an https link of at most 300 characters".to_string());
+    }
+    if input.total_size == 0 || input.total_size > MAX_MODULE_BYTES {
+        return Err(format!("Module must be 1-{} bytes", MAX_MODULE_BYTES));
+    }
+    if input.arg.as_ref().is_some_and(|a| a.len() as u64 > MAX_ARG_BYTES) {
+        return Err(format!("Argument must be at most {} bytes", MAX_ARG_BYTES));
+    }
+
+    // Only holders who could propose the upgrade may stage one
+    let required = OrbitOperationType::SystemUpgrade.minimum_voting_power();
+    let voting_power = get_user_voting_power_for_token(caller, token_canister_id).await?;
+    if voting_power < required {
+        return Err(format!(
+            "Insufficient voting power: have {}, need {}",
+            voting_power, required
+        ));
+    }
+
+    cleanup_system_upgrade_uploads().await;
+
+    let stale: Vec<String> = UPGRADE_UPLOADS.with(|uploads| {
+        uploads
+            .borrow()
+            .iter()
+            .map(|(_, u)| u)
+            .filter(|u| u.uploader == caller && u.token_canister_id == token_canister_id)
+            .filter(|u| u.orbit_request_id.is_none())
+            .map(|u| u.upload_id)
+            .collect()
+    });
+    for upload_id in stale {
+        delete_upload(&upload_id);
+    }
+
+    let now = time();
+    let upload_id = format!("upgrade-{}-{}", now, caller.to_text());
+    save_upload(UpgradeUpload {
+        upload_id: upload_id.clone(),
+        token_canister_id,
+        uploader: caller,
+        target: input.target,
+        expected_sha256,
+        source_commit: input.source_commit,
+        total_size: input.total_size,
+        arg: input.arg,
+        take_backup_snapshot: input.take_backup_snapshot,
+        created_at: now,
+        orbit_request_id: None,
+    });
+
+    Ok(upload_id)
+}
+
+/// Stage chunk `index` (from 0) of an upload; returns the bytes received so far
+#[update]
+pub fn upload_system_upgrade_chunk(upload_id: String, index: u32, chunk: Vec<u8>) -> Result<u64, String> {
+    let upload = load_upload(&upload_id)?;
+
+    if ic_cdk::caller() != upload.uploader {
+        return Err("Only the uploader can add chunks".to_string());
+    }
+    if upload.orbit_request_id.is_some() || is_proposing(&upload_id) {
+        return Err("This upgrade has already been proposed".to_string());
+    }
+    if chunk.is_empty() || chunk.len() > MAX_CHUNK_BYTES {
+        return Err(format!("Chunks must be 1-{} bytes", MAX_CHUNK_BYTES));
+    }
+
+    let key = (StorableString(upload_id), index);
+    let replaced = UPGRADE_CHUNKS.with(|chunks| chunks.borrow().get(&key).map_or(0, |c| c.len() as u64));
+    let received = upload_status(upload.clone()).received_bytes - replaced + chunk.len() as u64;
+    if received > upload.total_size {
+        return Err(format!(
+            "Chunk would take the upload past its declared {} bytes",
+            upload.total_size
+        ));
+    }
+
+    UPGRADE_CHUNKS.with(|chunks| chunks.borrow_mut().insert(key, chunk));
+    Ok(received)
+}
+
+/// Check the staged module against its claimed hash and put the upgrade to a vote
+/// Returns the Orbit request ID; the proposal needs 90% of voting power
+#[update]
+pub async fn propose_system_upgrade(upload_id: String) -> Result<String, String> {
+    let upload = load_upload(&upload_id)?;
+
+    if ic_cdk::caller() != upload.uploader {
+        return Err("Only the uploader can propose this upgrade".to_string());
+    }
+    if let Some(request_id) = &upload.orbit_request_id {
+        return Err(format!("Already proposed as Orbit request {}", request_id));
+    }
+    if !PROPOSING_UPLOADS.with(|p| p.borrow_mut().insert(upload_id.clone())) {
+        return Err("This upgrade is already being proposed".to_string());
+    }
+    let _proposing = ProposingGuard(upload_id.clone());
+
+    let (actual, first_chunk) = verify_module(&upload)?;
+    if actual != upload.expected_sha256 {
+        return Err(format!(
+            "Hash mismatch: the uploaded module is {}, the reproducible build claims {}",
+            actual, upload.expected_sha256
+        ));
+    }
+
+    let target_name = match upload.target {
+        SystemUpgradeTarget::UpgradeStation => "station",
+        SystemUpgradeTarget::UpgradeUpgrader => "upgrader",
+    };
+    let title = format!("Upgrade {} to {}", target_name, &actual[..12]);
+    let summary = format!(
+        "Wasm SHA-256 {} ({} bytes), verified against the reproducible build of {}",
+        actual, upload.total_size, upload.source_commit
+    );
+    // Orbit fetches everything after the first chunk from our asset queries
+    let module_extra_chunks = (first_chunk.len() as u64 != upload.total_size).then(|| WasmModuleExtraChunks {
+        store_canister: ic_cdk::id(),
+        extra_chunks_key: upload.upload_id.clone(),
+        wasm_module_hash: hex::decode(&actual).expect("SHA-256 hex is valid"),
+    });
+    let operation = OrbitOperation::SystemUpgrade(SystemUpgradeOperationInput {
+        target: upload.target.clone(),
+        module: first_chunk,
+        module_extra_chunks,
+        arg: upload.arg.clone(),
+        take_backup_snapshot: upload.take_backup_snapshot,
+    });
+
+    let request_id = create_governed_request(
+        upload.token_canister_id,
+        operation,
+        Some(title),
+        Some(summary),
+        Some(upload.source_commit.clone()),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    set_orbit_request(&upload_id, &request_id);
+    Ok(request_id)
+}
+
+#[query]
+pub fn get_system_upgrade_upload(upload_id: String) -> Option<SystemUpgradeUploadStatus> {
+    load_upload(&upload_id).ok().map(upload_status)
+}
+
+/// A staged chunk, so voters can download the module and hash it themselves
+#[query]
+pub fn get_system_upgrade_chunk(upload_id: String, index: u32) -> Option<Vec<u8>> {
+    UPGRADE_CHUNKS.with(|chunks| chunks.borrow().get(&(StorableString(upload_id), index)))
+}
+
+/// An upload's extra chunks as an asset, as Orbit reads module_extra_chunks
+/// Traps like an asset canister when there is no such asset
+#[query(name = "get")]
+pub fn get_upgrade_asset(arg: GetAssetArg) -> EncodedAsset {
+    let upload = load_upload(&arg.key).unwrap_or_else(|e| ic_cdk::trap(&e));
+    let first_chunk_len = UPGRADE_CHUNKS
+        .with(|chunks| chunks.borrow().get(&(StorableString(arg.key.clone()), 0)))
+        .map_or(0, |c| c.len() as u64);
+    EncodedAsset {
+        content: extra_chunk(&arg.key, 0),
+        content_type: "application/wasm".to_string(),
+        content_encoding: "identity".to_string(),
+        sha256: None,
+        total_length: Nat::from(upload.total_size.saturating_sub(first_chunk_len)),
+    }
+}
+
+/// Chunk `index` of an upload's extra chunks asset
+#[query(name = "get_chunk")]
+pub fn get_upgrade_asset_chunk(arg: GetAssetChunkArg) -> AssetChunk {
+    let index = u32::try_from(arg.index.0).unwrap_or_else(|_| ic_cdk::trap("chunk not found"));
+    AssetChunk { content: extra_chunk(&arg.key, index) }
+}
+
+/// Drop staged uploads whose proposal has finished, and unproposed ones
+/// left for over a day. Anyone can call this; it also runs on every new upload
+#[update]
+pub async fn cleanup_system_upgrade_uploads() -> u32 {
+    let now = time();
+    let uploads: Vec<UpgradeUpload> =
+        UPGRADE_UPLOADS.with(|uploads| uploads.borrow().iter().map(|(_, u)| u).collect());
+
+    let mut removed = 0;
+    for upload in uploads {
+        let finished = match &upload.orbit_request_id {
+            None => now.saturating_sub(upload.created_at) > ABANDONED_UPLOAD_NANOS,
+            Some(request_id) => proposal_finished(upload.token_canister_id, request_id, now).await,
+        };
+        if finished {
+            delete_upload(&upload.upload_id);
+            removed += 1;
+        }
+    }
+    removed
+}
+
+async fn proposal_finished(token_canister_id: Principal, request_id: &str, now: u64) -> bool {
+    let Ok(admin) = Principal::from_text(ADMIN_CANISTER) else {
+        return false;
+    };
+    let result: Result<(Option<ProposalState>,), _> =
+        ic_cdk::call(admin, "get_proposal", (token_canister_id, request_id.to_string())).await;
+    match result {
+        // An executed upgrade may still be fetching its chunks, so they are kept a day
+        Ok((Some(proposal),)) => match proposal.status {
+            ProposalStatus::Active => now > proposal.expires_at,
+            ProposalStatus::Executed => now > proposal.expires_at.saturating_add(ABANDONED_UPLOAD_NANOS),
+            ProposalStatus::Rejected | ProposalStatus::Expired => true,
+        },
+        Ok((None,)) => true,
+        Err(_) => false, // Try again next time
+    }
+}
@@ -1,35 +1,35 @@
-use crate::types::{TokenInfo, UserBalancesReply};
-use crate::storage::state::KONG_LOCKER_PRINCIPALS;
+use crate::kong_locker::registration::list_kong_locker_registrations;
+use crate::types::{LockedTokensPage, TokenInfo, UserBalancesReply};
 use candid::Principal;
 use ic_cdk::call;
 use std::collections::HashSet;
 
-/// Get ALL unique tokens that have locked liquidity across ALL lock canisters
-/// This is used for the public dashboard to show all DAOs
-pub async fn get_all_locked_tokens() -> Result<Vec<TokenInfo>, String> {
+// Lock canisters scanned per page; each is one KongSwap call, made concurrently
+pub const MAX_LOCK_CANISTERS_PER_PAGE: u64 = 50;
+
+/// Unique tokens with locked liquidity in one page of lock canisters
+/// This is used for the public dashboard to show all DAOs; callers walk the
+/// pages with `next_offset` (a token may appear on more than one page)
+pub async fn get_all_locked_tokens(offset: u64, limit: u64) -> Result<LockedTokensPage, String> {
     // Get all lock canister principals
-    let lock_canisters: Vec<Principal> = KONG_LOCKER_PRINCIPALS.with(|p| {
-        p.borrow()
-            .iter()
-            .map(|(_, canister)| canister.0)
-            .collect()
-    });
+    let lock_canisters: Vec<Principal> = list_kong_locker_registrations()
+        .await?
+        .into_iter()
+        .map(|(_, canister)| canister)
+        .collect();
 
-    if lock_canisters.is_empty() {
-        return Ok(Vec::new());
-    }
+    let total = lock_canisters.len() as u64;
+    let start = offset.min(total);
+    let end = start.saturating_add(limit.clamp(1, MAX_LOCK_CANISTERS_PER_PAGE)).min(total);
+    let page = &lock_canisters[start as usize..end as usize];
 
-    let mut all_tokens = HashSet::new();
+    // Query the page's lock canisters for their tokens
+    let results = futures::future::join_all(page.iter().map(|c| get_user_locked_tokens(*c))).await;
 
-    // Query each lock canister for its tokens
-    // Note: This is expensive - in production we'd cache this
-    for lock_canister in lock_canisters.iter() {
-        match get_user_locked_tokens(*lock_canister).await {
-            Ok(tokens) => {
-                for token in tokens {
-                    all_tokens.insert(token);
-                }
-            }
+    let mut all_tokens = HashSet::new();
+    for (lock_canister, result) in page.iter().zip(results) {
+        match result {
+            Ok(tokens) => all_tokens.extend(tokens),
             Err(e) => {
                 // Log error but continue with other canisters
                 ic_cdk::println!("Failed to get tokens for {}: {}", lock_canister, e);
@@ -37,7 +37,11 @@ pub async fn get_all_locked_tokens() -> Result<Vec<TokenInfo>, String> {
         }
     }
 
-    Ok(all_tokens.into_iter().collect())
+    Ok(LockedTokensPage {
+        tokens: all_tokens.into_iter().collect(),
+        total_lock_canisters: total,
+        next_offset: (end < total).then_some(end),
+    })
 }
 
 pub async fn get_user_locked_tokens(
@@ -1,59 +1,70 @@
-use crate::kong_locker::registration::get_or_lookup_kong_locker;
-use crate::types::UserBalancesReply;
-use candid::Principal;
+use crate::storage::memory::ADMIN_CANISTER;
+use candid::{CandidType, Deserialize, Principal};
 use ic_cdk::call;
 
-// Note: Minimum VP checks are now handled directly in the proposal system
+// Voting power is computed by the admin canister (TWAP-priced, from its
+// Kong Locker index) so proposals and votes always agree on the numbers.
+
+// Subset of the admin canister's VotingPowerResult
+#[derive(CandidType, Deserialize)]
+struct VotingPowerResult {
+    voting_power: u64,
+}
+
+// Subset of the admin canister's TokenVotingPowerTotal
+#[derive(CandidType, Deserialize)]
+struct TokenVotingPowerTotal {
+    total_vp: u64,
+}
+
+fn admin_canister() -> Result<Principal, String> {
+    Principal::from_text(ADMIN_CANISTER).map_err(|e| format!("Invalid admin canister ID: {}", e))
+}
 
 pub async fn get_user_voting_power_for_token(
     caller: Principal,
     token_canister_id: Principal,
 ) -> Result<u64, String> {
-    // Auto-lookup user's Kong Locker canister from factory if not cached
-    let kong_locker_principal = get_or_lookup_kong_locker(caller).await?;
+    let result: Result<(Result<VotingPowerResult, String>,), _> = call(
+        admin_canister()?,
+        "get_voting_power_display",
+        (token_canister_id, caller),
+    )
+    .await;
 
-    calculate_voting_power_for_token(kong_locker_principal, token_canister_id).await
+    Ok(result
+        .map_err(|e| format!("Failed to query admin canister: {:?}", e))?
+        .0?
+        .voting_power)
 }
 
-pub async fn calculate_voting_power_for_token(
-    kong_locker_principal: Principal,
-    token_canister_id: Principal,
-) -> Result<u64, String> {
-    let kongswap_id = Principal::from_text("2ipq2-uqaaa-aaaar-qailq-cai")
-        .map_err(|e| format!("Invalid KongSwap ID: {}", e))?;
-
-    let user_balances_result: Result<
-        (Result<Vec<UserBalancesReply>, String>,),
-        (ic_cdk::api::call::RejectionCode, String),
-    > = call(
-        kongswap_id,
-        "user_balances",
-        (kong_locker_principal.to_string(),),
+/// Indexed total voting power for a token across all lock canisters
+pub async fn get_total_indexed_voting_power(token_canister_id: Principal) -> Result<u64, String> {
+    let result: Result<(TokenVotingPowerTotal,), _> = call(
+        admin_canister()?,
+        "get_indexed_total_voting_power",
+        (token_canister_id,),
     )
     .await;
 
-    let user_balances = user_balances_result
-        .map_err(|e| format!("Failed to get LP positions: {:?}", e))?
+    Ok(result
+        .map_err(|e| format!("Failed to query admin canister: {:?}", e))?
         .0
-        .map_err(|e| format!("KongSwap returned error: {}", e))?;
-
-    // Calculate TOTAL USD value for positions containing this specific token
-    // Important: Use the full usd_balance of the LP position (both sides combined)
-    let token_id_str = token_canister_id.to_string();
-    let total_usd_value: f64 = user_balances
-        .iter()
-        .filter_map(|balance| {
-            let UserBalancesReply::LP(lp_reply) = balance;
-            // Count the FULL position value if this token is in the pair
-            if lp_reply.address_0 == token_id_str || lp_reply.address_1 == token_id_str {
-                // Use total USD balance of the entire LP position
-                Some(lp_reply.usd_balance)
-            } else {
-                None
-            }
-        })
-        .sum();
-
-    // Multiply by 100 to get voting power (preserving 2 decimal places as integer)
-    Ok((total_usd_value * 100.0) as u64)
+        .total_vp)
+}
+
+/// Indexed (lock_canister, voting_power) pairs for a token
+pub async fn list_indexed_voting_powers(
+    token_canister_id: Principal,
+) -> Result<Vec<(Principal, u64)>, String> {
+    let result: Result<(Vec<(Principal, u64)>,), _> = call(
+        admin_canister()?,
+        "list_indexed_voting_powers",
+        (token_canister_id,),
+    )
+    .await;
+
+    Ok(result
+        .map_err(|e| format!("Failed to query admin canister: {:?}", e))?
+        .0)
 }
@@ -6,8 +6,8 @@ mod storage;
 mod types;
 
 use candid::{Nat, Principal};
-use ic_cdk::init;
-use crate::types::AccountMinimalWithBalances;
+use ic_cdk::{init, post_upgrade};
+use crate::types::{AccountMinimalWithBalances, SecurityAlert, SecurityAuditRecord, UnlistedTransferPolicy};
 
 pub use api::*;
 pub use api::orbit_overview::DaoOverviewStats;
@@ -44,11 +44,18 @@ pub use types::orbit::{
     // Treasury management types
     TreasuryManagementData, TreasuryAccountDetails, TreasuryAddressBookEntry, AssetBalanceInfo,
 };
-pub use types::{AgreementSnapshot, TokenInfo, VotingThresholds};
+pub use types::{AgreementSnapshot, LockedTokensPage, TokenInfo, VotingThresholds};
 
 #[init]
 fn init() {
     ic_cdk::println!("DAOPad backend initialized");
+    api::init_security_audits();
+}
+
+// Timers don't survive upgrades - restart the security audits
+#[post_upgrade]
+fn post_upgrade() {
+    api::init_security_audits();
 }
 
 ic_cdk::export_candid!();
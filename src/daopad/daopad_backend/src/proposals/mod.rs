@@ -9,6 +9,7 @@ pub use unified::{
     OrbitOperation,
     UnifiedProposal,
 };
+pub(crate) use unified::ensure_typed_proposal;
 
 // Re-export types
 pub use types::{
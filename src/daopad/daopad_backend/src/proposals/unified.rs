@@ -8,6 +8,7 @@ const ADMIN_CANISTER_ID: &str = "odkrm-viaaa-aaaap-qp2oq-cai";
 
 // Constants
 const MINIMUM_VP_FOR_PROPOSAL: u64 = 10_000; // Same as orbit link proposals
+const MINIMUM_VP_FOR_CANISTER_PROPOSAL: u64 = 25_000; // Higher due to risk
 
 /// Helper function to format Orbit Error with details
 fn format_orbit_error_details(error: &crate::api::orbit_requests::Error) -> Option<String> {
@@ -32,6 +33,23 @@ pub struct TransferDetails {
     pub description: String,     // Transfer description for proposal
 }
 
+/// Address book entry a transfer pays to, shown to voters
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TransferDestination {
+    pub address_book_entry_id: String,
+    pub name: String,          // The entry's address owner
+    pub labels: Vec<String>,
+}
+
+/// SHA-256 hashes (hex) of the code and argument a request would run
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PayloadHashes {
+    pub wasm_module_hash: Option<String>,
+    pub wasm_module_size: Option<u64>,
+    pub arg_hash: Option<String>,
+    pub source_commit: Option<String>, // Link to the commit the wasm was built from
+}
+
 /// Unified proposal type for all Orbit operations
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UnifiedProposal {
@@ -49,6 +67,10 @@ pub struct UnifiedProposal {
     pub status: ProposalStatus,
     // Optional fields for specific operations
     pub transfer_details: Option<TransferDetails>,
+    pub transfer_destination: Option<TransferDestination>,
+    pub threshold_override: Option<u8>,
+    pub payload_hashes: Option<PayloadHashes>,
+    pub security_impact: Option<SecurityImpact>,
 }
 
 /// All possible Orbit operations in one enum
@@ -150,6 +172,18 @@ impl OrbitOperationType {
         }
     }
 
+    /// Voting power needed to propose this operation
+    pub fn minimum_voting_power(&self) -> u64 {
+        match self {
+            Self::CreateExternalCanister | Self::ConfigureExternalCanister
+            | Self::ChangeExternalCanister | Self::CallExternalCanister
+            | Self::FundExternalCanister | Self::MonitorExternalCanister
+            | Self::SnapshotExternalCanister | Self::RestoreExternalCanister
+            | Self::PruneExternalCanister => MINIMUM_VP_FOR_CANISTER_PROPOSAL,
+            _ => MINIMUM_VP_FOR_PROPOSAL,
+        }
+    }
+
     /// Get voting duration in hours for this operation type
     pub fn voting_duration_hours(&self) -> u64 {
         match self {
@@ -224,27 +258,165 @@ impl OrbitOperationType {
             _ => Self::Other(operation_type.to_string()),
         }
     }
+
+    /// Name the admin canister parses back with `from_string`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::AddAccount => "AddAccount",
+            Self::EditAccount => "EditAccount",
+            Self::AddUser => "AddUser",
+            Self::EditUser => "EditUser",
+            Self::RemoveUser => "RemoveUser",
+            Self::AddUserGroup => "AddUserGroup",
+            Self::EditUserGroup => "EditUserGroup",
+            Self::RemoveUserGroup => "RemoveUserGroup",
+            Self::CreateExternalCanister => "CreateExternalCanister",
+            Self::ConfigureExternalCanister => "ConfigureExternalCanister",
+            Self::ChangeExternalCanister => "ChangeExternalCanister",
+            Self::CallExternalCanister => "CallExternalCanister",
+            Self::FundExternalCanister => "FundExternalCanister",
+            Self::MonitorExternalCanister => "MonitorExternalCanister",
+            Self::SnapshotExternalCanister => "SnapshotExternalCanister",
+            Self::RestoreExternalCanister => "RestoreExternalCanister",
+            Self::PruneExternalCanister => "PruneExternalCanister",
+            Self::SystemUpgrade => "SystemUpgrade",
+            Self::SystemRestore => "SystemRestore",
+            Self::SetDisasterRecovery => "SetDisasterRecovery",
+            Self::ManageSystemInfo => "ManageSystemInfo",
+            Self::EditPermission => "EditPermission",
+            Self::AddRequestPolicy => "AddRequestPolicy",
+            Self::EditRequestPolicy => "EditRequestPolicy",
+            Self::RemoveRequestPolicy => "RemoveRequestPolicy",
+            Self::AddAsset => "AddAsset",
+            Self::EditAsset => "EditAsset",
+            Self::RemoveAsset => "RemoveAsset",
+            Self::AddNamedRule => "AddNamedRule",
+            Self::EditNamedRule => "EditNamedRule",
+            Self::RemoveNamedRule => "RemoveNamedRule",
+            Self::AddAddressBookEntry => "AddAddressBookEntry",
+            Self::EditAddressBookEntry => "EditAddressBookEntry",
+            Self::RemoveAddressBookEntry => "RemoveAddressBookEntry",
+            Self::Other(name) => name,
+        }
+    }
 }
 
 use crate::proposals::types::{ProposalError, ProposalId, ProposalStatus};
+use crate::types::orbit::{
+    AddAccountOperationInput, CallExternalCanisterOperationInput, ChangeExternalCanisterKind, AddAssetOperationInput, AddNamedRuleOperationInput,
+    AddRequestPolicyOperationInput, AddUserGroupOperationInput, AddUserOperationInput,
+    ChangeExternalCanisterOperationInput, ConfigureExternalCanisterOperationInput,
+    CreateExternalCanisterOperationInput, EditAccountOperationInput, EditAssetOperationInput,
+    EditNamedRuleOperationInput, EditPermissionOperationInput, EditRequestPolicyOperationInput,
+    EditUserGroupOperationInput, EditUserOperationInput, FundExternalCanisterOperationInput,
+    MonitorExternalCanisterOperationInput, PruneExternalCanisterOperationInput,
+    RemoveAssetOperationInput, RemoveNamedRuleOperationInput, RemoveRequestPolicyOperationInput,
+    RemoveUserGroupOperationInput, RequestOperation, RestoreExternalCanisterOperationInput,
+    SetDisasterRecoveryOperationInput, SnapshotExternalCanisterOperationInput,
+    SystemUpgradeOperationInput, UserStatus,
+};
+use crate::types::UnlistedTransferPolicy;
+use crate::api::security::simulation::simulate_security_impact;
+use crate::api::security::SecurityImpact;
+use crate::api::{
+    asset_blockchain, list_station_address_book, unlisted_transfer_policy, upload_total_size, AddAddressBookEntryOperationInput, EditAddressBookEntryOperationInput,
+    RemoveAddressBookEntryOperationInput,
+};
+
+const OPERATOR_GROUP_ID: &str = "00000000-0000-4000-8000-000000000001";
 
 /// Enum for all possible Orbit operations
+/// Each variant carries the Orbit input it submits, except for the
+/// DAOPad shorthands (Transfer, RemoveUser, RemoveAdmin)
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum OrbitOperation {
+    // Treasury
     Transfer(TransferDetails),
-    EditUser { user_id: String, groups: Vec<String>, name: Option<String> },
-    AddAsset { asset: AssetDetails },
-    RemoveAdmin { user_id: String, user_name: String },
-    // Add more operation types as needed
+    AddAccount(AddAccountOperationInput),
+    EditAccount(EditAccountOperationInput),
+
+    // Users and groups
+    AddUser(AddUserOperationInput),
+    EditUser(EditUserOperationInput),
+    RemoveUser { user_id: String },  // Orbit has no delete; the user is made inactive
+    RemoveAdmin { user_id: String, user_name: String },  // Leaves the user in the operator group only
+    AddUserGroup(AddUserGroupOperationInput),
+    EditUserGroup(EditUserGroupOperationInput),
+    RemoveUserGroup(RemoveUserGroupOperationInput),
+
+    // Governance configuration
+    EditPermission(EditPermissionOperationInput),
+    AddRequestPolicy(AddRequestPolicyOperationInput),
+    EditRequestPolicy(EditRequestPolicyOperationInput),
+    RemoveRequestPolicy(RemoveRequestPolicyOperationInput),
+    AddNamedRule(AddNamedRuleOperationInput),
+    EditNamedRule(EditNamedRuleOperationInput),
+    RemoveNamedRule(RemoveNamedRuleOperationInput),
+
+    // Assets
+    AddAsset(AddAssetOperationInput),
+    EditAsset(EditAssetOperationInput),
+    RemoveAsset(RemoveAssetOperationInput),
+
+    // Address book
+    AddAddressBookEntry(AddAddressBookEntryOperationInput),
+    EditAddressBookEntry(EditAddressBookEntryOperationInput),
+    RemoveAddressBookEntry(RemoveAddressBookEntryOperationInput),
+
+    // External canisters
+    CreateExternalCanister(CreateExternalCanisterOperationInput),
+    ChangeExternalCanister(ChangeExternalCanisterOperationInput),
+    ConfigureExternalCanister(ConfigureExternalCanisterOperationInput),
+    CallExternalCanister(CallExternalCanisterOperationInput),
+    FundExternalCanister(FundExternalCanisterOperationInput),
+    MonitorExternalCanister(MonitorExternalCanisterOperationInput),
+    SnapshotExternalCanister(SnapshotExternalCanisterOperationInput),
+    RestoreExternalCanister(RestoreExternalCanisterOperationInput),
+    PruneExternalCanister(PruneExternalCanisterOperationInput),
+
+    // System
+    SystemUpgrade(SystemUpgradeOperationInput),
+    SetDisasterRecovery(SetDisasterRecoveryOperationInput),
 }
 
 impl OrbitOperation {
     pub fn to_type(&self) -> OrbitOperationType {
         match self {
             OrbitOperation::Transfer(_) => OrbitOperationType::Transfer,
-            OrbitOperation::EditUser { .. } => OrbitOperationType::EditUser,
-            OrbitOperation::AddAsset { .. } => OrbitOperationType::AddAsset,
+            OrbitOperation::AddAccount(_) => OrbitOperationType::AddAccount,
+            OrbitOperation::EditAccount(_) => OrbitOperationType::EditAccount,
+            OrbitOperation::AddUser(_) => OrbitOperationType::AddUser,
+            OrbitOperation::EditUser(_) => OrbitOperationType::EditUser,
+            OrbitOperation::RemoveUser { .. } => OrbitOperationType::RemoveUser,
             OrbitOperation::RemoveAdmin { .. } => OrbitOperationType::EditUser,
+            OrbitOperation::AddUserGroup(_) => OrbitOperationType::AddUserGroup,
+            OrbitOperation::EditUserGroup(_) => OrbitOperationType::EditUserGroup,
+            OrbitOperation::RemoveUserGroup(_) => OrbitOperationType::RemoveUserGroup,
+            OrbitOperation::EditPermission(_) => OrbitOperationType::EditPermission,
+            OrbitOperation::AddRequestPolicy(_) => OrbitOperationType::AddRequestPolicy,
+            OrbitOperation::EditRequestPolicy(_) => OrbitOperationType::EditRequestPolicy,
+            OrbitOperation::RemoveRequestPolicy(_) => OrbitOperationType::RemoveRequestPolicy,
+            OrbitOperation::AddNamedRule(_) => OrbitOperationType::AddNamedRule,
+            OrbitOperation::EditNamedRule(_) => OrbitOperationType::EditNamedRule,
+            OrbitOperation::RemoveNamedRule(_) => OrbitOperationType::RemoveNamedRule,
+            OrbitOperation::AddAsset(_) => OrbitOperationType::AddAsset,
+            OrbitOperation::EditAsset(_) => OrbitOperationType::EditAsset,
+            OrbitOperation::RemoveAsset(_) => OrbitOperationType::RemoveAsset,
+            OrbitOperation::AddAddressBookEntry(_) => OrbitOperationType::AddAddressBookEntry,
+            OrbitOperation::EditAddressBookEntry(_) => OrbitOperationType::EditAddressBookEntry,
+            OrbitOperation::RemoveAddressBookEntry(_) => OrbitOperationType::RemoveAddressBookEntry,
+            OrbitOperation::CreateExternalCanister(_) => OrbitOperationType::CreateExternalCanister,
+            OrbitOperation::ChangeExternalCanister(_) => OrbitOperationType::ChangeExternalCanister,
+            OrbitOperation::ConfigureExternalCanister(_) => OrbitOperationType::ConfigureExternalCanister,
+            OrbitOperation::CallExternalCanister(_) => OrbitOperationType::CallExternalCanister,
+            OrbitOperation::FundExternalCanister(_) => OrbitOperationType::FundExternalCanister,
+            OrbitOperation::MonitorExternalCanister(_) => OrbitOperationType::MonitorExternalCanister,
+            OrbitOperation::SnapshotExternalCanister(_) => OrbitOperationType::SnapshotExternalCanister,
+            OrbitOperation::RestoreExternalCanister(_) => OrbitOperationType::RestoreExternalCanister,
+            OrbitOperation::PruneExternalCanister(_) => OrbitOperationType::PruneExternalCanister,
+            OrbitOperation::SystemUpgrade(_) => OrbitOperationType::SystemUpgrade,
+            OrbitOperation::SetDisasterRecovery(_) => OrbitOperationType::SetDisasterRecovery,
         }
     }
 
@@ -254,20 +426,150 @@ impl OrbitOperation {
             _ => None,
         }
     }
-}
 
-#[derive(CandidType, Deserialize, Clone, Debug)]
-pub struct AssetDetails {
-    pub symbol: String,
-    pub name: String,
-    pub decimals: u8,
+    /// Hashes of any wasm module or argument blob, so voters can check what runs
+    pub fn payload_hashes(&self) -> Option<PayloadHashes> {
+        let (module, arg) = match self {
+            OrbitOperation::ChangeExternalCanister(input) => match &input.kind {
+                ChangeExternalCanisterKind::Upgrade(upgrade) => (Some(&upgrade.wasm_module), upgrade.arg.as_ref()),
+                _ => return None,
+            },
+            OrbitOperation::CallExternalCanister(input) => (None, input.arg.as_ref()),
+            // With extra chunks the inline module is only the first chunk;
+            // Orbit checks the assembled module against module_extra_chunks
+            OrbitOperation::SystemUpgrade(SystemUpgradeOperationInput {
+                module_extra_chunks: Some(extra),
+                arg,
+                ..
+            }) => {
+                return Some(PayloadHashes {
+                    wasm_module_hash: Some(hex::encode(&extra.wasm_module_hash)),
+                    wasm_module_size: upload_total_size(&extra.extra_chunks_key),
+                    arg_hash: arg.as_ref().map(|a| sha256_hex(a)),
+                    source_commit: None,
+                });
+            }
+            OrbitOperation::SystemUpgrade(input) => (Some(&input.module), input.arg.as_ref()),
+            _ => return None,
+        };
+
+        Some(PayloadHashes {
+            wasm_module_hash: module.map(|m| sha256_hex(m)),
+            wasm_module_size: module.map(|m| m.len() as u64),
+            arg_hash: arg.map(|a| sha256_hex(a)),
+            source_commit: None,
+        })
+    }
+
+    /// Title shown on the Orbit request
+    fn title(&self) -> String {
+        match self {
+            OrbitOperation::Transfer(details) => details.title.clone(),
+            OrbitOperation::AddAccount(input) => format!("Add account {}", input.name),
+            OrbitOperation::EditAccount(input) => format!("Edit account {}", input.account_id),
+            OrbitOperation::AddUser(input) => format!("Add user {}", input.name),
+            OrbitOperation::EditUser(input) => format!("Edit user {}", input.id),
+            OrbitOperation::RemoveUser { user_id } => format!("Remove user {}", user_id),
+            OrbitOperation::RemoveAdmin { user_name, .. } => format!("Remove {} from Admin group", user_name),
+            OrbitOperation::AddUserGroup(input) => format!("Add user group {}", input.name),
+            OrbitOperation::EditUserGroup(input) => format!("Edit user group {}", input.user_group_id),
+            OrbitOperation::RemoveUserGroup(input) => format!("Remove user group {}", input.user_group_id),
+            OrbitOperation::EditPermission(input) => format!(
+                "Edit permission {}",
+                crate::api::security::security_utils::format_resource_for_reference(&input.resource)
+            ),
+            OrbitOperation::AddRequestPolicy(input) => format!(
+                "Add {} approval policy",
+                crate::api::security::format_request_specifier(&input.specifier)
+            ),
+            OrbitOperation::EditRequestPolicy(input) => format!("Edit approval policy {}", input.policy_id),
+            OrbitOperation::RemoveRequestPolicy(input) => format!("Remove approval policy {}", input.policy_id),
+            OrbitOperation::AddNamedRule(input) => format!("Add approval rule {}", input.name),
+            OrbitOperation::EditNamedRule(input) => format!("Edit approval rule {}", input.named_rule_id),
+            OrbitOperation::RemoveNamedRule(input) => format!("Remove approval rule {}", input.named_rule_id),
+            OrbitOperation::AddAsset(input) => format!("Add {} Asset", input.symbol),
+            OrbitOperation::EditAsset(input) => format!("Edit Asset {}", input.asset_id),
+            OrbitOperation::RemoveAsset(input) => format!("Remove Asset {}", input.asset_id),
+            OrbitOperation::AddAddressBookEntry(input) => format!("Add address book entry for {}", input.address_owner),
+            OrbitOperation::CreateExternalCanister(input) => format!("Add canister {}", input.name),
+            OrbitOperation::CallExternalCanister(input) => format!("Call canister method {}", input.execution_method.method_name),
+            other => format!("{} request", other.to_type().as_str()),
+        }
+    }
+
+    fn into_request(self) -> RequestOperation {
+        match self {
+            OrbitOperation::Transfer(details) => RequestOperation::Transfer(transfer_input(details)),
+            OrbitOperation::AddAccount(input) => RequestOperation::AddAccount(input),
+            OrbitOperation::EditAccount(input) => RequestOperation::EditAccount(input),
+            OrbitOperation::AddUser(input) => RequestOperation::AddUser(input),
+            OrbitOperation::EditUser(input) => RequestOperation::EditUser(input),
+            OrbitOperation::RemoveUser { user_id } => RequestOperation::EditUser(EditUserOperationInput {
+                id: user_id,
+                name: None,
+                identities: None,
+                groups: Some(vec![]),
+                status: Some(UserStatus::Inactive),
+                cancel_pending_requests: Some(true),
+            }),
+            OrbitOperation::RemoveAdmin { user_id, user_name } => RequestOperation::EditUser(EditUserOperationInput {
+                id: user_id,
+                name: Some(user_name),
+                identities: None,
+                groups: Some(vec![OPERATOR_GROUP_ID.to_string()]),
+                status: None,
+                cancel_pending_requests: None,
+            }),
+            OrbitOperation::AddUserGroup(input) => RequestOperation::AddUserGroup(input),
+            OrbitOperation::EditUserGroup(input) => RequestOperation::EditUserGroup(input),
+            OrbitOperation::RemoveUserGroup(input) => RequestOperation::RemoveUserGroup(input),
+            OrbitOperation::EditPermission(input) => RequestOperation::EditPermission(input),
+            OrbitOperation::AddRequestPolicy(input) => RequestOperation::AddRequestPolicy(input),
+            OrbitOperation::EditRequestPolicy(input) => RequestOperation::EditRequestPolicy(input),
+            OrbitOperation::RemoveRequestPolicy(input) => RequestOperation::RemoveRequestPolicy(input),
+            OrbitOperation::AddNamedRule(input) => RequestOperation::AddNamedRule(input),
+            OrbitOperation::EditNamedRule(input) => RequestOperation::EditNamedRule(input),
+            OrbitOperation::RemoveNamedRule(input) => RequestOperation::RemoveNamedRule(input),
+            OrbitOperation::AddAsset(input) => RequestOperation::AddAsset(input),
+            OrbitOperation::EditAsset(input) => RequestOperation::EditAsset(input),
+            OrbitOperation::RemoveAsset(input) => RequestOperation::RemoveAsset(input),
+            OrbitOperation::AddAddressBookEntry(input) => RequestOperation::AddAddressBookEntry(input),
+            OrbitOperation::EditAddressBookEntry(input) => RequestOperation::EditAddressBookEntry(input),
+            OrbitOperation::RemoveAddressBookEntry(input) => RequestOperation::RemoveAddressBookEntry(input),
+            OrbitOperation::CreateExternalCanister(input) => RequestOperation::CreateExternalCanister(input),
+            OrbitOperation::ChangeExternalCanister(input) => RequestOperation::ChangeExternalCanister(input),
+            OrbitOperation::ConfigureExternalCanister(input) => RequestOperation::ConfigureExternalCanister(input),
+            OrbitOperation::CallExternalCanister(input) => RequestOperation::CallExternalCanister(input),
+            OrbitOperation::FundExternalCanister(input) => RequestOperation::FundExternalCanister(input),
+            OrbitOperation::MonitorExternalCanister(input) => RequestOperation::MonitorExternalCanister(input),
+            OrbitOperation::SnapshotExternalCanister(input) => RequestOperation::SnapshotExternalCanister(input),
+            OrbitOperation::RestoreExternalCanister(input) => RequestOperation::RestoreExternalCanister(input),
+            OrbitOperation::PruneExternalCanister(input) => RequestOperation::PruneExternalCanister(input),
+            OrbitOperation::SystemUpgrade(input) => RequestOperation::SystemUpgrade(input),
+            OrbitOperation::SetDisasterRecovery(input) => RequestOperation::SetDisasterRecovery(input),
+        }
+    }
 }
 
 /// Create any Orbit request with auto-proposal
+/// If the admin canister can't open the proposal, the Orbit request is
+/// cancelled so no request is left behind that nobody can vote on
 #[update]
 pub async fn create_orbit_request_with_proposal(
     token_id: Principal,
     operation: OrbitOperation,
+) -> Result<String, ProposalError> {
+    create_governed_request(token_id, operation, None, None, None).await
+}
+
+/// Same as `create_orbit_request_with_proposal`, with the proposer's own
+/// title and summary on the Orbit request, and the source commit of any wasm
+pub(crate) async fn create_governed_request(
+    token_id: Principal,
+    operation: OrbitOperation,
+    title: Option<String>,
+    summary: Option<String>,
+    source_commit: Option<String>,
 ) -> Result<String, ProposalError> {
     // 1. Validate caller has minimum VP
     let caller = ic_cdk::caller();
@@ -275,10 +577,11 @@ pub async fn create_orbit_request_with_proposal(
         .await
         .map_err(|_| ProposalError::NoVotingPower)?;
 
-    if voting_power < MINIMUM_VP_FOR_PROPOSAL {
+    let required = operation.to_type().minimum_voting_power();
+    if voting_power < required {
         return Err(ProposalError::InsufficientVotingPowerToPropose {
             current: voting_power,
-            required: MINIMUM_VP_FOR_PROPOSAL,
+            required,
         });
     }
 
@@ -291,71 +594,175 @@ pub async fn create_orbit_request_with_proposal(
             .ok_or(ProposalError::NoStationLinked(token_id))
     })?;
 
-    // 3. Create appropriate Orbit request based on operation type
-    let orbit_request_id = match &operation {
-        OrbitOperation::Transfer(details) => {
-            create_transfer_request_in_orbit(station_id, details.clone()).await?
-        },
-        OrbitOperation::EditUser { user_id, groups, name } => {
-            create_edit_user_request_in_orbit(station_id, user_id.clone(), groups.clone(), name.clone()).await?
-        },
-        OrbitOperation::RemoveAdmin { user_id, user_name } => {
-            // Remove admin is just EditUser with operator group only
-            create_edit_user_request_in_orbit(
-                station_id,
-                user_id.clone(),
-                vec!["00000000-0000-4000-8000-000000000001".to_string()], // Operator group
-                Some(user_name.clone())
-            ).await?
-        },
-        OrbitOperation::AddAsset { asset } => {
-            create_add_asset_request_in_orbit(station_id, asset.clone()).await?
-        },
+    // 3. Check a transfer's destination against the address book
+    let transfer = match operation.transfer_details() {
+        Some(details) => Some(review_transfer_destination(token_id, station_id, details).await?),
+        None => None,
     };
 
-    // 4. Create proposal in admin canister
-    let operation_type_str = match &operation {
-        OrbitOperation::Transfer(_) => "Transfer",
-        OrbitOperation::EditUser { .. } => "EditUser",
-        OrbitOperation::AddAsset { .. } => "AddAsset",
-        OrbitOperation::RemoveAdmin { .. } => "EditUser",
-    }.to_string();
+    // 4. Simulate permission and policy edits so voters see the security impact
+    // A failed simulation shouldn't block the proposal
+    let security_impact = match &operation {
+        OrbitOperation::EditPermission(_) | OrbitOperation::EditRequestPolicy(_) => {
+            simulate_security_impact(station_id, &operation).await.ok()
+        }
+        _ => None,
+    };
+
+    // 5. Create the Orbit request
+    let operation_type = operation.to_type();
+    let payload_hashes = operation
+        .payload_hashes()
+        .map(|hashes| PayloadHashes { source_commit, ..hashes });
+    let title = title.unwrap_or_else(|| operation.title());
+    let summary = summary.unwrap_or_else(|| match &operation {
+        OrbitOperation::Transfer(details) => details.description.clone(),
+        _ => format!("Community proposal via DAOPad ({}% voting power to pass)", operation_type.voting_threshold()),
+    });
+    let orbit_request_id =
+        submit_orbit_request(station_id, operation.into_request(), title, summary).await?;
+
+    // 6. Create proposal in admin canister, undoing the request if that fails
+    let proposal = match (transfer, payload_hashes, security_impact) {
+        (Some((details, destination, threshold_override)), _, _) => {
+            ensure_transfer_proposal(token_id, orbit_request_id.clone(), details, destination, threshold_override).await
+        }
+        (None, Some(hashes), _) => {
+            ensure_hashed_proposal(token_id, orbit_request_id.clone(), operation_type.as_str().to_string(), hashes).await
+        }
+        (None, None, Some(impact)) => {
+            ensure_simulated_proposal(token_id, orbit_request_id.clone(), operation_type.as_str().to_string(), impact).await
+        }
+        (None, None, None) => {
+            ensure_typed_proposal(token_id, orbit_request_id.clone(), operation_type.as_str().to_string()).await
+        }
+    };
+    match proposal {
+        Ok(_) => Ok(orbit_request_id),
+        Err(e) => {
+            let cleanup = match cancel_orbit_request(station_id, &orbit_request_id).await {
+                Ok(()) => "the Orbit request was cancelled".to_string(),
+                Err(cancel_err) => format!(
+                    "cancelling Orbit request {} also failed: {}",
+                    orbit_request_id, cancel_err
+                ),
+            };
+            Err(ProposalError::Custom(format!(
+                "Admin proposal creation failed: {}; {}",
+                e, cleanup
+            )))
+        }
+    }
+}
 
+/// Create proposal in admin canister for an Orbit request
+#[update]
+pub async fn ensure_proposal_for_request(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+) -> Result<ProposalId, ProposalError> {
     let admin_principal = Principal::from_text(ADMIN_CANISTER_ID)
         .map_err(|e| ProposalError::Custom(format!("Invalid admin canister ID: {}", e)))?;
 
-    let result: Result<(Result<String, String>,), _> = ic_cdk::call(
+    let result: Result<(Result<ProposalId, ProposalError>,), _> = ic_cdk::call(
         admin_principal,
-        "create_proposal",
-        (token_id, orbit_request_id.clone(), operation_type_str)
+        "ensure_proposal_for_request",
+        (token_id, orbit_request_id, request_type_str)
     ).await;
 
     match result {
-        Ok((Ok(_),)) => Ok(orbit_request_id),
-        Ok((Err(e),)) => Err(ProposalError::Custom(format!("Admin proposal creation failed: {}", e))),
+        Ok((result,)) => result,
         Err((code, msg)) => Err(ProposalError::Custom(format!("Admin call failed: {:?} - {}", code, msg))),
     }
 }
 
-/// Create proposal in admin canister for an Orbit request
-#[update]
-pub async fn ensure_proposal_for_request(
+/// Open the proposal for a request this canister just submitted, with its type
+/// The admin canister only takes the type from the backend, so a proposal
+/// someone opened first can't keep a lower threshold
+pub(crate) async fn ensure_typed_proposal(
     token_id: Principal,
     orbit_request_id: String,
     request_type_str: String,
-) -> Result<String, ProposalError> {
+) -> Result<ProposalId, ProposalError> {
     let admin_principal = Principal::from_text(ADMIN_CANISTER_ID)
         .map_err(|e| ProposalError::Custom(format!("Invalid admin canister ID: {}", e)))?;
 
-    let result: Result<(Result<String, String>,), _> = ic_cdk::call(
+    let result: Result<(Result<ProposalId, ProposalError>,), _> = ic_cdk::call(
         admin_principal,
-        "create_proposal",
+        "ensure_typed_proposal",
         (token_id, orbit_request_id, request_type_str)
     ).await;
 
     match result {
-        Ok((Ok(proposal_id),)) => Ok(proposal_id),
-        Ok((Err(e),)) => Err(ProposalError::Custom(e)),
+        Ok((result,)) => result,
+        Err((code, msg)) => Err(ProposalError::Custom(format!("Admin call failed: {:?} - {}", code, msg))),
+    }
+}
+
+/// Open a transfer proposal showing voters where the funds go
+async fn ensure_transfer_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    details: TransferDetails,
+    destination: Option<TransferDestination>,
+    threshold_override: Option<u8>,
+) -> Result<ProposalId, ProposalError> {
+    let admin_principal = Principal::from_text(ADMIN_CANISTER_ID)
+        .map_err(|e| ProposalError::Custom(format!("Invalid admin canister ID: {}", e)))?;
+
+    let result: Result<(Result<ProposalId, ProposalError>,), _> = ic_cdk::call(
+        admin_principal,
+        "ensure_transfer_proposal",
+        (token_id, orbit_request_id, details, destination, threshold_override)
+    ).await;
+
+    match result {
+        Ok((result,)) => result,
+        Err((code, msg)) => Err(ProposalError::Custom(format!("Admin call failed: {:?} - {}", code, msg))),
+    }
+}
+
+/// Open a proposal showing voters the hashes of the code and argument it runs
+async fn ensure_hashed_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+    hashes: PayloadHashes,
+) -> Result<ProposalId, ProposalError> {
+    let admin_principal = Principal::from_text(ADMIN_CANISTER_ID)
+        .map_err(|e| ProposalError::Custom(format!("Invalid admin canister ID: {}", e)))?;
+
+    let result: Result<(Result<ProposalId, ProposalError>,), _> = ic_cdk::call(
+        admin_principal,
+        "ensure_hashed_proposal",
+        (token_id, orbit_request_id, request_type_str, hashes)
+    ).await;
+
+    match result {
+        Ok((result,)) => result,
+        Err((code, msg)) => Err(ProposalError::Custom(format!("Admin call failed: {:?} - {}", code, msg))),
+    }
+}
+
+/// Open a proposal showing voters how the change moves the security score
+async fn ensure_simulated_proposal(
+    token_id: Principal,
+    orbit_request_id: String,
+    request_type_str: String,
+    impact: SecurityImpact,
+) -> Result<ProposalId, ProposalError> {
+    let admin_principal = Principal::from_text(ADMIN_CANISTER_ID)
+        .map_err(|e| ProposalError::Custom(format!("Invalid admin canister ID: {}", e)))?;
+
+    let result: Result<(Result<ProposalId, ProposalError>,), _> = ic_cdk::call(
+        admin_principal,
+        "ensure_simulated_proposal",
+        (token_id, orbit_request_id, request_type_str, impact)
+    ).await;
+
+    match result {
+        Ok((result,)) => result,
         Err((code, msg)) => Err(ProposalError::Custom(format!("Admin call failed: {:?} - {}", code, msg))),
     }
 }
@@ -364,16 +771,15 @@ pub async fn ensure_proposal_for_request(
 // Internal helper functions
 // ============================================================================
 
-async fn create_transfer_request_in_orbit(
-    station_id: Principal,
-    details: TransferDetails,
-) -> Result<String, ProposalError> {
-    use crate::api::CreateRequestResult;
-    use crate::types::orbit::{
-        RequestOperation, SubmitRequestInput, TransferMetadata, TransferOperationInput,
-    };
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn transfer_input(details: TransferDetails) -> crate::types::orbit::TransferOperationInput {
+    use crate::types::orbit::{TransferMetadata, TransferOperationInput};
 
-    let transfer_op = TransferOperationInput {
+    TransferOperationInput {
         from_account_id: details.from_account_id,
         from_asset_id: details.from_asset_id,
         with_standard: "icrc1".to_string(),
@@ -389,12 +795,32 @@ async fn create_transfer_request_in_orbit(
             vec![]
         },
         network: None,
-    };
+    }
+}
+
+pub(crate) async fn create_transfer_request_in_orbit(
+    station_id: Principal,
+    details: TransferDetails,
+) -> Result<String, ProposalError> {
+    let title = details.title.clone();
+    let summary = details.description.clone();
+    submit_orbit_request(station_id, RequestOperation::Transfer(transfer_input(details)), title, summary).await
+}
+
+/// Submit any operation to the station, returning the Orbit request ID
+async fn submit_orbit_request(
+    station_id: Principal,
+    operation: RequestOperation,
+    title: String,
+    summary: String,
+) -> Result<String, ProposalError> {
+    use crate::api::CreateRequestResult;
+    use crate::types::orbit::SubmitRequestInput;
 
     let request_input = SubmitRequestInput {
-        operation: RequestOperation::Transfer(transfer_op),
-        title: Some(details.title.clone()),
-        summary: Some(details.description.clone()),
+        operation,
+        title: Some(title),
+        summary: Some(summary),
         execution_plan: None,
     };
 
@@ -415,62 +841,125 @@ async fn create_transfer_request_in_orbit(
     }
 }
 
-async fn create_edit_user_request_in_orbit(
+/// Match a transfer's destination to the station's address book and apply the
+/// token's policy for unlisted addresses
+/// Returns the details with the matching entry and any raised threshold
+async fn review_transfer_destination(
+    token_id: Principal,
     station_id: Principal,
-    user_id: String,
-    groups: Vec<String>,
-    name: Option<String>,
-) -> Result<String, ProposalError> {
-    use crate::api::CreateRequestResult;
-    use crate::types::orbit::{
-        RequestOperation, SubmitRequestInput, EditUserOperationInput,
+    details: TransferDetails,
+) -> Result<(TransferDetails, Option<TransferDestination>, Option<u8>), ProposalError> {
+    let policy = unlisted_transfer_policy(token_id);
+
+    // An entry only lists the address on its own blockchain
+    let lookup = async {
+        let blockchain = asset_blockchain(station_id, &details.from_asset_id).await?;
+        let entries = list_station_address_book(station_id).await?;
+        Ok::<_, String>((blockchain, entries))
     };
-
-    // Create the EditUser operation input
-    let edit_user_op = EditUserOperationInput {
-        id: user_id.clone(),
-        name,
-        identities: None,  // Not changing identities
-        groups: Some(groups),
-        status: None,  // Not changing status
-        cancel_pending_requests: None,
+    let (blockchain, entries) = match lookup.await {
+        Ok(lookup) => lookup,
+        // Without a policy the destination is only informational
+        Err(_) if policy == UnlistedTransferPolicy::Allow => return Ok((details, None, None)),
+        Err(e) => return Err(ProposalError::Custom(format!("Can't check address book: {}", e))),
     };
 
-    let request_input = SubmitRequestInput {
-        operation: RequestOperation::EditUser(edit_user_op),
-        title: Some(format!("Edit user {}", user_id)),
-        summary: Some("Community-approved user modification".to_string()),
-        execution_plan: None,
+    let destination = entries
+        .into_iter()
+        .find(|e| e.address == details.to && e.blockchain == blockchain)
+        .map(|e| TransferDestination {
+            address_book_entry_id: e.id,
+            name: e.address_owner,
+            labels: e.labels,
+        });
+    if destination.is_some() {
+        return Ok((details, destination, None));
+    }
+
+    match policy {
+        UnlistedTransferPolicy::Allow => Ok((details, None, None)),
+        UnlistedTransferPolicy::RaiseThreshold(threshold) => Ok((details, None, Some(threshold))),
+        UnlistedTransferPolicy::Reject => Err(ProposalError::Custom(format!(
+            "{} is not in the address book; add it through an address book proposal first",
+            details.to
+        ))),
+    }
+}
+
+/// Cancel a request we created that can't be governed
+async fn cancel_orbit_request(station_id: Principal, request_id: &str) -> Result<(), String> {
+    use crate::types::orbit::{CancelRequestInput, CancelRequestResult};
+
+    let input = CancelRequestInput {
+        request_id: request_id.to_string(),
+        reason: Some("No DAOPad proposal could be created for this request".to_string()),
     };
 
-    let result: Result<(CreateRequestResult,), _> =
-        ic_cdk::call(station_id, "create_request", (request_input,)).await;
+    let result: Result<(CancelRequestResult,), _> =
+        ic_cdk::call(station_id, "cancel_request", (input,)).await;
 
     match result {
-        Ok((CreateRequestResult::Ok(response),)) => Ok(response.request.id),
-        Ok((CreateRequestResult::Err(e),)) => Err(ProposalError::OrbitError {
-            code: e.code.clone(),
-            message: e.message.clone().unwrap_or_else(|| "No message provided".to_string()),
-            details: format_orbit_error_details(&e),
-        }),
-        Err((code, msg)) => Err(ProposalError::IcCallFailed {
-            code: code as i32,
-            message: msg,
-        }),
+        Ok((CancelRequestResult::Ok(_),)) => Ok(()),
+        Ok((CancelRequestResult::Err(e),)) => Err(format!("Orbit error: {}", e)),
+        Err((code, msg)) => Err(format!("Call failed: {:?} - {}", code, msg)),
     }
 }
 
-async fn create_add_asset_request_in_orbit(
-    _station_id: Principal,
-    _asset: AssetDetails,
-) -> Result<String, ProposalError> {
-    // NOTE: AddAsset operation is not yet supported in RequestOperation enum
-    // The types exist in RequestOperationInput but not in RequestOperation
-    // This requires updating the Orbit type definitions
-    // For now, return an error indicating this operation is not yet supported
-
-    Err(ProposalError::Custom(
-        "AddAsset operation is not yet implemented in Orbit integration. \
-         The type definitions need to be updated to support asset management operations.".to_string()
-    ))
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::state::UPGRADE_UPLOADS;
+    use crate::types::orbit::{SystemUpgradeTarget, WasmModuleExtraChunks};
+    use crate::types::{StorableString, UpgradeUpload};
+
+    fn system_upgrade(module: Vec<u8>, module_extra_chunks: Option<WasmModuleExtraChunks>) -> OrbitOperation {
+        OrbitOperation::SystemUpgrade(SystemUpgradeOperationInput {
+            target: SystemUpgradeTarget::UpgradeStation,
+            module,
+            module_extra_chunks,
+            arg: Some(vec![1, 2, 3]),
+            take_backup_snapshot: None,
+        })
+    }
+
+    #[test]
+    fn inline_module_is_hashed_whole() {
+        let hashes = system_upgrade(vec![0; 10], None).payload_hashes().unwrap();
+        assert_eq!(hashes.wasm_module_hash, Some(sha256_hex(&[0; 10])));
+        assert_eq!(hashes.wasm_module_size, Some(10));
+        assert_eq!(hashes.arg_hash, Some(sha256_hex(&[1, 2, 3])));
+    }
+
+    #[test]
+    fn chunked_module_shows_the_full_module_hash() {
+        let full_hash = vec![0xab; 32];
+        UPGRADE_UPLOADS.with(|uploads| {
+            uploads.borrow_mut().insert(
+                StorableString("upload-1".to_string()),
+                UpgradeUpload {
+                    upload_id: "upload-1".to_string(),
+                    token_canister_id: Principal::anonymous(),
+                    uploader: Principal::anonymous(),
+                    target: SystemUpgradeTarget::UpgradeStation,
+                    expected_sha256: hex::encode(&full_hash),
+                    source_commit: String::new(),
+                    total_size: 3_000_000,
+                    arg: None,
+                    take_backup_snapshot: None,
+                    created_at: 0,
+                    orbit_request_id: None,
+                },
+            );
+        });
+        let extra = WasmModuleExtraChunks {
+            store_canister: Principal::anonymous(),
+            extra_chunks_key: "upload-1".to_string(),
+            wasm_module_hash: full_hash.clone(),
+        };
+
+        let hashes = system_upgrade(vec![0; 1_000], Some(extra)).payload_hashes().unwrap();
+        assert_eq!(hashes.wasm_module_hash, Some(hex::encode(&full_hash)));
+        assert_eq!(hashes.wasm_module_size, Some(3_000_000));
+        assert_eq!(hashes.arg_hash, Some(sha256_hex(&[1, 2, 3])));
+    }
+}
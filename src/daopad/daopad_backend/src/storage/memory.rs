@@ -6,13 +6,20 @@ use std::cell::RefCell;
 
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
 
-pub const KONG_LOCKER_PRINCIPALS_MEM_ID: MemoryId = MemoryId::new(0);
+// MemoryId 0 held the retired KONG_LOCKER_PRINCIPALS registry - do not reuse
 pub const ORBIT_STATIONS_MEM_ID: MemoryId = MemoryId::new(2); // Changed from 1 to 2 to get fresh storage
 pub const STATION_TO_TOKEN_MEM_ID: MemoryId = MemoryId::new(3); // Reverse mapping to prevent duplicate stations
 pub const AGREEMENT_SNAPSHOTS_MEM_ID: MemoryId = MemoryId::new(4); // Agreement snapshot storage
-                                                                // Note: Proposals use regular BTreeMap (not stable memory) since they're temporary (7-day expiry)
+pub const TRANSFER_ALLOWLIST_MEM_ID: MemoryId = MemoryId::new(5); // Per-token policy for transfers outside the address book
+pub const UPGRADE_UPLOADS_MEM_ID: MemoryId = MemoryId::new(6); // System upgrade wasm uploads being staged
+pub const UPGRADE_CHUNKS_MEM_ID: MemoryId = MemoryId::new(7); // Their chunks, keyed by (upload ID, index)
+pub const SECURITY_HISTORY_MEM_ID: MemoryId = MemoryId::new(8); // Scheduled audit results, keyed by (station, time)
+pub const SECURITY_ALERTS_MEM_ID: MemoryId = MemoryId::new(9); // Score drops and new Critical checks, keyed by (station, time)
+pub const DIVIDEND_REQUESTS_MEM_ID: MemoryId = MemoryId::new(10); // Orbit request ID of each dividend payout, keyed by (dividend, holder)
+// Note: Proposals use regular BTreeMap (not stable memory) since they're temporary (7-day expiry)
 
 pub const KONG_LOCKER_FACTORY: &str = "eazgb-giaaa-aaaap-qqc2q-cai";
+pub const ADMIN_CANISTER: &str = "odkrm-viaaa-aaaap-qp2oq-cai";
 
 thread_local! {
     pub static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
@@ -1,20 +1,18 @@
 use crate::proposals::types::{ProposalId, VoteChoice};
 use crate::storage::memory::{
-    Memory, AGREEMENT_SNAPSHOTS_MEM_ID, KONG_LOCKER_PRINCIPALS_MEM_ID, MEMORY_MANAGER,
-    ORBIT_STATIONS_MEM_ID, STATION_TO_TOKEN_MEM_ID,
+    Memory, AGREEMENT_SNAPSHOTS_MEM_ID, DIVIDEND_REQUESTS_MEM_ID, MEMORY_MANAGER, ORBIT_STATIONS_MEM_ID,
+    SECURITY_ALERTS_MEM_ID, SECURITY_HISTORY_MEM_ID, STATION_TO_TOKEN_MEM_ID, TRANSFER_ALLOWLIST_MEM_ID, UPGRADE_CHUNKS_MEM_ID,
+    UPGRADE_UPLOADS_MEM_ID,
+};
+use crate::types::{
+    AgreementSnapshot, SecurityAlert, SecurityAuditRecord, StorablePrincipal, StorableString,
+    UnlistedTransferPolicy, UpgradeUpload, VotingThresholds,
 };
-use crate::types::{AgreementSnapshot, StorablePrincipal, VotingThresholds};
 use ic_stable_structures::StableBTreeMap;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 
 thread_local! {
-    pub static KONG_LOCKER_PRINCIPALS: RefCell<StableBTreeMap<StorablePrincipal, StorablePrincipal, Memory>> = RefCell::new(
-        StableBTreeMap::init(
-            MEMORY_MANAGER.with(|m| m.borrow().get(KONG_LOCKER_PRINCIPALS_MEM_ID))
-        )
-    );
-
     // Simple mapping: token_principal -> orbit_station_principal
     pub static TOKEN_ORBIT_STATIONS: RefCell<StableBTreeMap<StorablePrincipal, StorablePrincipal, Memory>> = RefCell::new(
         StableBTreeMap::init(
@@ -39,6 +37,48 @@ thread_local! {
         )
     );
 
+    // What each token DAO does with transfers to addresses outside its address book
+    pub static TRANSFER_ALLOWLIST_POLICIES: RefCell<StableBTreeMap<StorablePrincipal, UnlistedTransferPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(TRANSFER_ALLOWLIST_MEM_ID))
+        )
+    );
+
+    // Staged system upgrade wasm uploads and their chunks
+    // Chunks are kept until the upgrade's proposal finishes so voters can fetch the module
+    pub static UPGRADE_UPLOADS: RefCell<StableBTreeMap<StorableString, UpgradeUpload, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(UPGRADE_UPLOADS_MEM_ID))
+        )
+    );
+
+    pub static UPGRADE_CHUNKS: RefCell<StableBTreeMap<(StorableString, u32), Vec<u8>, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(UPGRADE_CHUNKS_MEM_ID))
+        )
+    );
+
+    // Scheduled security audits of every linked station and the regressions they found
+    pub static SECURITY_HISTORY: RefCell<StableBTreeMap<(StorablePrincipal, u64), SecurityAuditRecord, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(SECURITY_HISTORY_MEM_ID))
+        )
+    );
+
+    pub static SECURITY_ALERTS: RefCell<StableBTreeMap<(StorablePrincipal, u64), SecurityAlert, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(SECURITY_ALERTS_MEM_ID))
+        )
+    );
+
+    // Orbit request created for each dividend payout, so a retried batch
+    // never pays a holder twice
+    pub static DIVIDEND_PAYOUT_REQUESTS: RefCell<StableBTreeMap<(StorableString, StorablePrincipal), StorableString, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(DIVIDEND_REQUESTS_MEM_ID))
+        )
+    );
+
     // Voting thresholds for each token's governance
     pub static VOTING_THRESHOLDS: RefCell<BTreeMap<StorablePrincipal, VotingThresholds>> = RefCell::new(BTreeMap::new());
 
@@ -21,3 +21,13 @@ impl Default for VotingThresholds {
         }
     }
 }
+
+/// What to do with a treasury transfer whose destination isn't in the
+/// station's address book
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum UnlistedTransferPolicy {
+    #[default]
+    Allow,
+    RaiseThreshold(u8), // Percent of voting power the proposal needs instead
+    Reject,
+}
@@ -31,3 +31,11 @@ pub struct TokenInfo {
     pub symbol: String,
     pub chain: String,
 }
+
+/// Tokens found in one page of lock canisters
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct LockedTokensPage {
+    pub tokens: Vec<TokenInfo>,
+    pub total_lock_canisters: u64,
+    pub next_offset: Option<u64>, // None once every lock canister has been scanned
+}
@@ -85,7 +85,7 @@ pub enum FetchAccountBalancesResult {
 }
 
 // Add account operation types
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct AddAccountOperationInput {
     pub name: String,
     pub assets: Vec<String>, // Asset UUIDs
@@ -98,14 +98,14 @@ pub struct AddAccountOperationInput {
 }
 
 // Change assets enum for EditAccount operation
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ChangeAssets {
     ReplaceWith { assets: Vec<String> }, // Asset UUIDs
     Change { add_assets: Vec<String>, remove_assets: Vec<String> },
 }
 
 // Edit account operation types
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct EditAccountOperationInput {
     pub account_id: String, // UUID
     pub name: Option<String>,
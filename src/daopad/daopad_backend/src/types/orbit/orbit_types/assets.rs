@@ -12,7 +12,7 @@ pub struct AssetMetadata {
     pub value: String,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct AddAssetOperationInput {
     pub blockchain: String,
     pub standards: Vec<String>,
@@ -22,14 +22,14 @@ pub struct AddAssetOperationInput {
     pub decimals: u32,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ChangeMetadata {
     ReplaceAllBy(Vec<AssetMetadata>),
     OverrideSpecifiedBy(Vec<AssetMetadata>),
     RemoveKeys(Vec<String>),
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct EditAssetOperationInput {
     pub asset_id: String,
     pub name: Option<String>,
@@ -39,7 +39,7 @@ pub struct EditAssetOperationInput {
     pub change_metadata: Option<ChangeMetadata>,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct RemoveAssetOperationInput {
     pub asset_id: String,
 }
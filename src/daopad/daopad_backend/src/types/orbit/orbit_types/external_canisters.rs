@@ -191,7 +191,7 @@ pub struct ExternalCanisterIdInput {
 }
 
 // Create external canister operation
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct CreateExternalCanisterOperationInput {
     pub kind: CreateExternalCanisterKind,
     pub name: String,
@@ -202,31 +202,31 @@ pub struct CreateExternalCanisterOperationInput {
     pub request_policies: ExternalCanisterRequestPolicies,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum CreateExternalCanisterKind {
     CreateNew(CreateExternalCanisterOptions),
     AddExisting { canister_id: Principal },
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct CreateExternalCanisterOptions {
     pub subnet_selection: Option<SubnetSelection>,
     pub initial_cycles: Option<Nat>,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum SubnetSelection {
     Subnet { subnet_id: Principal },
 }
 
 // Change external canister operation
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ChangeExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub kind: ChangeExternalCanisterKind,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ChangeExternalCanisterKind {
     Upgrade(UpgradeExternalCanisterInput),
     NativeSettings(NativeCanisterSettingsInput),
@@ -234,14 +234,14 @@ pub enum ChangeExternalCanisterKind {
     State(ExternalCanisterState),
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct UpgradeExternalCanisterInput {
     pub mode: CanisterInstallMode,
     pub wasm_module: Vec<u8>,
     pub arg: Option<Vec<u8>>,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum CanisterInstallMode {
     #[serde(rename = "install")]
     Install,
@@ -251,7 +251,7 @@ pub enum CanisterInstallMode {
     Upgrade,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct NativeCanisterSettingsInput {
     pub controllers: Option<Vec<Principal>>,
     pub compute_allocation: Option<Nat>,
@@ -262,7 +262,7 @@ pub struct NativeCanisterSettingsInput {
     pub wasm_memory_limit: Option<Nat>,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum LogVisibility {
     #[serde(rename = "public")]
     Public,
@@ -270,7 +270,7 @@ pub enum LogVisibility {
     Controllers,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ExternalCanisterSettingsInput {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -279,13 +279,13 @@ pub struct ExternalCanisterSettingsInput {
 }
 
 // Configure external canister operation
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct ConfigureExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub kind: ConfigureExternalCanisterKind,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum ConfigureExternalCanisterKind {
     Permissions(ExternalCanisterPermissions),
     RequestPolicies(ExternalCanisterRequestPolicies),
@@ -307,56 +307,65 @@ pub struct ExternalCanisterValidationMethodInput {
     pub arg: Option<Vec<u8>>,
 }
 
+/// Orbit's request to call a method on an external canister
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CallExternalCanisterOperationInput {
+    pub validation_method: Option<CanisterMethod>,
+    pub execution_method: CanisterMethod,
+    pub arg: Option<Vec<u8>>,
+    pub execution_method_cycles: Option<u64>,
+}
+
 // Fund external canister
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct FundExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub kind: FundExternalCanisterKind,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum FundExternalCanisterKind {
     Send(FundExternalCanisterSendCyclesInput),
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct FundExternalCanisterSendCyclesInput {
     pub cycles: Nat,
 }
 
 // Monitor external canister
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct MonitorExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub kind: MonitorExternalCanisterKind,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum MonitorExternalCanisterKind {
     Start(MonitorExternalCanisterStartInput),
     Stop,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct MonitorExternalCanisterStartInput {
     pub strategy: MonitoringStrategy,
     pub funding_amount: Nat,
 }
 
 // Snapshot operations
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct SnapshotExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub force: bool,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct RestoreExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub snapshot_id: String,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct PruneExternalCanisterOperationInput {
     pub external_canister_id: String,
     pub snapshot_ids: Vec<String>,
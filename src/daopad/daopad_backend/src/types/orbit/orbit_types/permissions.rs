@@ -123,7 +123,7 @@ pub struct GetPermissionInput {
 }
 
 // EditPermission operation input
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct EditPermissionOperationInput {
     pub resource: Resource,
     pub auth_scope: Option<AuthScope>,
@@ -4,12 +4,19 @@
 use candid::{CandidType, Nat};
 use serde::{Deserialize, Serialize};
 
-use super::system::Error;
-use super::users::{AddUserOperationInput, EditUserOperationInput, UserSpecifier};
+use super::system::{Error, SetDisasterRecoveryOperationInput, SystemUpgradeOperationInput};
+use super::users::{
+    AddUserGroupOperationInput, AddUserOperationInput, EditUserGroupOperationInput,
+    EditUserOperationInput, RemoveUserGroupOperationInput, UserSpecifier,
+};
 use super::accounts::{AccountMetadata, AddAccountOperationInput, EditAccountOperationInput};
 use super::assets::{AddAssetOperationInput, EditAssetOperationInput, RemoveAssetOperationInput};
 use super::permissions::{EditPermissionOperationInput, ResourceSpecifier};
 use super::external_canisters::*;
+use crate::api::{
+    AddAddressBookEntryOperationInput, EditAddressBookEntryOperationInput,
+    RemoveAddressBookEntryOperationInput,
+};
 
 #[derive(CandidType, Deserialize)]
 pub enum RequestOperationInput {
@@ -132,13 +139,31 @@ pub enum RequestPolicyRule {
 pub enum RequestOperation {
     AddUser(AddUserOperationInput),
     EditUser(EditUserOperationInput),
+    AddUserGroup(AddUserGroupOperationInput),
+    EditUserGroup(EditUserGroupOperationInput),
+    RemoveUserGroup(RemoveUserGroupOperationInput),
     EditPermission(EditPermissionOperationInput),
+    AddRequestPolicy(AddRequestPolicyOperationInput),
+    EditRequestPolicy(EditRequestPolicyOperationInput),
+    RemoveRequestPolicy(RemoveRequestPolicyOperationInput),
+    AddNamedRule(AddNamedRuleOperationInput),
+    EditNamedRule(EditNamedRuleOperationInput),
+    RemoveNamedRule(RemoveNamedRuleOperationInput),
     AddAccount(AddAccountOperationInput),
+    EditAccount(EditAccountOperationInput),
     Transfer(TransferOperationInput),
+    AddAsset(AddAssetOperationInput),
+    EditAsset(EditAssetOperationInput),
+    RemoveAsset(RemoveAssetOperationInput),
+    AddAddressBookEntry(AddAddressBookEntryOperationInput),
+    EditAddressBookEntry(EditAddressBookEntryOperationInput),
+    RemoveAddressBookEntry(RemoveAddressBookEntryOperationInput),
+    SystemUpgrade(SystemUpgradeOperationInput),
+    SetDisasterRecovery(SetDisasterRecoveryOperationInput),
     CreateExternalCanister(CreateExternalCanisterOperationInput),
     ChangeExternalCanister(ChangeExternalCanisterOperationInput),
     ConfigureExternalCanister(ConfigureExternalCanisterOperationInput),
-    CallExternalCanister(ExternalCanisterIdInput, ExternalCanisterCallerMethodCallInput),
+    CallExternalCanister(CallExternalCanisterOperationInput),
     FundExternalCanister(FundExternalCanisterOperationInput),
     MonitorExternalCanister(MonitorExternalCanisterOperationInput),
     SnapshotExternalCanister(SnapshotExternalCanisterOperationInput),
@@ -146,6 +171,59 @@ pub enum RequestOperation {
     PruneExternalCanister(PruneExternalCanisterOperationInput),
 }
 
+// Request policy operations
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddRequestPolicyOperationInput {
+    pub specifier: RequestSpecifier,
+    pub rule: RequestPolicyRule,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EditRequestPolicyOperationInput {
+    pub policy_id: String, // UUID
+    pub specifier: Option<RequestSpecifier>,
+    pub rule: Option<RequestPolicyRule>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemoveRequestPolicyOperationInput {
+    pub policy_id: String, // UUID
+}
+
+// Named rule operations (reusable approval rules referenced by NamedRule)
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddNamedRuleOperationInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub rule: RequestPolicyRule,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EditNamedRuleOperationInput {
+    pub named_rule_id: String, // UUID
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub rule: Option<RequestPolicyRule>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemoveNamedRuleOperationInput {
+    pub named_rule_id: String, // UUID
+}
+
+// Cancel a request that has not been approved yet
+#[derive(CandidType, Deserialize, Debug)]
+pub struct CancelRequestInput {
+    pub request_id: String,
+    pub reason: Option<String>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub enum CancelRequestResult {
+    Ok(candid::Reserved),
+    Err(Error),
+}
+
 // Submit request input with RequestOperation
 #[derive(CandidType, Deserialize, Debug)]
 pub struct SubmitRequestInput {
@@ -76,12 +76,40 @@ pub struct DisasterRecovery {
     pub user_group_name: Option<String>,
 }
 
-#[derive(CandidType, Serialize, Deserialize, Debug)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct DisasterRecoveryCommittee {
     pub user_group_id: String, // UUID as string
     pub quorum: u16,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SetDisasterRecoveryOperationInput {
+    pub committee: Option<DisasterRecoveryCommittee>,
+}
+
+// System upgrade operation
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SystemUpgradeTarget {
+    UpgradeStation,
+    UpgradeUpgrader,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct WasmModuleExtraChunks {
+    pub store_canister: Principal,
+    pub extra_chunks_key: String,
+    pub wasm_module_hash: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SystemUpgradeOperationInput {
+    pub target: SystemUpgradeTarget,
+    pub module: Vec<u8>,
+    pub module_extra_chunks: Option<WasmModuleExtraChunks>,
+    pub arg: Option<Vec<u8>>,
+    pub take_backup_snapshot: Option<bool>,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum CycleObtainStrategy {
     Disabled,
@@ -13,7 +13,7 @@ pub enum UserStatus {
     Inactive,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct AddUserOperationInput {
     pub name: String,
     pub identities: Vec<Principal>,
@@ -21,7 +21,7 @@ pub struct AddUserOperationInput {
     pub status: UserStatus,
 }
 
-#[derive(CandidType, Deserialize, Debug)]
+#[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct EditUserOperationInput {
     pub id: String, // UUID
     pub name: Option<String>,
@@ -31,6 +31,23 @@ pub struct EditUserOperationInput {
     pub cancel_pending_requests: Option<bool>,
 }
 
+// User group operations
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct AddUserGroupOperationInput {
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct EditUserGroupOperationInput {
+    pub user_group_id: String, // UUID
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RemoveUserGroupOperationInput {
+    pub user_group_id: String, // UUID
+}
+
 #[derive(CandidType, Deserialize, Serialize, Debug, Clone)]
 pub struct UserGroup {
     pub id: String, // UUID
@@ -1,3 +1,4 @@
+use crate::api::security::{CheckStatus, Severity};
 use candid::{CandidType, Deserialize, Principal};
 use ic_stable_structures::{storable::Bound, Storable};
 use serde::Serialize;
@@ -21,6 +22,24 @@ impl Storable for StorablePrincipal {
     };
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StorableString(pub String);
+
+impl Storable for StorableString {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.0.as_bytes().to_vec())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        Self(String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 200, // Upload IDs
+        is_fixed_size: false,
+    };
+}
+
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub struct AgreementSnapshot {
     pub token_id: Principal,
@@ -45,3 +64,99 @@ impl Storable for AgreementSnapshot {
         is_fixed_size: false,
     };
 }
+
+impl Storable for super::UnlistedTransferPolicy {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode UnlistedTransferPolicy"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(bytes.as_ref()).expect("Failed to decode UnlistedTransferPolicy")
+    }
+
+    const BOUND: Bound = Bound::Bounded {
+        max_size: 64,
+        is_fixed_size: false,
+    };
+}
+
+/// A system upgrade wasm being uploaded in chunks
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UpgradeUpload {
+    pub upload_id: String,
+    pub token_canister_id: Principal,
+    pub uploader: Principal,
+    pub target: super::SystemUpgradeTarget,
+    pub expected_sha256: String,    // Hex hash of the reproducible build
+    pub source_commit: String,      // Link to the commit it was built from
+    pub total_size: u64,
+    pub arg: Option<Vec<u8>>,
+    pub take_backup_snapshot: Option<bool>,
+    pub created_at: u64,
+    pub orbit_request_id: Option<String>, // Set once proposed
+}
+
+impl Storable for UpgradeUpload {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode UpgradeUpload"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(bytes.as_ref()).expect("Failed to decode UpgradeUpload")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// A failing or warning check kept in the security history
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FailingCheck {
+    pub category: String,
+    pub name: String,
+    pub status: CheckStatus,  // Fail or Warn
+    pub severity: Severity,
+}
+
+/// One scheduled security audit of a station
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SecurityAuditRecord {
+    pub station_id: Principal,
+    pub audited_at: u64,
+    pub score: u8,
+    pub overall_status: String,
+    pub failing_checks: Vec<FailingCheck>,
+}
+
+impl Storable for SecurityAuditRecord {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode SecurityAuditRecord"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(bytes.as_ref()).expect("Failed to decode SecurityAuditRecord")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+/// Raised when an audit scores lower than the previous one or finds new Critical checks
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SecurityAlert {
+    pub station_id: Principal,
+    pub raised_at: u64,
+    pub score_before: u8,
+    pub score_after: u8,
+    pub new_critical_checks: Vec<FailingCheck>,
+}
+
+impl Storable for SecurityAlert {
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(candid::encode_one(self).expect("Failed to encode SecurityAlert"))
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(bytes.as_ref()).expect("Failed to decode SecurityAlert")
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
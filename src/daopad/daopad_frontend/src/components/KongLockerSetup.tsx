@@ -1,8 +1,6 @@
 import React, { useState, useEffect } from 'react';
 import { useDispatch } from 'react-redux';
-import { Principal } from '@dfinity/principal';
 import type { Identity } from '@dfinity/agent';
-import { getKongLockerService } from '../services/backend';
 import { KongLockerService } from '../services/backend';
 import { setKongLockerCanister, setKongLockerLoading, setKongLockerError } from '../features/dao/daoSlice';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
@@ -65,22 +63,13 @@ const KongLockerSetup: React.FC<KongLockerSetupProps> = ({ identity, onComplete
     try {
       setValidationStep('Connecting to your Kong Locker...');
 
-      // Register with DAOPad backend using the detected canister
-      const daopadService = getKongLockerService(identity);
-      const kongLockerPrincipal = Principal.fromText(detectedCanister);
-      const result = await daopadService.register(kongLockerPrincipal);
-
-      if (result.success) {
-        dispatch(setKongLockerCanister(detectedCanister));
-        if (onComplete) {
-          onComplete();
-        }
-      } else {
-        setError(result.error || 'Failed to register Kong Locker canister');
-        dispatch(setKongLockerError(result.error));
+      // No registration needed - the admin canister indexes lock canisters from the factory
+      dispatch(setKongLockerCanister(detectedCanister));
+      if (onComplete) {
+        onComplete();
       }
     } catch (err) {
-      console.error('Error registering Kong Locker canister:', err);
+      console.error('Error connecting Kong Locker canister:', err);
       const errorMessage = err instanceof Error ? err.message : 'An error occurred';
       setError(errorMessage);
       dispatch(setKongLockerError(errorMessage));
@@ -461,7 +461,7 @@ const TokenDashboard = memo(function TokenDashboard({
                       <h3 className="text-lg font-semibold">Saved Addresses</h3>
                       <span className="text-sm text-muted-foreground">Manage addresses for easy transfers</span>
                     </div>
-                    <AddressBookPage identity={identity} />
+                    <AddressBookPage identity={identity} tokenId={token.canister_id} />
                   </div>
                 </>
               )}
@@ -9,7 +9,8 @@ const AddressBookDialog = ({
   mode = 'create', // 'create', 'edit', 'view'
   onOpenChange,
   onSuccess,
-  identity
+  identity,
+  tokenId
 }) => {
   const [open, setOpen] = useState(false);
   const [loading, setLoading] = useState(false);
@@ -32,7 +33,7 @@ const AddressBookDialog = ({
 
       if (mode === 'create') {
         // Create new entry
-        result = await service.createEntry(formData);
+        result = await service.createEntry(tokenId, formData);
       } else if (mode === 'edit' && entry) {
         // Edit existing entry
         const editData = {
@@ -43,7 +44,7 @@ const AddressBookDialog = ({
             ReplaceAllBy: formData.metadata
           } : undefined
         };
-        result = await service.editEntry(editData);
+        result = await service.editEntry(tokenId, editData);
       }
 
       if (result?.success) {
@@ -108,6 +108,12 @@ export function RequestCard({ request, tokenId, userVotingPower, onVote }) {
   const statusInfo = statusConfig[statusValue] || statusConfig.Created;
   const StatusIcon = statusInfo.icon;
 
+  // Transfers outside the address book may need more than the usual 50%
+  const threshold = proposal?.threshold_override?.[0] ?? 50;
+  const destination = proposal?.transfer_destination?.[0];
+  const hashes = proposal?.payload_hashes?.[0];
+  const impact = proposal?.security_impact?.[0];
+
   return (
     <Card className="hover:shadow-md transition-shadow">
       <CardHeader>
@@ -137,6 +143,57 @@ export function RequestCard({ request, tokenId, userVotingPower, onVote }) {
           {request.requester_name && <div>Requester: {request.requester_name}</div>}
         </div>
 
+        {/* Where a transfer pays to, from the station's address book */}
+        {destination && (
+          <div className="text-sm mb-3 flex flex-wrap items-center gap-2">
+            <span>Pays to: <span className="font-medium">{destination.name}</span></span>
+            {destination.labels.map((label) => (
+              <Badge key={label} variant="secondary">{label}</Badge>
+            ))}
+          </div>
+        )}
+        {/* What an upgrade or canister call would run, to check against a reproducible build */}
+        {hashes && (
+          <div className="text-xs mb-3 space-y-1 font-mono break-all">
+            {hashes.wasm_module_hash?.[0] && (
+              <div>
+                Wasm SHA-256: {hashes.wasm_module_hash[0]}
+                {hashes.wasm_module_size?.[0] !== undefined && ` (${Number(hashes.wasm_module_size[0]).toLocaleString()} bytes)`}
+              </div>
+            )}
+            {hashes.arg_hash?.[0] && <div>Argument SHA-256: {hashes.arg_hash[0]}</div>}
+            {hashes.source_commit?.[0] && (
+              <div>
+                Source:{' '}
+                <a href={hashes.source_commit[0]} target="_blank" rel="noopener noreferrer" className="underline">
+                  {hashes.source_commit[0]}
+                </a>
+              </div>
+            )}
+          </div>
+        )}
+        {/* Backend simulation of how a permission or policy edit changes the security score */}
+        {impact && (
+          <div className="text-sm mb-3 space-y-1">
+            <div className={impact.score_after < impact.score_before ? 'text-red-600' : 'text-green-600'}>
+              Security score: {impact.score_before} → {impact.score_after}
+              {impact.overall_status_after !== impact.overall_status_before &&
+                ` (${impact.overall_status_before} → ${impact.overall_status_after})`}
+            </div>
+            {impact.changed_checks.map((check) => (
+              <div key={`${check.category}-${check.name}`} className="text-xs text-muted-foreground">
+                {check.name}: {check.status_before?.[0] ?? 'n/a'} → {check.status_after?.[0] ?? 'n/a'}
+                {check.status_after?.length > 0 && ` - ${check.message}`}
+              </div>
+            ))}
+          </div>
+        )}
+        {proposal?.threshold_override?.length > 0 && (
+          <div className="text-sm mb-3 text-amber-600">
+            Destination is not in the address book - needs {threshold}% to pass
+          </div>
+        )}
+
         {/* Voting section - only for Created status requests */}
         {(statusValue === 'Created' || statusValue === 'Scheduled') && (
           <div className="mt-4 space-y-3 border-t pt-4">
@@ -189,7 +246,7 @@ export function RequestCard({ request, tokenId, userVotingPower, onVote }) {
                 {/* Vote progress */}
                 <VoteProgressBar
                   proposal={proposal}
-                  threshold={50} // Simplified: 50% for all
+                  threshold={threshold}
                 />
 
                 {/* Vote buttons */}
@@ -215,7 +272,7 @@ export function RequestCard({ request, tokenId, userVotingPower, onVote }) {
             <h4 className="font-medium text-sm">Final Vote Results</h4>
             <VoteProgressBar
               proposal={proposal}
-              threshold={50}
+              threshold={threshold}
             />
           </div>
         )}
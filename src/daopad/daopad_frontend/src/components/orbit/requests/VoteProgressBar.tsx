@@ -2,8 +2,13 @@ import React from 'react';
 import { Badge } from '@/components/ui/badge';
 
 export function VoteProgressBar({ proposal, threshold = 50 }) {
-  // Calculate percentages
-  const totalVP = Number(proposal.total_voting_power || 0);
+  // Per-voter cap (candid opt arrives as an array)
+  const voterCap = proposal.voter_cap?.[0];
+  const cutVP = Number(proposal.cut_voting_power || 0);
+  const spreadsCut = voterCap && 'Spread' in voterCap.policy;
+
+  // Calculate percentages (a spread cap removes cut weight from the total)
+  const totalVP = Math.max(0, Number(proposal.total_voting_power || 0) - (spreadsCut ? cutVP : 0));
   const yesVotes = Number(proposal.yes_votes || 0);
   const noVotes = Number(proposal.no_votes || 0);
   const requiredVotes = (totalVP * threshold) / 100;
@@ -64,6 +69,15 @@ export function VoteProgressBar({ proposal, threshold = 50 }) {
       <div className="text-xs text-muted-foreground">
         {Number(proposal.voter_count || 0)} voters participated
       </div>
+
+      {/* Weight cut by the per-voter cap */}
+      {voterCap && cutVP > 0 && (
+        <div className="text-xs text-muted-foreground">
+          {cutVP.toLocaleString()} VP above the {Number(voterCap.max_share_bps) / 100}% per-voter cap
+          {spreadsCut ? ' was removed from the total' : ' was not counted'}
+          {' '}({Number(proposal.capped_voter_count || 0)} capped voters)
+        </div>
+      )}
     </div>
   );
 }
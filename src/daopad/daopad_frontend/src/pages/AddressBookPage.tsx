@@ -9,7 +9,7 @@ import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Card, CardContent } from '@/components/ui/card';
 import { ExecutiveCard } from '@/components/ui/executive-card';
 
-const AddressBookPage = ({ identity }) => {
+const AddressBookPage = ({ identity, tokenId }) => {
   // Initialize service instance with identity
   const [addressBookService, setAddressBookService] = useState(null);
 
@@ -52,7 +52,7 @@ const AddressBookPage = ({ identity }) => {
 
   // Fetch data function - Lines 177-198 in Vue component
   const fetchList = useCallback(async () => {
-    if (!canList || !addressBookService) return;
+    if (!canList || !addressBookService || !tokenId) return;
 
     setLoading(true);
     setError(null);
@@ -65,7 +65,7 @@ const AddressBookPage = ({ identity }) => {
         }
       };
 
-      const result = await addressBookService.listEntries(input);
+      const result = await addressBookService.listEntries(tokenId, input);
       if (result.success) {
         setEntries(result.data.address_book_entries);
         setPrivileges(
@@ -89,7 +89,7 @@ const AddressBookPage = ({ identity }) => {
     } finally {
       setLoading(false);
     }
-  }, [searchTerm, pagination.offset, pagination.limit, canList, addressBookService]);
+  }, [searchTerm, pagination.offset, pagination.limit, canList, addressBookService, tokenId]);
 
   // Effect 1: Initial load only (runs once on mount)
   useEffect(() => {
@@ -152,7 +152,7 @@ const AddressBookPage = ({ identity }) => {
 
     if (window.confirm(`Are you sure you want to delete the address book entry for ${entry.address_owner}?`)) {
       try {
-        const result = await addressBookService.removeEntry(entry.id);
+        const result = await addressBookService.removeEntry(tokenId, entry.id);
         if (result.success) {
           // Refresh the list
           setForceReload(prev => prev + 1);
@@ -206,6 +206,7 @@ const AddressBookPage = ({ identity }) => {
               </Button>
             }
             identity={identity}
+            tokenId={tokenId}
             onOpenChange={setDisableRefresh}
             onSuccess={() => setForceReload(prev => prev + 1)}
           />
@@ -32,6 +32,12 @@ import {
 import { Badge } from '@/components/ui/badge';
 import { Loader2, ArrowRight, Check, X } from 'lucide-react';
 
+// Equity is stored in basis points (10000 = 100%)
+const formatBps = (bps: number | bigint) =>
+  `${(Number(bps) / 100).toLocaleString(undefined, { maximumFractionDigits: 2 })}%`;
+
+const SECONDS_PER_MONTH = 30n * 24n * 60n * 60n;
+
 export default function DaoEquity() {
   const {
     token,
@@ -43,7 +49,13 @@ export default function DaoEquity() {
   const [loading, setLoading] = useState(true);
   const [equityHolders, setEquityHolders] = useState<Array<[Principal, number]>>([]);
   const [proposals, setProposals] = useState<any[]>([]);
+  const [issuances, setIssuances] = useState<any[]>([]);
+  const [dividends, setDividends] = useState<any[]>([]);
+  const [conversions, setConversions] = useState<any[]>([]);
+  const [sellOrders, setSellOrders] = useState<any[]>([]);
+  const [conversionHistory, setConversionHistory] = useState<any[]>([]);
   const [userEquity, setUserEquity] = useState<number>(0);
+  const [vestingSchedule, setVestingSchedule] = useState<any | null>(null);
   const [showTransferDialog, setShowTransferDialog] = useState(false);
   const [error, setError] = useState<string>('');
 
@@ -53,8 +65,28 @@ export default function DaoEquity() {
   const [ckusdcAmount, setCkusdcAmount] = useState('');
   const [paymentDestination, setPaymentDestination] = useState<'seller' | 'treasury'>('treasury');
   const [sellerAccount, setSellerAccount] = useState('');
+  const [standingOffer, setStandingOffer] = useState(false);
+  const [offerDays, setOfferDays] = useState('30');
   const [submitting, setSubmitting] = useState(false);
 
+  // Issuance form state
+  const [showIssueDialog, setShowIssueDialog] = useState(false);
+  const [recipientPrincipal, setRecipientPrincipal] = useState('');
+  const [issuePercentage, setIssuePercentage] = useState('');
+  const [subscriptionAmount, setSubscriptionAmount] = useState('');
+  const [cliffMonths, setCliffMonths] = useState('');
+  const [vestingMonths, setVestingMonths] = useState('');
+
+  // Dividend form state
+  const [showDividendDialog, setShowDividendDialog] = useState(false);
+  const [dividendAccountId, setDividendAccountId] = useState('');
+  const [dividendAssetId, setDividendAssetId] = useState('');
+  const [dividendAmount, setDividendAmount] = useState('');
+
+  // Conversion form state
+  const [showConversionDialog, setShowConversionDialog] = useState(false);
+  const [conversionToken, setConversionToken] = useState('');
+
   // Fetch equity data on mount
   useEffect(() => {
     async function loadEquityData() {
@@ -68,19 +100,33 @@ export default function DaoEquity() {
         const stationId = orbitStation.station_id;
 
         // Parallel fetch
-        const [holders, proposalsList] = await Promise.all([
+        const [holders, proposalsList, issuanceList, dividendList, conversionList, history, orders] = await Promise.all([
           adminService.getEquityHolders(stationId),
-          adminService.getEquityTransferProposals(stationId)
+          adminService.getEquityTransferProposals(stationId),
+          adminService.getEquityIssuanceProposals(stationId),
+          adminService.getDividendProposals(stationId),
+          adminService.getEquityConversionProposals(stationId),
+          adminService.getStationConversions(stationId),
+          adminService.getOpenSellOrders(stationId)
         ]);
 
         setEquityHolders(holders);
         setProposals(proposalsList);
+        setIssuances(issuanceList);
+        setDividends(dividendList);
+        setConversions(conversionList);
+        setConversionHistory(history);
+        setSellOrders(orders);
 
         // Get user's equity if authenticated
         if (isAuthenticated && identity) {
           const userPrincipal = identity.getPrincipal();
-          const equity = await adminService.getUserEquity(stationId, userPrincipal);
+          const [equity, schedule] = await Promise.all([
+            adminService.getUserEquity(stationId, userPrincipal),
+            adminService.getVestingSchedule(stationId, userPrincipal)
+          ]);
           setUserEquity(equity);
+          setVestingSchedule(schedule);
         }
       } catch (err: any) {
         console.error('Failed to load equity data:', err);
@@ -95,14 +141,14 @@ export default function DaoEquity() {
 
   const handleCreateProposal = async () => {
     // Validation
-    if (!buyerPrincipal.trim()) {
+    if (!standingOffer && !buyerPrincipal.trim()) {
       setError('Buyer principal is required');
       return;
     }
 
-    const pct = parseInt(percentage);
-    if (isNaN(pct) || pct < 1 || pct > 100) {
-      setError('Percentage must be between 1 and 100');
+    const equityBps = Math.round(parseFloat(percentage) * 100);
+    if (isNaN(equityBps) || equityBps < 1 || equityBps > 10000) {
+      setError('Percentage must be between 0.01 and 100');
       return;
     }
 
@@ -117,30 +163,41 @@ export default function DaoEquity() {
       return;
     }
 
+    const days = parseInt(offerDays, 10);
+    if (standingOffer && (isNaN(days) || days < 1 || days > 365)) {
+      setError('Offer must stay open for 1-365 days');
+      return;
+    }
+
     try {
       setSubmitting(true);
       setError('');
 
       const adminService = getAdminService(identity);
-      const buyer = Principal.fromText(buyerPrincipal.trim());
 
       const destination = paymentDestination === 'treasury'
         ? { StationTreasury: Principal.fromText(orbitStation.station_id) }
         : { SellerAccount: sellerAccount.trim() };
 
-      const proposalId = await adminService.createEquityTransferProposal(
-        orbitStation.station_id,
-        buyer,
-        pct,
-        amount,
-        destination
-      );
+      if (standingOffer) {
+        const expiresAt = (BigInt(Date.now()) + BigInt(days) * 86_400_000n) * 1_000_000n;
+        await adminService.createSellOrder(orbitStation.station_id, equityBps, amount, destination, expiresAt);
+        setSellOrders(await adminService.getOpenSellOrders(orbitStation.station_id));
+      } else {
+        const proposalId = await adminService.createEquityTransferProposal(
+          orbitStation.station_id,
+          Principal.fromText(buyerPrincipal.trim()),
+          equityBps,
+          amount,
+          destination
+        );
 
-      console.log('Proposal created:', proposalId);
+        console.log('Proposal created:', proposalId);
 
-      // Refresh proposals list
-      const updated = await adminService.getEquityTransferProposals(orbitStation.station_id);
-      setProposals(updated);
+        // Refresh proposals list
+        const updated = await adminService.getEquityTransferProposals(orbitStation.station_id);
+        setProposals(updated);
+      }
 
       // Close dialog and reset form
       setShowTransferDialog(false);
@@ -149,6 +206,7 @@ export default function DaoEquity() {
       setCkusdcAmount('');
       setPaymentDestination('treasury');
       setSellerAccount('');
+      setStandingOffer(false);
     } catch (err: any) {
       console.error('Failed to create proposal:', err);
       setError(err.message || 'Failed to create proposal');
@@ -171,6 +229,46 @@ export default function DaoEquity() {
     }
   };
 
+  const handleCancel = async (proposalId: string) => {
+    try {
+      const adminService = getAdminService(identity);
+      await adminService.cancelEquityTransfer(proposalId);
+
+      const updated = await adminService.getEquityTransferProposals(orbitStation.station_id);
+      setProposals(updated);
+    } catch (err: any) {
+      console.error('Failed to cancel:', err);
+      setError(err.message || 'Failed to cancel proposal');
+    }
+  };
+
+  const handleExercise = async (proposalId: string, equityBps: number) => {
+    try {
+      const adminService = getAdminService(identity);
+      await adminService.exerciseRightOfFirstRefusal(proposalId, equityBps);
+
+      const updated = await adminService.getEquityTransferProposals(orbitStation.station_id);
+      setProposals(updated);
+    } catch (err: any) {
+      console.error('Failed to exercise right of first refusal:', err);
+      setError(err.message || 'Failed to exercise right of first refusal');
+    }
+  };
+
+  const handleDeposit = async (proposal: any) => {
+    try {
+      const adminService = getAdminService(identity);
+      await adminService.depositEquityEscrow(proposal.proposal_id, BigInt(proposal.ckusdc_amount));
+
+      // Refresh proposals (cards reload their escrow)
+      const updated = await adminService.getEquityTransferProposals(orbitStation.station_id);
+      setProposals(updated);
+    } catch (err: any) {
+      console.error('Failed to deposit:', err);
+      setError(err.message || 'Failed to deposit payment into escrow');
+    }
+  };
+
   const handleExecute = async (proposalId: string) => {
     try {
       const adminService = getAdminService(identity);
@@ -197,6 +295,273 @@ export default function DaoEquity() {
     }
   };
 
+  const handleCreateIssuance = async () => {
+    if (!recipientPrincipal.trim()) {
+      setError('Recipient principal is required');
+      return;
+    }
+
+    // Existing holders always keep something, so 100% can't be issued
+    const equityBps = Math.round(parseFloat(issuePercentage) * 100);
+    if (isNaN(equityBps) || equityBps < 1 || equityBps > 9999) {
+      setError('Percentage must be between 0.01 and 99.99');
+      return;
+    }
+
+    const amount = BigInt(subscriptionAmount || '0');
+    if (amount < 0n) {
+      setError('Amount cannot be negative');
+      return;
+    }
+
+    // Vesting starts now and runs for the given number of months
+    const duration = BigInt(vestingMonths || '0');
+    const cliff = BigInt(cliffMonths || '0');
+    if (cliff > duration) {
+      setError('Cliff cannot be longer than the vesting period');
+      return;
+    }
+    const vesting = duration > 0n
+      ? {
+          start_at: BigInt(Date.now()) * 1_000_000n,
+          cliff_seconds: cliff * SECONDS_PER_MONTH,
+          duration_seconds: duration * SECONDS_PER_MONTH,
+        }
+      : null;
+
+    try {
+      setSubmitting(true);
+      setError('');
+
+      const adminService = getAdminService(identity);
+      await adminService.createEquityIssuanceProposal(
+        orbitStation.station_id,
+        Principal.fromText(recipientPrincipal.trim()),
+        equityBps,
+        amount,
+        vesting
+      );
+
+      const updated = await adminService.getEquityIssuanceProposals(orbitStation.station_id);
+      setIssuances(updated);
+
+      setShowIssueDialog(false);
+      setRecipientPrincipal('');
+      setIssuePercentage('');
+      setSubscriptionAmount('');
+      setCliffMonths('');
+      setVestingMonths('');
+    } catch (err: any) {
+      console.error('Failed to create issuance:', err);
+      setError(err.message || 'Failed to create issuance proposal');
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  // Run an issuance action, then refresh the cap table and issuance list
+  const runIssuanceAction = async (action: () => Promise<unknown>, failure: string) => {
+    try {
+      await action();
+
+      const adminService = getAdminService(identity);
+      const [holders, issuanceList] = await Promise.all([
+        adminService.getEquityHolders(orbitStation.station_id),
+        adminService.getEquityIssuanceProposals(orbitStation.station_id)
+      ]);
+      setEquityHolders(holders);
+      setIssuances(issuanceList);
+
+      if (isAuthenticated && identity) {
+        const equity = await adminService.getUserEquity(orbitStation.station_id, identity.getPrincipal());
+        setUserEquity(equity);
+      }
+    } catch (err: any) {
+      console.error(`${failure}:`, err);
+      setError(err.message || failure);
+    }
+  };
+
+  const issuanceHandlers = {
+    onVote: (proposalId: string, approve: boolean) => runIssuanceAction(
+      () => getAdminService(identity).voteOnEquityIssuance(proposalId, approve),
+      'Failed to vote'
+    ),
+    onCancel: (proposalId: string) => runIssuanceAction(
+      () => getAdminService(identity).cancelEquityIssuance(proposalId),
+      'Failed to cancel proposal'
+    ),
+    onDeposit: (proposal: any) => runIssuanceAction(
+      () => getAdminService(identity).depositEquityEscrow(proposal.proposal_id, BigInt(proposal.ckusdc_amount)),
+      'Failed to deposit subscription into escrow'
+    ),
+    onExecute: (proposalId: string) => runIssuanceAction(
+      () => getAdminService(identity).executeEquityIssuance(proposalId),
+      'Failed to execute issuance'
+    ),
+  };
+
+  const handleCreateDividend = async () => {
+    if (!dividendAccountId.trim() || !dividendAssetId.trim()) {
+      setError('Treasury account and asset IDs are required');
+      return;
+    }
+
+    let amount: bigint;
+    try {
+      amount = BigInt(dividendAmount);
+    } catch {
+      setError('Amount must be a whole number of the asset\'s smallest units');
+      return;
+    }
+    if (amount <= 0n) {
+      setError('Amount must be positive');
+      return;
+    }
+
+    try {
+      setSubmitting(true);
+      setError('');
+
+      const adminService = getAdminService(identity);
+      await adminService.createDividendProposal(
+        orbitStation.station_id,
+        dividendAccountId.trim(),
+        dividendAssetId.trim(),
+        amount
+      );
+
+      setDividends(await adminService.getDividendProposals(orbitStation.station_id));
+      setShowDividendDialog(false);
+      setDividendAccountId('');
+      setDividendAssetId('');
+      setDividendAmount('');
+    } catch (err: any) {
+      console.error('Failed to create dividend:', err);
+      setError(err.message || 'Failed to create dividend proposal');
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  // Run a dividend action, then refresh the dividend list
+  const runDividendAction = async (action: () => Promise<unknown>, failure: string) => {
+    try {
+      await action();
+      setDividends(await getAdminService(identity).getDividendProposals(orbitStation.station_id));
+    } catch (err: any) {
+      console.error(`${failure}:`, err);
+      setError(err.message || failure);
+    }
+  };
+
+  const dividendHandlers = {
+    onVote: (proposalId: string, approve: boolean) => runDividendAction(
+      () => getAdminService(identity).voteOnDividend(proposalId, approve),
+      'Failed to vote'
+    ),
+    onCancel: (proposalId: string) => runDividendAction(
+      () => getAdminService(identity).cancelDividend(proposalId),
+      'Failed to cancel proposal'
+    ),
+    onExecute: (proposalId: string) => runDividendAction(
+      () => getAdminService(identity).executeDividend(proposalId),
+      'Failed to pay dividend'
+    ),
+  };
+
+  const handleCreateConversion = async () => {
+    let tokenId: Principal;
+    try {
+      tokenId = Principal.fromText(conversionToken.trim());
+    } catch {
+      setError('Invalid token canister ID');
+      return;
+    }
+
+    try {
+      setSubmitting(true);
+      setError('');
+
+      const adminService = getAdminService(identity);
+      await adminService.createEquityConversionProposal(orbitStation.station_id, tokenId);
+      setConversions(await adminService.getEquityConversionProposals(orbitStation.station_id));
+      setShowConversionDialog(false);
+      setConversionToken('');
+    } catch (err: any) {
+      console.error('Failed to create conversion:', err);
+      setError(err.message || 'Failed to create conversion proposal');
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  // Run a conversion action, then refresh the conversion list and cap table
+  const runConversionAction = async (action: () => Promise<unknown>, failure: string) => {
+    try {
+      await action();
+
+      const adminService = getAdminService(identity);
+      const [conversionList, history, holders] = await Promise.all([
+        adminService.getEquityConversionProposals(orbitStation.station_id),
+        adminService.getStationConversions(orbitStation.station_id),
+        adminService.getEquityHolders(orbitStation.station_id)
+      ]);
+      setConversions(conversionList);
+      setConversionHistory(history);
+      setEquityHolders(holders);
+    } catch (err: any) {
+      console.error(`${failure}:`, err);
+      setError(err.message || failure);
+    }
+  };
+
+  const conversionHandlers = {
+    onVote: (proposalId: string, approve: boolean) => runConversionAction(
+      () => getAdminService(identity).voteOnEquityConversion(proposalId, approve),
+      'Failed to vote'
+    ),
+    onCancel: (proposalId: string) => runConversionAction(
+      () => getAdminService(identity).cancelEquityConversion(proposalId),
+      'Failed to cancel proposal'
+    ),
+    onExecute: (proposalId: string) => runConversionAction(
+      () => getAdminService(identity).executeEquityConversion(proposalId),
+      'Failed to convert station'
+    ),
+  };
+
+  // Accepting opens a transfer proposal, so refresh both lists
+  const runOrderAction = async (action: () => Promise<unknown>, failure: string) => {
+    try {
+      await action();
+
+      const adminService = getAdminService(identity);
+      const [orders, proposalsList] = await Promise.all([
+        adminService.getOpenSellOrders(orbitStation.station_id),
+        adminService.getEquityTransferProposals(orbitStation.station_id)
+      ]);
+      setSellOrders(orders);
+      setProposals(proposalsList);
+    } catch (err: any) {
+      console.error(`${failure}:`, err);
+      setError(err.message || failure);
+    }
+  };
+
+  const orderHandlers = {
+    onAccept: (orderId: string, equityBps: number) => runOrderAction(
+      () => getAdminService(identity).acceptSellOrder(orderId, equityBps),
+      'Failed to accept sell order'
+    ),
+    onCancel: (orderId: string) => runOrderAction(
+      () => getAdminService(identity).cancelSellOrder(orderId),
+      'Failed to cancel sell order'
+    ),
+  };
+
+  const lastConversion = conversionHistory[conversionHistory.length - 1];
+
   if (!orbitStation) {
     return (
       <Alert className="bg-executive-mediumGray border-executive-gold/30">
@@ -224,6 +589,15 @@ export default function DaoEquity() {
         </Alert>
       )}
 
+      {lastConversion && 'EquityToToken' in lastConversion.direction && (
+        <Alert className="bg-executive-mediumGray border-executive-gold/30">
+          <AlertDescription className="text-executive-lightGray">
+            This station is now governed by holders of token {lastConversion.token_canister_id.toText()}.
+            Its final cap table is kept in the export history below.
+          </AlertDescription>
+        </Alert>
+      )}
+
       {/* Equity Holders Table */}
       <Card className="bg-executive-darkGray border-executive-gold/20">
         <CardHeader>
@@ -233,14 +607,42 @@ export default function DaoEquity() {
               <CardDescription className="text-executive-lightGray/70">
                 Current ownership distribution
               </CardDescription>
+              {vestingSchedule && vestingSchedule.unvested_bps > 0 && (
+                <p className="text-sm text-executive-lightGray/70 mt-1">
+                  Your equity: {formatBps(vestingSchedule.vested_bps)} vested, {formatBps(vestingSchedule.unvested_bps)} still vesting
+                </p>
+              )}
             </div>
             {userEquity > 0 && isAuthenticated && (
-              <Button
-                onClick={() => setShowTransferDialog(true)}
-                className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
-              >
-                Transfer Equity
-              </Button>
+              <div className="flex gap-2">
+                <Button
+                  onClick={() => setShowConversionDialog(true)}
+                  variant="outline"
+                  className="border-executive-gold/50 text-executive-gold"
+                >
+                  Convert to Token DAO
+                </Button>
+                <Button
+                  onClick={() => setShowDividendDialog(true)}
+                  variant="outline"
+                  className="border-executive-gold/50 text-executive-gold"
+                >
+                  Pay Dividend
+                </Button>
+                <Button
+                  onClick={() => setShowIssueDialog(true)}
+                  variant="outline"
+                  className="border-executive-gold/50 text-executive-gold"
+                >
+                  Issue Equity
+                </Button>
+                <Button
+                  onClick={() => setShowTransferDialog(true)}
+                  className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+                >
+                  Transfer Equity
+                </Button>
+              </div>
             )}
           </div>
         </CardHeader>
@@ -260,14 +662,14 @@ export default function DaoEquity() {
                   </TableCell>
                 </TableRow>
               ) : (
-                equityHolders.map(([principal, pct]) => (
+                equityHolders.map(([principal, bps]) => (
                   <TableRow key={principal.toText()}>
                     <TableCell className="font-mono text-executive-lightGray text-sm">
                       {principal.toText()}
                     </TableCell>
                     <TableCell className="text-right">
                       <Badge variant="secondary" className="bg-executive-gold/20 text-executive-gold">
-                        {pct}%
+                        {formatBps(bps)}
                       </Badge>
                     </TableCell>
                   </TableRow>
@@ -275,9 +677,53 @@ export default function DaoEquity() {
               )}
             </TableBody>
           </Table>
+          <div className="flex justify-end gap-4 mt-4 text-sm">
+            <span className="text-executive-lightGray/70">Export cap table:</span>
+            <a
+              href={getAdminService(identity).capTableExportUrl(orbitStation.station_id, 'csv')}
+              className="text-executive-gold hover:underline"
+            >
+              CSV
+            </a>
+            <a
+              href={getAdminService(identity).capTableExportUrl(orbitStation.station_id, 'json')}
+              className="text-executive-gold hover:underline"
+            >
+              JSON
+            </a>
+            <a
+              href={getAdminService(identity).capTableExportUrl(orbitStation.station_id, 'history')}
+              className="text-executive-gold hover:underline"
+            >
+              Full history
+            </a>
+          </div>
         </CardContent>
       </Card>
 
+      {/* Sell Orders */}
+      {sellOrders.length > 0 && (
+        <Card className="bg-executive-darkGray border-executive-gold/20">
+          <CardHeader>
+            <CardTitle className="text-executive-ivory">Equity For Sale</CardTitle>
+            <CardDescription className="text-executive-lightGray/70">
+              Standing offers anyone can accept; each purchase still needs 75% holder approval and escrowed payment
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            {sellOrders.map((listing) => (
+              <SellOrderCard
+                key={listing.order.order_id}
+                listing={listing}
+                userPrincipal={identity?.getPrincipal()}
+                isAuthenticated={isAuthenticated}
+                {...orderHandlers}
+              />
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
       {/* Active Proposals */}
       <Card className="bg-executive-darkGray border-executive-gold/20">
         <CardHeader>
@@ -300,71 +746,448 @@ export default function DaoEquity() {
                 isAuthenticated={isAuthenticated}
                 onVote={handleVote}
                 onExecute={handleExecute}
+                onDeposit={handleDeposit}
+                onCancel={handleCancel}
+                onExercise={handleExercise}
+                userEquity={userEquity}
               />
             ))
           )}
         </CardContent>
       </Card>
 
-      {/* Transfer Dialog */}
-      <Dialog open={showTransferDialog} onOpenChange={setShowTransferDialog}>
+      {/* Issuance Proposals */}
+      {issuances.length > 0 && (
+        <Card className="bg-executive-darkGray border-executive-gold/20">
+          <CardHeader>
+            <CardTitle className="text-executive-ivory">Equity Issuance Proposals</CardTitle>
+            <CardDescription className="text-executive-lightGray/70">
+              New equity dilutes every holder pro rata and requires 80% approval
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            {issuances.map((proposal) => (
+              <IssuanceCard
+                key={proposal.proposal_id}
+                proposal={proposal}
+                userPrincipal={identity?.getPrincipal()}
+                isAuthenticated={isAuthenticated}
+                {...issuanceHandlers}
+              />
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Dividend Proposals */}
+      {dividends.length > 0 && (
+        <Card className="bg-executive-darkGray border-executive-gold/20">
+          <CardHeader>
+            <CardTitle className="text-executive-ivory">Dividends</CardTitle>
+            <CardDescription className="text-executive-lightGray/70">
+              Treasury payouts split by equity, requiring 75% approval
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            {dividends.map((proposal) => (
+              <DividendCard
+                key={proposal.proposal_id}
+                proposal={proposal}
+                userPrincipal={identity?.getPrincipal()}
+                isAuthenticated={isAuthenticated}
+                {...dividendHandlers}
+              />
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Conversion Proposals */}
+      {conversions.length > 0 && (
+        <Card className="bg-executive-darkGray border-executive-gold/20">
+          <CardHeader>
+            <CardTitle className="text-executive-ivory">Token Governance Proposals</CardTitle>
+            <CardDescription className="text-executive-lightGray/70">
+              Moving to token governance requires 80% approval
+            </CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            {conversions.map((proposal) => (
+              <ConversionCard
+                key={proposal.proposal_id}
+                proposal={proposal}
+                userPrincipal={identity?.getPrincipal()}
+                isAuthenticated={isAuthenticated}
+                {...conversionHandlers}
+              />
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
+      {/* Conversion Dialog */}
+      <Dialog open={showConversionDialog} onOpenChange={setShowConversionDialog}>
         <DialogContent className="bg-executive-darkGray border-executive-gold/20 max-w-2xl">
           <DialogHeader>
-            <DialogTitle className="text-executive-ivory">Transfer Equity</DialogTitle>
+            <DialogTitle className="text-executive-ivory">Convert to Token DAO</DialogTitle>
             <DialogDescription className="text-executive-lightGray">
-              Create a proposal to transfer {userEquity}% of your equity. Requires 75% approval.
+              Hand governance of this station to the token's Kong Locker holders. Requires 80% approval.
             </DialogDescription>
           </DialogHeader>
 
           <div className="space-y-4">
             <div>
-              <Label htmlFor="buyer" className="text-executive-lightGray">
-                Buyer Principal
+              <Label htmlFor="conversion-token" className="text-executive-lightGray">
+                Token Canister ID
               </Label>
               <Input
-                id="buyer"
-                value={buyerPrincipal}
-                onChange={(e) => setBuyerPrincipal(e.target.value)}
-                placeholder="Principal ID"
+                id="conversion-token"
+                value={conversionToken}
+                onChange={(e) => setConversionToken(e.target.value)}
+                placeholder="ICRC-1 ledger canister"
                 className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
               />
             </div>
 
+            <Alert className="bg-executive-charcoal/50 border-executive-gold/20">
+              <AlertDescription className="text-executive-lightGray/70 text-sm">
+                Open transfers and issuances must be executed or cancelled first. The final cap table is archived and equity stops carrying votes. The token DAO can later go private again through an approved call to convert_to_equity_station.
+              </AlertDescription>
+            </Alert>
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => {
+                setShowConversionDialog(false);
+                setError('');
+              }}
+              className="border-executive-gold/30 text-executive-lightGray hover:bg-executive-gold/10"
+            >
+              Cancel
+            </Button>
+            <Button
+              onClick={handleCreateConversion}
+              disabled={submitting}
+              className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              {submitting ? (
+                <>
+                  <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                  Creating...
+                </>
+              ) : (
+                'Create Proposal'
+              )}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
+      {/* Dividend Dialog */}
+      <Dialog open={showDividendDialog} onOpenChange={setShowDividendDialog}>
+        <DialogContent className="bg-executive-darkGray border-executive-gold/20 max-w-2xl">
+          <DialogHeader>
+            <DialogTitle className="text-executive-ivory">Pay Dividend</DialogTitle>
+            <DialogDescription className="text-executive-lightGray">
+              Split a treasury amount between holders by their current equity. Requires 75% approval.
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-4">
             <div className="grid grid-cols-2 gap-4">
               <div>
-                <Label htmlFor="percentage" className="text-executive-lightGray">
-                  Percentage (1-{userEquity})
+                <Label htmlFor="dividend-account" className="text-executive-lightGray">
+                  Treasury Account ID
                 </Label>
                 <Input
-                  id="percentage"
-                  type="number"
-                  min="1"
-                  max={userEquity}
-                  value={percentage}
-                  onChange={(e) => setPercentage(e.target.value)}
-                  placeholder="20"
+                  id="dividend-account"
+                  value={dividendAccountId}
+                  onChange={(e) => setDividendAccountId(e.target.value)}
+                  placeholder="Orbit account UUID"
                   className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
                 />
               </div>
 
               <div>
-                <Label htmlFor="amount" className="text-executive-lightGray">
-                  ckUSDC Amount
+                <Label htmlFor="dividend-asset" className="text-executive-lightGray">
+                  Asset ID
                 </Label>
                 <Input
-                  id="amount"
-                  type="number"
-                  min="0"
-                  value={ckusdcAmount}
-                  onChange={(e) => setCkusdcAmount(e.target.value)}
-                  placeholder="1000"
+                  id="dividend-asset"
+                  value={dividendAssetId}
+                  onChange={(e) => setDividendAssetId(e.target.value)}
+                  placeholder="Orbit asset UUID"
                   className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
                 />
               </div>
             </div>
 
             <div>
-              <Label className="text-executive-lightGray">Payment Destination</Label>
+              <Label htmlFor="dividend-amount" className="text-executive-lightGray">
+                Total Amount (smallest units)
+              </Label>
+              <Input
+                id="dividend-amount"
+                type="number"
+                min="1"
+                value={dividendAmount}
+                onChange={(e) => setDividendAmount(e.target.value)}
+                placeholder="1000000"
+                className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+              />
+            </div>
+
+            <Alert className="bg-executive-charcoal/50 border-executive-gold/20">
+              <AlertDescription className="text-executive-lightGray/70 text-sm">
+                Each holder is paid to the ICRC-1 address book entry under their principal. Holders without one are held back until they add it and the dividend is paid again. Transfer fees come out of the treasury on top of the total.
+              </AlertDescription>
+            </Alert>
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => {
+                setShowDividendDialog(false);
+                setError('');
+              }}
+              className="border-executive-gold/30 text-executive-lightGray hover:bg-executive-gold/10"
+            >
+              Cancel
+            </Button>
+            <Button
+              onClick={handleCreateDividend}
+              disabled={submitting}
+              className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              {submitting ? (
+                <>
+                  <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                  Creating...
+                </>
+              ) : (
+                'Create Proposal'
+              )}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
+      {/* Issuance Dialog */}
+      <Dialog open={showIssueDialog} onOpenChange={setShowIssueDialog}>
+        <DialogContent className="bg-executive-darkGray border-executive-gold/20 max-w-2xl">
+          <DialogHeader>
+            <DialogTitle className="text-executive-ivory">Issue New Equity</DialogTitle>
+            <DialogDescription className="text-executive-lightGray">
+              The recipient ends up with this share of the company and every existing holding shrinks pro rata. Requires 80% approval.
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-4">
+            <div>
+              <Label htmlFor="recipient" className="text-executive-lightGray">
+                Recipient Principal
+              </Label>
+              <Input
+                id="recipient"
+                value={recipientPrincipal}
+                onChange={(e) => setRecipientPrincipal(e.target.value)}
+                placeholder="Principal ID"
+                className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+              />
+            </div>
+
+            <div className="grid grid-cols-2 gap-4">
+              <div>
+                <Label htmlFor="issue-percentage" className="text-executive-lightGray">
+                  Percentage After Issuance (0.01-99.99)
+                </Label>
+                <Input
+                  id="issue-percentage"
+                  type="number"
+                  min="0.01"
+                  step="0.01"
+                  max="99.99"
+                  value={issuePercentage}
+                  onChange={(e) => setIssuePercentage(e.target.value)}
+                  placeholder="10"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+
+              <div>
+                <Label htmlFor="subscription" className="text-executive-lightGray">
+                  Subscription (ckUSDC, optional)
+                </Label>
+                <Input
+                  id="subscription"
+                  type="number"
+                  min="0"
+                  value={subscriptionAmount}
+                  onChange={(e) => setSubscriptionAmount(e.target.value)}
+                  placeholder="0"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+            </div>
+
+            <div className="grid grid-cols-2 gap-4">
+              <div>
+                <Label htmlFor="vesting-months" className="text-executive-lightGray">
+                  Vesting Period (months, optional)
+                </Label>
+                <Input
+                  id="vesting-months"
+                  type="number"
+                  min="0"
+                  value={vestingMonths}
+                  onChange={(e) => setVestingMonths(e.target.value)}
+                  placeholder="48"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+
+              <div>
+                <Label htmlFor="cliff-months" className="text-executive-lightGray">
+                  Cliff (months)
+                </Label>
+                <Input
+                  id="cliff-months"
+                  type="number"
+                  min="0"
+                  value={cliffMonths}
+                  onChange={(e) => setCliffMonths(e.target.value)}
+                  placeholder="12"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+            </div>
+
+            <Alert className="bg-executive-charcoal/50 border-executive-gold/20">
+              <AlertDescription className="text-executive-lightGray/70 text-sm">
+                Unvested equity can't vote or be transferred. A subscription is escrowed by the recipient and paid into the station treasury when the issuance executes.
+              </AlertDescription>
+            </Alert>
+          </div>
+
+          <DialogFooter>
+            <Button
+              variant="outline"
+              onClick={() => {
+                setShowIssueDialog(false);
+                setError('');
+              }}
+              className="border-executive-gold/30 text-executive-lightGray hover:bg-executive-gold/10"
+            >
+              Cancel
+            </Button>
+            <Button
+              onClick={handleCreateIssuance}
+              disabled={submitting}
+              className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              {submitting ? (
+                <>
+                  <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+                  Creating...
+                </>
+              ) : (
+                'Create Proposal'
+              )}
+            </Button>
+          </DialogFooter>
+        </DialogContent>
+      </Dialog>
+
+      {/* Transfer Dialog */}
+      <Dialog open={showTransferDialog} onOpenChange={setShowTransferDialog}>
+        <DialogContent className="bg-executive-darkGray border-executive-gold/20 max-w-2xl">
+          <DialogHeader>
+            <DialogTitle className="text-executive-ivory">Transfer Equity</DialogTitle>
+            <DialogDescription className="text-executive-lightGray">
+              Create a proposal to transfer some of your {formatBps(userEquity)} equity. Requires 75% approval.
+            </DialogDescription>
+          </DialogHeader>
+
+          <div className="space-y-4">
+            <label className="flex items-center space-x-2">
+              <input
+                type="checkbox"
+                checked={standingOffer}
+                onChange={(e) => setStandingOffer(e.target.checked)}
+                className="text-executive-gold"
+              />
+              <span className="text-executive-lightGray">Standing offer: list it for any buyer</span>
+            </label>
+
+            {standingOffer ? (
+              <div>
+                <Label htmlFor="offer-days" className="text-executive-lightGray">
+                  Offer Open For (days)
+                </Label>
+                <Input
+                  id="offer-days"
+                  type="number"
+                  min="1"
+                  max="365"
+                  value={offerDays}
+                  onChange={(e) => setOfferDays(e.target.value)}
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+            ) : (
+              <div>
+                <Label htmlFor="buyer" className="text-executive-lightGray">
+                  Buyer Principal
+                </Label>
+                <Input
+                  id="buyer"
+                  value={buyerPrincipal}
+                  onChange={(e) => setBuyerPrincipal(e.target.value)}
+                  placeholder="Principal ID"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+            )}
+
+            <div className="grid grid-cols-2 gap-4">
+              <div>
+                <Label htmlFor="percentage" className="text-executive-lightGray">
+                  Percentage (0.01-{userEquity / 100})
+                </Label>
+                <Input
+                  id="percentage"
+                  type="number"
+                  min="0.01"
+                  step="0.01"
+                  max={userEquity / 100}
+                  value={percentage}
+                  onChange={(e) => setPercentage(e.target.value)}
+                  placeholder="20"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+
+              <div>
+                <Label htmlFor="amount" className="text-executive-lightGray">
+                  ckUSDC Amount
+                </Label>
+                <Input
+                  id="amount"
+                  type="number"
+                  min="0"
+                  value={ckusdcAmount}
+                  onChange={(e) => setCkusdcAmount(e.target.value)}
+                  placeholder="1000"
+                  className="bg-executive-mediumGray border-executive-gold/30 text-executive-ivory"
+                />
+              </div>
+            </div>
+
+            <div>
+              <Label className="text-executive-lightGray">Payment Destination</Label>
               <div className="space-y-2 mt-2">
                 <label className="flex items-center space-x-2">
                   <input
@@ -442,22 +1265,49 @@ export default function DaoEquity() {
 }
 
 // Proposal Card Component
-function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExecute }: any) {
+function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExecute, onDeposit, onCancel, onExercise, userEquity }: any) {
   const status = proposal.status;
   const isProposed = 'Proposed' in status;
   const isApproved = 'Approved' in status;
   const isExecuted = 'Executed' in status;
   const isExpired = 'Expired' in status;
+  const isRejected = 'Rejected' in status;
+  const isCancelled = 'Cancelled' in status;
 
   const isBuyer = userPrincipal && proposal.buyer.toText() === userPrincipal.toText();
-  const canExecute = isAuthenticated && isBuyer && isApproved;
+  const isSeller = userPrincipal && proposal.seller.toText() === userPrincipal.toText();
+  const isClaim = proposal.rofr_parent.length > 0;
+  const canCancel = isAuthenticated && isSeller && isProposed && !isClaim;
+
+  // Right of first refusal: holders can take up the offer until the window closes,
+  // and only then can the outside buyer pay and execute
+  const rofr = proposal.rofr.length > 0 ? proposal.rofr[0] : null;
+  const rofrEndsAt = rofr ? new Date(Number(BigInt(rofr.ends_at) / 1_000_000n)) : null;
+  const rofrOpen = rofrEndsAt !== null && rofrEndsAt.getTime() > Date.now();
+  const canExercise = isAuthenticated && rofrOpen && !isSeller && userEquity > 0 && (isProposed || isApproved);
+  const [exercisePercentage, setExercisePercentage] = useState('');
+  // Payment is escrowed before execution (free transfers need no escrow)
+  const needsEscrow = BigInt(proposal.ckusdc_amount) > 0n;
+  const [escrow, setEscrow] = useState<any | null>(null);
+  useEffect(() => {
+    if (!needsEscrow) return;
+    getAdminService().getEquityEscrow(proposal.proposal_id)
+      .then(setEscrow)
+      .catch((err) => console.error('Failed to load escrow:', err));
+  }, [proposal, needsEscrow]);
+
+  const escrowStatus = escrow ? Object.keys(escrow.status)[0] : null;
+  const canDeposit = isAuthenticated && isBuyer && needsEscrow && !escrow && (isProposed || isApproved) && !rofrOpen;
+  const canExecute = isAuthenticated && isBuyer && isApproved && !rofrOpen && (!needsEscrow || escrowStatus === 'Held');
 
   const statusColor = isExecuted
     ? 'bg-green-500/20 text-green-400'
     : isApproved
     ? 'bg-blue-500/20 text-blue-400'
-    : isExpired
+    : isExpired || isRejected
     ? 'bg-red-500/20 text-red-400'
+    : isCancelled
+    ? 'bg-gray-500/20 text-gray-400'
     : 'bg-yellow-500/20 text-yellow-400';
 
   return (
@@ -482,7 +1332,7 @@ function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExec
               </div>
             </div>
             <Badge className={statusColor}>
-              {isProposed ? 'Proposed' : isApproved ? 'Approved' : isExecuted ? 'Executed' : 'Expired'}
+              {Object.keys(status)[0]}
             </Badge>
           </div>
 
@@ -490,7 +1340,7 @@ function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExec
           <div className="grid grid-cols-2 gap-4 text-sm">
             <div>
               <span className="text-executive-lightGray/70">Percentage:</span>
-              <span className="ml-2 text-executive-gold font-semibold">{proposal.percentage}%</span>
+              <span className="ml-2 text-executive-gold font-semibold">{formatBps(proposal.equity_bps)}</span>
             </div>
             <div>
               <span className="text-executive-lightGray/70">Amount:</span>
@@ -498,26 +1348,61 @@ function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExec
             </div>
           </div>
 
+          {isClaim && (
+            <div className="text-xs text-executive-lightGray/70">
+              Right of first refusal claim, decided by the vote on the original offer
+            </div>
+          )}
+
+          {rofr && (
+            <div className="text-xs text-executive-lightGray/70">
+              Right of first refusal on {formatBps(rofr.offered_bps)} for {rofr.offered_ckusdc.toString()} ckUSDC
+              {rofrOpen ? ` — holders can take it up until ${rofrEndsAt!.toLocaleString()}` : ' — window closed'}
+            </div>
+          )}
+
+          {canExercise && (
+            <div className="flex gap-2">
+              <Input
+                type="number"
+                min="0.01"
+                step="0.01"
+                value={exercisePercentage}
+                onChange={(e) => setExercisePercentage(e.target.value)}
+                placeholder="Percentage to take up"
+                className="bg-executive-charcoal border-executive-gold/30 text-executive-ivory"
+              />
+              <Button
+                onClick={() => onExercise(proposal.proposal_id, Math.round(parseFloat(exercisePercentage) * 100))}
+                disabled={!(parseFloat(exercisePercentage) > 0)}
+                variant="outline"
+                className="border-executive-gold/50 text-executive-gold"
+              >
+                Exercise Right
+              </Button>
+            </div>
+          )}
+
           {/* Vote Tally */}
           <div className="space-y-2">
             <div className="flex justify-between text-sm">
               <span className="text-executive-lightGray/70">Yes Votes:</span>
-              <span className="text-green-400">{proposal.yes_votes_pct}%</span>
+              <span className="text-green-400">{formatBps(proposal.yes_votes_bps)}</span>
             </div>
             <div className="flex justify-between text-sm">
               <span className="text-executive-lightGray/70">No Votes:</span>
-              <span className="text-red-400">{proposal.no_votes_pct}%</span>
+              <span className="text-red-400">{formatBps(proposal.no_votes_bps)}</span>
             </div>
             <div className="h-2 bg-executive-charcoal rounded-full overflow-hidden">
               <div
                 className="h-full bg-green-500"
-                style={{ width: `${proposal.yes_votes_pct}%` }}
+                style={{ width: `${Number(proposal.yes_votes_bps) / 100}%` }}
               />
             </div>
           </div>
 
           {/* Actions */}
-          {isProposed && isAuthenticated && (
+          {isProposed && isAuthenticated && !isClaim && (
             <div className="flex gap-2">
               <Button
                 onClick={() => onVote(proposal.proposal_id, true)}
@@ -539,6 +1424,33 @@ function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExec
             </div>
           )}
 
+          {needsEscrow && (
+            <div className="text-xs text-executive-lightGray/70">
+              Escrow: {escrowStatus ?? 'Awaiting buyer deposit'}
+            </div>
+          )}
+
+          {canDeposit && (
+            <Button
+              onClick={() => onDeposit(proposal)}
+              variant="outline"
+              className="w-full border-executive-gold/50 text-executive-gold"
+            >
+              Deposit {proposal.ckusdc_amount.toString()} ckUSDC into Escrow
+            </Button>
+          )}
+
+          {canCancel && (
+            <Button
+              onClick={() => onCancel(proposal.proposal_id)}
+              variant="outline"
+              size="sm"
+              className="w-full border-executive-lightGray/30 text-executive-lightGray"
+            >
+              Cancel Proposal
+            </Button>
+          )}
+
           {canExecute && (
             <Button
               onClick={() => onExecute(proposal.proposal_id)}
@@ -552,3 +1464,409 @@ function ProposalCard({ proposal, userPrincipal, isAuthenticated, onVote, onExec
     </Card>
   );
 }
+
+// Issuance Card Component
+function IssuanceCard({ proposal, userPrincipal, isAuthenticated, onVote, onExecute, onDeposit, onCancel }: any) {
+  const status = proposal.status;
+  const statusName = Object.keys(status)[0];
+  const isProposed = 'Proposed' in status;
+  const isApproved = 'Approved' in status;
+
+  const isRecipient = userPrincipal && proposal.recipient.toText() === userPrincipal.toText();
+  const isProposer = userPrincipal && proposal.proposer.toText() === userPrincipal.toText();
+  const needsEscrow = BigInt(proposal.ckusdc_amount) > 0n;
+  const [escrow, setEscrow] = useState<any | null>(null);
+  useEffect(() => {
+    if (!needsEscrow) return;
+    getAdminService().getEquityEscrow(proposal.proposal_id)
+      .then(setEscrow)
+      .catch((err) => console.error('Failed to load escrow:', err));
+  }, [proposal, needsEscrow]);
+
+  const escrowStatus = escrow ? Object.keys(escrow.status)[0] : null;
+  const canDeposit = isAuthenticated && isRecipient && needsEscrow && !escrow && (isProposed || isApproved);
+  const canCancel = isAuthenticated && isProposer && isProposed;
+  const canExecute = isAuthenticated && (isRecipient || isProposer) && isApproved
+    && (!needsEscrow || escrowStatus === 'Held');
+
+  return (
+    <Card className="bg-executive-mediumGray border-executive-gold/10">
+      <CardContent className="pt-6">
+        <div className="space-y-4">
+          <div className="flex justify-between items-start">
+            <div className="space-y-1">
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Recipient:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {proposal.recipient.toText().slice(0, 20)}...
+                </span>
+              </div>
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Proposed by:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {proposal.proposer.toText().slice(0, 20)}...
+                </span>
+              </div>
+            </div>
+            <Badge variant="secondary">{statusName}</Badge>
+          </div>
+
+          <div className="grid grid-cols-2 gap-4 text-sm">
+            <div>
+              <span className="text-executive-lightGray/70">New Equity:</span>
+              <span className="ml-2 text-executive-gold font-semibold">{formatBps(proposal.equity_bps)}</span>
+            </div>
+            <div>
+              <span className="text-executive-lightGray/70">Subscription:</span>
+              <span className="ml-2 text-executive-lightGray">{proposal.ckusdc_amount.toString()} ckUSDC</span>
+            </div>
+          </div>
+
+          {proposal.vesting.length > 0 && (
+            <div className="text-xs text-executive-lightGray/70">
+              Vests over {(BigInt(proposal.vesting[0].duration_seconds) / SECONDS_PER_MONTH).toString()} months
+              with a {(BigInt(proposal.vesting[0].cliff_seconds) / SECONDS_PER_MONTH).toString()} month cliff
+            </div>
+          )}
+
+          <div className="flex justify-between text-sm">
+            <span className="text-green-400">Yes {formatBps(proposal.yes_votes_bps)}</span>
+            <span className="text-red-400">No {formatBps(proposal.no_votes_bps)}</span>
+          </div>
+
+          {isProposed && isAuthenticated && (
+            <div className="flex gap-2">
+              <Button
+                onClick={() => onVote(proposal.proposal_id, true)}
+                size="sm"
+                className="flex-1 bg-green-600 hover:bg-green-700"
+              >
+                <Check className="mr-2 h-4 w-4" />
+                Vote Yes
+              </Button>
+              <Button
+                onClick={() => onVote(proposal.proposal_id, false)}
+                size="sm"
+                variant="outline"
+                className="flex-1 border-red-500/50 text-red-400 hover:bg-red-500/10"
+              >
+                <X className="mr-2 h-4 w-4" />
+                Vote No
+              </Button>
+            </div>
+          )}
+
+          {needsEscrow && (
+            <div className="text-xs text-executive-lightGray/70">
+              Escrow: {escrowStatus ?? 'Awaiting recipient deposit'}
+            </div>
+          )}
+
+          {canDeposit && (
+            <Button
+              onClick={() => onDeposit(proposal)}
+              variant="outline"
+              className="w-full border-executive-gold/50 text-executive-gold"
+            >
+              Deposit {proposal.ckusdc_amount.toString()} ckUSDC Subscription
+            </Button>
+          )}
+
+          {canCancel && (
+            <Button
+              onClick={() => onCancel(proposal.proposal_id)}
+              variant="outline"
+              size="sm"
+              className="w-full border-executive-lightGray/30 text-executive-lightGray"
+            >
+              Cancel Proposal
+            </Button>
+          )}
+
+          {canExecute && (
+            <Button
+              onClick={() => onExecute(proposal.proposal_id)}
+              className="w-full bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              Execute Issuance
+            </Button>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
+
+// Dividend Card Component
+function DividendCard({ proposal, userPrincipal, isAuthenticated, onVote, onExecute, onCancel }: any) {
+  const status = proposal.status;
+  const statusName = Object.keys(status)[0];
+  const isProposed = 'Proposed' in status;
+  const isApproved = 'Approved' in status;
+
+  const isProposer = userPrincipal && proposal.proposer.toText() === userPrincipal.toText();
+  const isPayee = userPrincipal && proposal.payouts.some(
+    (payout: any) => payout.holder.toText() === userPrincipal.toText()
+  );
+  const canCancel = isAuthenticated && isProposer && isProposed;
+  const canExecute = isAuthenticated && (isProposer || isPayee) && isApproved;
+  const startedPaying = proposal.payouts.some((payout: any) => !('Pending' in payout.status));
+
+  return (
+    <Card className="bg-executive-mediumGray border-executive-gold/10">
+      <CardContent className="pt-6">
+        <div className="space-y-4">
+          <div className="flex justify-between items-start">
+            <div className="space-y-1">
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Total:</span>
+                <span className="text-executive-gold font-semibold">{proposal.total_amount.toString()}</span>
+              </div>
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Proposed by:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {proposal.proposer.toText().slice(0, 20)}...
+                </span>
+              </div>
+            </div>
+            <Badge variant="secondary">{statusName}</Badge>
+          </div>
+
+          <div className="space-y-1 text-xs">
+            {proposal.payouts.map((payout: any) => (
+              <div key={payout.holder.toText()} className="flex justify-between text-executive-lightGray/70">
+                <span className="font-mono">
+                  {payout.holder.toText().slice(0, 20)}... ({formatBps(payout.equity_bps)})
+                </span>
+                <span>
+                  {payout.amount.toString()} · {Object.keys(payout.status)[0]}
+                </span>
+              </div>
+            ))}
+          </div>
+
+          <div className="flex justify-between text-sm">
+            <span className="text-green-400">Yes {formatBps(proposal.yes_votes_bps)}</span>
+            <span className="text-red-400">No {formatBps(proposal.no_votes_bps)}</span>
+          </div>
+
+          {isProposed && isAuthenticated && (
+            <div className="flex gap-2">
+              <Button
+                onClick={() => onVote(proposal.proposal_id, true)}
+                size="sm"
+                className="flex-1 bg-green-600 hover:bg-green-700"
+              >
+                <Check className="mr-2 h-4 w-4" />
+                Vote Yes
+              </Button>
+              <Button
+                onClick={() => onVote(proposal.proposal_id, false)}
+                size="sm"
+                variant="outline"
+                className="flex-1 border-red-500/50 text-red-400 hover:bg-red-500/10"
+              >
+                <X className="mr-2 h-4 w-4" />
+                Vote No
+              </Button>
+            </div>
+          )}
+
+          {canCancel && (
+            <Button
+              onClick={() => onCancel(proposal.proposal_id)}
+              variant="outline"
+              size="sm"
+              className="w-full border-executive-lightGray/30 text-executive-lightGray"
+            >
+              Cancel Proposal
+            </Button>
+          )}
+
+          {canExecute && (
+            <Button
+              onClick={() => onExecute(proposal.proposal_id)}
+              className="w-full bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              {startedPaying ? 'Retry Unpaid Holders' : 'Pay Dividend'}
+            </Button>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
+
+// Conversion Card Component
+function ConversionCard({ proposal, userPrincipal, isAuthenticated, onVote, onExecute, onCancel }: any) {
+  const status = proposal.status;
+  const statusName = Object.keys(status)[0];
+  const isProposed = 'Proposed' in status;
+  const isApproved = 'Approved' in status;
+
+  const isProposer = userPrincipal && proposal.proposer.toText() === userPrincipal.toText();
+  const canCancel = isAuthenticated && isProposer && isProposed;
+  const canExecute = isAuthenticated && isApproved;
+
+  return (
+    <Card className="bg-executive-mediumGray border-executive-gold/10">
+      <CardContent className="pt-6">
+        <div className="space-y-4">
+          <div className="flex justify-between items-start">
+            <div className="space-y-1">
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Token:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {proposal.token_canister_id.toText()}
+                </span>
+              </div>
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Proposed by:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {proposal.proposer.toText().slice(0, 20)}...
+                </span>
+              </div>
+            </div>
+            <Badge variant="secondary">{statusName}</Badge>
+          </div>
+
+          <div className="flex justify-between text-sm">
+            <span className="text-green-400">Yes {formatBps(proposal.yes_votes_bps)}</span>
+            <span className="text-red-400">No {formatBps(proposal.no_votes_bps)}</span>
+          </div>
+
+          {isProposed && isAuthenticated && (
+            <div className="flex gap-2">
+              <Button
+                onClick={() => onVote(proposal.proposal_id, true)}
+                size="sm"
+                className="flex-1 bg-green-600 hover:bg-green-700"
+              >
+                <Check className="mr-2 h-4 w-4" />
+                Vote Yes
+              </Button>
+              <Button
+                onClick={() => onVote(proposal.proposal_id, false)}
+                size="sm"
+                variant="outline"
+                className="flex-1 border-red-500/50 text-red-400 hover:bg-red-500/10"
+              >
+                <X className="mr-2 h-4 w-4" />
+                Vote No
+              </Button>
+            </div>
+          )}
+
+          {canCancel && (
+            <Button
+              onClick={() => onCancel(proposal.proposal_id)}
+              variant="outline"
+              size="sm"
+              className="w-full border-executive-lightGray/30 text-executive-lightGray"
+            >
+              Cancel Proposal
+            </Button>
+          )}
+
+          {canExecute && (
+            <Button
+              onClick={() => onExecute(proposal.proposal_id)}
+              className="w-full bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+            >
+              Convert Station
+            </Button>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
+
+// Sell Order Card Component
+function SellOrderCard({ listing, userPrincipal, isAuthenticated, onAccept, onCancel }: any) {
+  const order = listing.order;
+  const isSeller = userPrincipal && order.seller.toText() === userPrincipal.toText();
+  const [buyPercentage, setBuyPercentage] = useState('');
+
+  // Price per whole percent, for comparing offers
+  const pricePerPercent = Number(order.ckusdc_amount) * 100 / order.equity_bps;
+  const buyBps = Math.round(parseFloat(buyPercentage) * 100);
+  const canBuy = !isNaN(buyBps) && buyBps >= 1 && buyBps <= listing.available_bps;
+
+  return (
+    <Card className="bg-executive-mediumGray border-executive-gold/10">
+      <CardContent className="pt-6">
+        <div className="space-y-4">
+          <div className="flex justify-between items-start">
+            <div className="space-y-1">
+              <div className="flex items-center gap-2">
+                <span className="text-executive-lightGray/70 text-sm">Seller:</span>
+                <span className="font-mono text-sm text-executive-lightGray">
+                  {order.seller.toText().slice(0, 20)}...
+                </span>
+              </div>
+              <div className="text-xs text-executive-lightGray/70">
+                Open until {new Date(Number(order.expires_at / 1_000_000n)).toLocaleDateString()}
+              </div>
+            </div>
+            <Badge variant="secondary">{formatBps(listing.available_bps)} available</Badge>
+          </div>
+
+          <div className="grid grid-cols-2 gap-4 text-sm">
+            <div>
+              <span className="text-executive-lightGray/70">Offer:</span>
+              <span className="ml-2 text-executive-gold font-semibold">
+                {formatBps(order.equity_bps)} for {order.ckusdc_amount.toString()} ckUSDC
+              </span>
+            </div>
+            <div>
+              <span className="text-executive-lightGray/70">Per 1%:</span>
+              <span className="ml-2 text-executive-lightGray">
+                {pricePerPercent.toLocaleString(undefined, { maximumFractionDigits: 2 })} ckUSDC
+              </span>
+            </div>
+          </div>
+
+          {listing.pending_bps > 0 && (
+            <div className="text-xs text-executive-lightGray/70">
+              {formatBps(listing.pending_bps)} awaiting approval, {formatBps(listing.filled_bps)} sold
+            </div>
+          )}
+
+          {isAuthenticated && !isSeller && (
+            <div className="flex gap-2">
+              <Input
+                type="number"
+                min="0.01"
+                step="0.01"
+                max={listing.available_bps / 100}
+                value={buyPercentage}
+                onChange={(e) => setBuyPercentage(e.target.value)}
+                placeholder={`Up to ${listing.available_bps / 100}%`}
+                className="bg-executive-darkGray border-executive-gold/30 text-executive-ivory"
+              />
+              <Button
+                onClick={() => onAccept(order.order_id, buyBps)}
+                disabled={!canBuy}
+                className="bg-executive-gold text-executive-charcoal hover:bg-executive-goldLight"
+              >
+                Buy
+              </Button>
+            </div>
+          )}
+
+          {isAuthenticated && isSeller && (
+            <Button
+              onClick={() => onCancel(order.order_id)}
+              variant="outline"
+              size="sm"
+              className="w-full border-executive-lightGray/30 text-executive-lightGray"
+            >
+              Withdraw Offer
+            </Button>
+          )}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -43,7 +43,7 @@ export default function DaoTreasury() {
       {isAuthenticated && (
         <div className="space-y-4">
           <h3 className="text-xl font-semibold text-executive-ivory">Saved Addresses</h3>
-          <AddressBookPage identity={identity} />
+          <AddressBookPage identity={identity} tokenId={token.canister_id} />
         </div>
       )}
     </div>
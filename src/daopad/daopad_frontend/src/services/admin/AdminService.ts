@@ -7,19 +7,60 @@ import type { _SERVICE } from '../../declarations/admin/admin.did.d';
 // Admin canister ID (hardcoded as per backend)
 const ADMIN_CANISTER_ID = 'odkrm-viaaa-aaaap-qp2oq-cai';
 
+// ckUSDC ledger - equity escrow deposits are pulled with ICRC-2 transfer_from
+const CKUSDC_LEDGER_ID = 'xevnm-gaaaa-aaaar-qafnq-cai';
+
 // Use the generated type from Candid declarations
 type AdminActor = _SERVICE;
 
+// IDL for the ICRC-2 calls needed to approve an escrow deposit
+const icrc2Idl = ({ IDL }) => {
+  const Account = IDL.Record({
+    'owner': IDL.Principal,
+    'subaccount': IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const ApproveArgs = IDL.Record({
+    'fee': IDL.Opt(IDL.Nat),
+    'memo': IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'from_subaccount': IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'created_at_time': IDL.Opt(IDL.Nat64),
+    'amount': IDL.Nat,
+    'expected_allowance': IDL.Opt(IDL.Nat),
+    'expires_at': IDL.Opt(IDL.Nat64),
+    'spender': Account,
+  });
+  const ApproveError = IDL.Variant({
+    'GenericError': IDL.Record({ 'message': IDL.Text, 'error_code': IDL.Nat }),
+    'TemporarilyUnavailable': IDL.Null,
+    'Duplicate': IDL.Record({ 'duplicate_of': IDL.Nat }),
+    'BadFee': IDL.Record({ 'expected_fee': IDL.Nat }),
+    'AllowanceChanged': IDL.Record({ 'current_allowance': IDL.Nat }),
+    'CreatedInFuture': IDL.Record({ 'ledger_time': IDL.Nat64 }),
+    'TooOld': IDL.Null,
+    'Expired': IDL.Record({ 'ledger_time': IDL.Nat64 }),
+    'InsufficientFunds': IDL.Record({ 'balance': IDL.Nat }),
+  });
+  return IDL.Service({
+    'icrc1_fee': IDL.Func([], [IDL.Nat], ['query']),
+    'icrc2_approve': IDL.Func(
+      [ApproveArgs],
+      [IDL.Variant({ 'Ok': IDL.Nat, 'Err': ApproveError })],
+      []
+    ),
+  });
+};
+
 export class AdminService {
   private actor: ActorSubclass<AdminActor> | null = null;
+  private agent: HttpAgent | null = null;
   private identity: any;
 
   constructor(identity?: any) {
     this.identity = identity;
   }
 
-  async getActor(): Promise<ActorSubclass<AdminActor>> {
-    if (!this.actor) {
+  private async getAgent(): Promise<HttpAgent> {
+    if (!this.agent) {
       // Use provided identity or get from auth client
       let actualIdentity = this.identity;
 
@@ -39,9 +80,17 @@ export class AdminService {
         await agent.fetchRootKey();
       }
 
+      this.agent = agent;
+    }
+
+    return this.agent;
+  }
+
+  async getActor(): Promise<ActorSubclass<AdminActor>> {
+    if (!this.actor) {
       // Create actor using generated IDL factory
       this.actor = Actor.createActor(idlFactory, {
-        agent,
+        agent: await this.getAgent(),
         canisterId: Principal.fromText(ADMIN_CANISTER_ID),
       });
     }
@@ -129,7 +178,7 @@ export class AdminService {
   async createEquityTransferProposal(
     stationId: string,
     buyer: Principal,
-    percentage: number,
+    equityBps: number,
     ckusdcAmount: bigint,
     paymentDestination: { SellerAccount: string } | { StationTreasury: Principal }
   ): Promise<string> {
@@ -137,7 +186,7 @@ export class AdminService {
     const result = await actor.create_equity_transfer_proposal(
       Principal.fromText(stationId),
       buyer,
-      percentage,
+      equityBps,
       ckusdcAmount,
       paymentDestination
     );
@@ -149,6 +198,56 @@ export class AdminService {
     return result.Ok;
   }
 
+  // Standing offer anyone can accept until expiresAtNanos
+  async createSellOrder(
+    stationId: string,
+    equityBps: number,
+    ckusdcAmount: bigint,
+    paymentDestination: { SellerAccount: string } | { StationTreasury: Principal },
+    expiresAtNanos: bigint
+  ): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.create_sell_order(
+      Principal.fromText(stationId),
+      equityBps,
+      ckusdcAmount,
+      paymentDestination,
+      expiresAtNanos
+    );
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async cancelSellOrder(orderId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.cancel_sell_order(orderId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  // Opens a transfer proposal from the seller to the caller
+  async acceptSellOrder(orderId: string, equityBps: number): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.accept_sell_order(orderId, equityBps);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async getOpenSellOrders(stationId: string): Promise<any[]> {
+    const actor = await this.getActor();
+    return await actor.get_open_sell_orders(Principal.fromText(stationId));
+  }
+
   async voteOnEquityTransfer(proposalId: string, approve: boolean): Promise<void> {
     const actor = await this.getActor();
     const result = await actor.vote_on_equity_transfer(proposalId, approve);
@@ -158,6 +257,27 @@ export class AdminService {
     }
   }
 
+  async cancelEquityTransfer(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.cancel_equity_transfer(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  // Take up part of an outside offer at the same price; returns the new transfer's ID
+  async exerciseRightOfFirstRefusal(proposalId: string, equityBps: number): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.exercise_right_of_first_refusal(proposalId, equityBps);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
   async executeEquityTransfer(proposalId: string): Promise<void> {
     const actor = await this.getActor();
     const result = await actor.execute_equity_transfer(proposalId);
@@ -177,6 +297,231 @@ export class AdminService {
     const result = await actor.get_equity_transfer_proposal(proposalId);
     return result.length > 0 ? result[0] : null;
   }
+
+  async createEquityIssuanceProposal(
+    stationId: string,
+    recipient: Principal,
+    equityBps: number,
+    ckusdcAmount: bigint,
+    vesting: { start_at: bigint; cliff_seconds: bigint; duration_seconds: bigint } | null = null
+  ): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.create_equity_issuance_proposal(
+      Principal.fromText(stationId),
+      recipient,
+      equityBps,
+      ckusdcAmount,
+      vesting ? [vesting] : []
+    );
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async voteOnEquityIssuance(proposalId: string, approve: boolean): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.vote_on_equity_issuance(proposalId, approve);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async cancelEquityIssuance(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.cancel_equity_issuance(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async executeEquityIssuance(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.execute_equity_issuance(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async getEquityIssuanceProposals(stationId: string): Promise<any[]> {
+    const actor = await this.getActor();
+    return await actor.get_equity_issuance_proposals(Principal.fromText(stationId));
+  }
+
+  // Pay `totalAmount` (smallest units) of an Orbit treasury asset to holders pro rata
+  async createDividendProposal(
+    stationId: string,
+    fromAccountId: string,
+    fromAssetId: string,
+    totalAmount: bigint,
+    recordDateNanos: bigint | null = null
+  ): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.create_dividend_proposal(
+      Principal.fromText(stationId),
+      fromAccountId,
+      fromAssetId,
+      totalAmount,
+      recordDateNanos !== null ? [recordDateNanos] : []
+    );
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async voteOnDividend(proposalId: string, approve: boolean): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.vote_on_dividend(proposalId, approve);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async cancelDividend(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.cancel_dividend(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  // Creates and approves the Orbit transfers; call again to retry unpaid holders
+  async executeDividend(proposalId: string): Promise<any> {
+    const actor = await this.getActor();
+    const result = await actor.execute_dividend(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async getDividendProposals(stationId: string): Promise<any[]> {
+    const actor = await this.getActor();
+    return await actor.get_dividend_proposals(Principal.fromText(stationId));
+  }
+
+  // Propose handing the station's governance to a token's Kong Locker holders
+  async createEquityConversionProposal(stationId: string, tokenCanisterId: Principal): Promise<string> {
+    const actor = await this.getActor();
+    const result = await actor.create_equity_conversion_proposal(Principal.fromText(stationId), tokenCanisterId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+
+    return result.Ok;
+  }
+
+  async voteOnEquityConversion(proposalId: string, approve: boolean): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.vote_on_equity_conversion(proposalId, approve);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async cancelEquityConversion(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.cancel_equity_conversion(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async executeEquityConversion(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.execute_equity_conversion(proposalId);
+
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async getEquityConversionProposals(stationId: string): Promise<any[]> {
+    const actor = await this.getActor();
+    return await actor.get_equity_conversion_proposals(Principal.fromText(stationId));
+  }
+
+  async getStationConversions(stationId: string): Promise<any[]> {
+    const actor = await this.getActor();
+    return await actor.get_station_conversions(Principal.fromText(stationId));
+  }
+
+  // Cap table as it stood at a time in nanoseconds (null before the station had one)
+  async getCapTableAt(stationId: string, timestampNanos: bigint): Promise<any | null> {
+    const actor = await this.getActor();
+    const result = await actor.get_cap_table_at(Principal.fromText(stationId), timestampNanos);
+    return result.length > 0 ? result[0] : null;
+  }
+
+  // Download link for the cap table export (served uncertified, so via the raw domain)
+  capTableExportUrl(stationId: string, format: 'csv' | 'json' | 'history'): string {
+    const path = format === 'history' ? `${stationId}/history.json` : `${stationId}.${format}`;
+    return `https://${ADMIN_CANISTER_ID}.raw.icp0.io/cap-table/${path}`;
+  }
+
+  async getVestingSchedule(stationId: string, holder: Principal): Promise<any> {
+    const actor = await this.getActor();
+    return await actor.get_vesting_schedule(Principal.fromText(stationId), holder);
+  }
+
+  // Approve the admin canister for amount + 2 fees (transfer_from and payout), then deposit
+  async depositEquityEscrow(proposalId: string, ckusdcAmount: bigint): Promise<any> {
+    const ledger: any = Actor.createActor(icrc2Idl, {
+      agent: await this.getAgent(),
+      canisterId: Principal.fromText(CKUSDC_LEDGER_ID),
+    });
+
+    const fee: bigint = await ledger.icrc1_fee();
+    const approval = await ledger.icrc2_approve({
+      fee: [],
+      memo: [],
+      from_subaccount: [],
+      created_at_time: [],
+      amount: ckusdcAmount + fee * 2n,
+      expected_allowance: [],
+      expires_at: [],
+      spender: { owner: Principal.fromText(ADMIN_CANISTER_ID), subaccount: [] },
+    });
+    if ('Err' in approval) {
+      throw new Error(`ckUSDC approval failed: ${JSON.stringify(approval.Err, (_, v) => typeof v === 'bigint' ? v.toString() : v)}`);
+    }
+
+    const actor = await this.getActor();
+    const result = await actor.deposit_equity_escrow(proposalId);
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+    return result.Ok;
+  }
+
+  async refundEquityEscrow(proposalId: string): Promise<void> {
+    const actor = await this.getActor();
+    const result = await actor.refund_equity_escrow(proposalId);
+    if ('Err' in result) {
+      throw new Error(result.Err);
+    }
+  }
+
+  async getEquityEscrow(proposalId: string): Promise<any | null> {
+    const actor = await this.getActor();
+    const result = await actor.get_equity_escrow(proposalId);
+    return result.length > 0 ? result[0] : null;
+  }
 }
 
 // Export singleton instance for convenience
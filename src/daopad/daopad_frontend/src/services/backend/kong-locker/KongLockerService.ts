@@ -1,21 +1,6 @@
 import { BackendServiceBase } from '../base/BackendServiceBase';
 
 export class KongLockerService extends BackendServiceBase {
-  /**
-   * Register with Kong Locker
-   */
-  async register(kongLockerPrincipal) {
-    try {
-      const actor = await this.getActor();
-      const principal = this.toPrincipal(kongLockerPrincipal);
-      const result = await actor.register_with_kong_locker(principal);
-      return this.wrapResult(result);
-    } catch (error) {
-      console.error('Failed to register with Kong Locker:', error);
-      return { success: false, error: error.message };
-    }
-  }
-
   /**
    * Get my Kong Locker canister
    */
@@ -30,20 +15,6 @@ export class KongLockerService extends BackendServiceBase {
     }
   }
 
-  /**
-   * Unregister from Kong Locker
-   */
-  async unregister() {
-    try {
-      const actor = await this.getActor();
-      const result = await actor.unregister_kong_locker();
-      return this.wrapResult(result);
-    } catch (error) {
-      console.error('Failed to unregister Kong Locker:', error);
-      return { success: false, error: error.message };
-    }
-  }
-
   /**
    * List all Kong Locker registrations (admin)
    */
@@ -51,7 +22,7 @@ export class KongLockerService extends BackendServiceBase {
     try {
       const actor = await this.getActor();
       const result = await actor.list_all_kong_locker_registrations();
-      return { success: true, data: result };
+      return this.wrapResult(result);
     } catch (error) {
       console.error('Failed to list registrations:', error);
       return { success: false, error: error.message };
@@ -60,12 +31,25 @@ export class KongLockerService extends BackendServiceBase {
 
   /**
    * List all tokens with locked liquidity (for public dashboard)
+   * The backend scans lock canisters a page at a time; walk every page
    */
   async listAllLockedTokens() {
     try {
       const actor = await this.getActor();
-      const result = await actor.list_all_locked_tokens();
-      return this.wrapResult(result);
+      const tokens = new Map();
+      let offset = BigInt(0);
+      for (;;) {
+        const result = await actor.list_all_locked_tokens(offset, BigInt(50));
+        if ('Err' in result) {
+          return this.wrapResult(result);
+        }
+        for (const token of result.Ok.tokens) {
+          tokens.set(token.canister_id, token);
+        }
+        if (result.Ok.next_offset.length === 0) break;
+        offset = result.Ok.next_offset[0];
+      }
+      return { success: true, data: Array.from(tokens.values()) };
     } catch (error) {
       console.error('Failed to list locked tokens:', error);
       return { success: false, error: error.message };
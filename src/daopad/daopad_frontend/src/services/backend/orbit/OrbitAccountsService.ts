@@ -287,6 +287,90 @@ export class OrbitAccountsService extends BackendServiceBase {
       };
     }
   }
+
+  /**
+   * Read an ICRC-1 ledger's symbol, name, decimals and fee the way
+   * addTreasuryAsset would propose it, without proposing anything
+   */
+  async previewTreasuryAsset(ledgerCanisterId, indexCanisterId = null, name = null) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.preview_treasury_asset(
+        this.assetInput(ledgerCanisterId, indexCanisterId, name)
+      );
+      return 'Ok' in result
+        ? { success: true, data: result.Ok }
+        : { success: false, error: result.Err };
+    } catch (error) {
+      console.error('Failed to preview treasury asset:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Propose adding an ICRC-1 ledger as a treasury asset
+   * Returns the Orbit request ID the proposal votes on
+   */
+  async addTreasuryAsset(tokenId, ledgerCanisterId, indexCanisterId = null, name = null) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.add_treasury_asset(
+        this.toPrincipal(tokenId),
+        this.assetInput(ledgerCanisterId, indexCanisterId, name)
+      );
+      return 'Ok' in result
+        ? { success: true, data: result.Ok }
+        : { success: false, error: result.Err };
+    } catch (error) {
+      console.error('Failed to propose treasury asset:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Propose renaming an asset and/or replacing its metadata
+   */
+  async editTreasuryAsset(tokenId, assetId, name = null, metadata = null) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.edit_treasury_asset(
+        this.toPrincipal(tokenId),
+        assetId,
+        name ? [name] : [],
+        metadata ? [metadata] : []
+      );
+      return 'Ok' in result
+        ? { success: true, data: result.Ok }
+        : { success: false, error: result.Err };
+    } catch (error) {
+      console.error('Failed to propose asset edit:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Propose removing an asset from the treasury
+   */
+  async removeTreasuryAsset(tokenId, assetId) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.remove_treasury_asset(this.toPrincipal(tokenId), assetId);
+      return 'Ok' in result
+        ? { success: true, data: result.Ok }
+        : { success: false, error: result.Err };
+    } catch (error) {
+      console.error('Failed to propose asset removal:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  assetInput(ledgerCanisterId, indexCanisterId, name) {
+    return {
+      ledger_canister_id: this.toPrincipal(ledgerCanisterId),
+      index_canister_id: indexCanisterId ? [this.toPrincipal(indexCanisterId)] : [],
+      name: name ? [name] : [],
+    };
+  }
 }
 
 export const getOrbitAccountsService = (identity) => {
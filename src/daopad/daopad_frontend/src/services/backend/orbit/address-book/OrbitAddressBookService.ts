@@ -7,9 +7,9 @@ const MAX_LIST_ENTRIES_LIMIT = 1000;
 
 export class OrbitAddressBookService extends BackendServiceBase {
   /**
-   * List address book entries with filtering and pagination
+   * List the address book of the token's Orbit station, with filtering and pagination
    */
-  async listEntries(input = {}) {
+  async listEntries(tokenId, input = {}) {
     try {
       const actor = await this.getActor();
 
@@ -42,7 +42,7 @@ export class OrbitAddressBookService extends BackendServiceBase {
         search_term: input.search_term ? [input.search_term] : []
       };
 
-      const rawResult = await actor.list_address_book_entries(candidInput);
+      const rawResult = await actor.list_address_book_entries(this.toPrincipal(tokenId), candidInput);
 
       if ('Ok' in rawResult) {
         let entries = rawResult.Ok.address_book_entries;
@@ -77,14 +77,14 @@ export class OrbitAddressBookService extends BackendServiceBase {
   /**
    * Get a single address book entry by ID
    */
-  async getEntry(id) {
+  async getEntry(tokenId, id) {
     try {
       if (!this.isValidUUID(id)) {
         throw new Error('Invalid UUID format');
       }
 
       const actor = await this.getActor();
-      const result = await actor.get_address_book_entry({
+      const result = await actor.get_address_book_entry(this.toPrincipal(tokenId), {
         address_book_entry_id: id
       });
 
@@ -99,21 +99,17 @@ export class OrbitAddressBookService extends BackendServiceBase {
   }
 
   /**
-   * Create a new address book entry (creates a request)
+   * Propose a new address book entry (creates a request and its proposal)
    */
-  async createEntry(input) {
+  async createEntry(tokenId, input) {
     try {
       this.validateInput(input);
 
       const actor = await this.getActor();
-      const requestInput = {
-        operation: { AddAddressBookEntry: input },
-        title: [` Add address book entry: ${input.address_owner}`],
-        summary: [],
-        execution_plan: [{ Immediate: null }]
-      };
-
-      const result = await actor.create_address_book_request(requestInput);
+      const result = await actor.create_address_book_request(
+        this.toPrincipal(tokenId),
+        { AddAddressBookEntry: input }
+      );
       return this.wrapResult(result);
     } catch (error) {
       console.error('Error creating address book entry:', error);
@@ -122,9 +118,9 @@ export class OrbitAddressBookService extends BackendServiceBase {
   }
 
   /**
-   * Edit an existing address book entry (creates a request)
+   * Propose an edit to an address book entry (creates a request and its proposal)
    */
-  async editEntry(input) {
+  async editEntry(tokenId, input) {
     try {
       if (!this.isValidUUID(input.address_book_entry_id)) {
         throw new Error('Invalid UUID format');
@@ -134,18 +130,14 @@ export class OrbitAddressBookService extends BackendServiceBase {
       const editInput = {
         address_book_entry_id: input.address_book_entry_id,
         address_owner: input.address_owner ? [input.address_owner] : [],
-        labels: input.labels || [],
+        labels: input.labels ? [input.labels] : [],
         change_metadata: input.change_metadata ? [input.change_metadata] : []
       };
 
-      const requestInput = {
-        operation: { EditAddressBookEntry: editInput },
-        title: ['Edit address book entry'],
-        summary: [],
-        execution_plan: [{ Immediate: null }]
-      };
-
-      const result = await actor.create_address_book_request(requestInput);
+      const result = await actor.create_address_book_request(
+        this.toPrincipal(tokenId),
+        { EditAddressBookEntry: editInput }
+      );
       return this.wrapResult(result);
     } catch (error) {
       console.error('Error editing address book entry:', error);
@@ -154,23 +146,19 @@ export class OrbitAddressBookService extends BackendServiceBase {
   }
 
   /**
-   * Remove an address book entry (creates a request)
+   * Propose removing an address book entry (creates a request and its proposal)
    */
-  async removeEntry(id) {
+  async removeEntry(tokenId, id) {
     try {
       if (!this.isValidUUID(id)) {
         throw new Error('Invalid UUID format');
       }
 
       const actor = await this.getActor();
-      const requestInput = {
-        operation: { RemoveAddressBookEntry: { address_book_entry_id: id } },
-        title: ['Remove address book entry'],
-        summary: [],
-        execution_plan: [{ Immediate: null }]
-      };
-
-      const result = await actor.create_address_book_request(requestInput);
+      const result = await actor.create_address_book_request(
+        this.toPrincipal(tokenId),
+        { RemoveAddressBookEntry: { address_book_entry_id: id } }
+      );
       return this.wrapResult(result);
     } catch (error) {
       console.error('Error removing address book entry:', error);
@@ -73,7 +73,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -81,7 +81,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -105,7 +105,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -113,7 +113,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -137,7 +137,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -145,7 +145,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -169,7 +169,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -177,7 +177,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -201,7 +201,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -209,7 +209,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -233,7 +233,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -241,7 +241,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -265,7 +265,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -273,7 +273,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -297,7 +297,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         tokenPrincipal,
         operationInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -305,7 +305,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -332,7 +332,7 @@ export class OrbitCanisterService extends BackendServiceBase {
         canisterPrincipal,
         methodInput,
         title,
-        summary
+        summary ? [summary] : []
       );
 
       if ('Ok' in result) {
@@ -340,7 +340,7 @@ export class OrbitCanisterService extends BackendServiceBase {
       } else {
         return {
           success: false,
-          error: formatOrbitError(result.Err)
+          error: result.Err
         };
       }
     } catch (error) {
@@ -155,6 +155,48 @@ export class OrbitGovernanceService extends BackendServiceBase {
       return { success: false, error: error.message };
     }
   }
+
+  /**
+   * Upload a station or upgrader wasm in chunks and put the upgrade to a vote
+   * The backend checks the module's SHA-256 against the reproducible build hash
+   * @param {string|Principal} tokenId - Token canister ID
+   * @param {Object} upgrade - { target: 'UpgradeStation' | 'UpgradeUpgrader', wasm: Uint8Array,
+   *   expectedSha256: string, sourceCommit: string, arg?: Uint8Array, takeBackupSnapshot?: boolean }
+   * @param {Function} onProgress - Called with (uploadedBytes, totalBytes) after each chunk
+   */
+  async proposeSystemUpgrade(tokenId, upgrade, onProgress = null) {
+    const CHUNK_BYTES = 1_000_000;
+    try {
+      const actor = await this.getActor();
+      const tokenPrincipal = this.toPrincipal(tokenId);
+      const started = await actor.start_system_upgrade_upload(tokenPrincipal, {
+        target: { [upgrade.target]: null },
+        expected_sha256: upgrade.expectedSha256,
+        source_commit: upgrade.sourceCommit,
+        total_size: BigInt(upgrade.wasm.length),
+        arg: upgrade.arg ? [upgrade.arg] : [],
+        take_backup_snapshot: upgrade.takeBackupSnapshot === undefined ? [] : [upgrade.takeBackupSnapshot],
+      });
+      if ('Err' in started) {
+        return { success: false, error: started.Err };
+      }
+
+      const uploadId = started.Ok;
+      for (let offset = 0, index = 0; offset < upgrade.wasm.length; offset += CHUNK_BYTES, index++) {
+        const chunk = upgrade.wasm.slice(offset, offset + CHUNK_BYTES);
+        const uploaded = await actor.upload_system_upgrade_chunk(uploadId, index, chunk);
+        if ('Err' in uploaded) {
+          return { success: false, error: uploaded.Err };
+        }
+        onProgress?.(Number(uploaded.Ok), upgrade.wasm.length);
+      }
+
+      return this.wrapResult(await actor.propose_system_upgrade(uploadId));
+    } catch (error) {
+      console.error('Failed to propose system upgrade:', error);
+      return { success: false, error: error.message };
+    }
+  }
 }
 
 export const getOrbitGovernanceService = (identity) => {
@@ -66,6 +66,79 @@ export class OrbitPermissionService extends BackendServiceBase {
     }
   }
 
+  /**
+   * Propose adding, editing or removing a request policy (70% to pass)
+   * @param {string|Principal} tokenId - Token principal
+   * @param {Object} operation - { AddRequestPolicy } | { EditRequestPolicy } | { RemoveRequestPolicy }
+   * @returns {Promise<{success: boolean, data?: {requestId: string}, error?: string}>}
+   */
+  async createRequestPolicyRequest(tokenId, operation) {
+    try {
+      const actor = await this.getActor();
+      const tokenPrincipal = this.toPrincipal(tokenId);
+
+      const result = await actor.create_request_policy_request(tokenPrincipal, operation);
+
+      if ('Ok' in result) {
+        return {
+          success: true,
+          data: { requestId: result.Ok }
+        };
+      } else {
+        return { success: false, error: result.Err };
+      }
+    } catch (error) {
+      console.error('Failed to create request policy request:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * List the changes a governance profile would propose
+   * @param {string|Principal} tokenId - Token principal
+   * @param {string} profile - Profile name, e.g. 'FullyDecentralized'
+   * @returns {Promise<{success: boolean, data?: Array, error?: string}>}
+   */
+  async previewGovernanceProfile(tokenId, profile = 'FullyDecentralized') {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.preview_governance_profile(this.toPrincipal(tokenId), { [profile]: null });
+      return this.wrapResult(result);
+    } catch (error) {
+      console.error('Failed to preview governance profile:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Propose every change of a governance profile as one batch
+   * @param {string|Principal} tokenId - Token principal
+   * @param {string} profile - Profile name, e.g. 'FullyDecentralized'
+   * @returns {Promise<{success: boolean, data?: Array, error?: string}>}
+   */
+  async applyGovernanceProfile(tokenId, profile = 'FullyDecentralized') {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.apply_governance_profile(this.toPrincipal(tokenId), { [profile]: null });
+      if ('Err' in result) {
+        return { success: false, error: result.Err };
+      }
+      return {
+        success: true,
+        data: result.Ok.map(({ change, result: outcome }) => ({
+          title: change.title,
+          reason: change.reason,
+          success: 'Ok' in outcome,
+          requestId: outcome.Ok,
+          error: outcome.Err
+        }))
+      };
+    } catch (error) {
+      console.error('Failed to apply governance profile:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
   /**
    * Fix all critical permissions by removing Operator group
    * @param {string|Principal} tokenId - Token principal
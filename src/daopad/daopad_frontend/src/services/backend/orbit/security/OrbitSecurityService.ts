@@ -423,6 +423,104 @@ export class OrbitSecurityService extends BackendServiceBase {
       return { success: false, error: error.message };
     }
   }
+
+  /**
+   * Get scheduled security audit results for a station
+   * @param {string|Principal} stationId - Orbit Station ID
+   * @param {bigint} from - Start time in nanoseconds
+   * @param {bigint} to - End time in nanoseconds
+   * @returns {Promise<{success: boolean, data?: Array, error?: string}>}
+   */
+  async getSecurityHistory(stationId, from, to) {
+    try {
+      const actor = await this.getActor();
+      const records = await actor.get_security_history(this.toPrincipal(stationId), BigInt(from), BigInt(to));
+      return { success: true, data: records };
+    } catch (error) {
+      console.error('Failed to get security history:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Get score drops and new Critical checks found by scheduled audits, newest first (100 per page)
+   * @param {string|Principal|null} stationId - Orbit Station ID, or null for every station
+   * @param {bigint|null} before - Only alerts raised before this time (nanoseconds); pass the last alert's raised_at for the next page
+   * @returns {Promise<{success: boolean, data?: Array, error?: string}>}
+   */
+  async getSecurityAlerts(stationId = null, before = null) {
+    try {
+      const actor = await this.getActor();
+      const alerts = await actor.get_security_alerts(
+        stationId ? [this.toPrincipal(stationId)] : [],
+        before !== null ? [BigInt(before)] : []
+      );
+      return { success: true, data: alerts };
+    } catch (error) {
+      console.error('Failed to get security alerts:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Simulate an EditPermission or EditRequestPolicy operation against the station
+   * @param {string|Principal} stationId - Orbit Station ID
+   * @param {Object} operation - Candid OrbitOperation, e.g. { EditPermission: {...} }
+   * @returns {Promise<{success: boolean, data?: Object, error?: string}>} Score and check changes
+   */
+  async previewSecurityImpact(stationId, operation) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.preview_security_impact(this.toPrincipal(stationId), operation);
+      return this.wrapResult(result);
+    } catch (error) {
+      console.error('Failed to preview security impact:', error);
+      return { success: false, error: error.message };
+    }
+  }
+
+  /**
+   * Propose the station changes that fix failed security checks
+   * @param {string|Principal} tokenId - Token principal
+   * @param {string[]} checkNames - Check names to fix; empty means every failing check
+   * @returns {Promise<{success: boolean, data?: Object, error?: string}>}
+   */
+  async generateRemediationProposals(tokenId, checkNames = []) {
+    try {
+      const actor = await this.getActor();
+      const result = await actor.generate_remediation_proposals(this.toPrincipal(tokenId), checkNames);
+      if ('Err' in result) {
+        return { success: false, error: result.Err };
+      }
+      const report = result.Ok;
+      return {
+        success: true,
+        data: {
+          proposals: report.proposals.map(({ check_name, change, result: outcome }) => ({
+            checkName: check_name,
+            title: change.title,
+            reason: change.reason,
+            success: 'Ok' in outcome,
+            requestId: outcome.Ok,
+            error: outcome.Err
+          })),
+          unremediated: report.unremediated.map(({ check_name, reason }) => ({
+            checkName: check_name,
+            reason
+          })),
+          projectedChecks: report.projected_checks.map(check => ({
+            category: check.category,
+            name: check.name,
+            status: Object.keys(check.status)[0],
+            message: check.message
+          }))
+        }
+      };
+    } catch (error) {
+      console.error('Failed to generate remediation proposals:', error);
+      return { success: false, error: error.message };
+    }
+  }
 }
 
 export const getOrbitSecurityService = (identity) => {